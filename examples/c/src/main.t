@@ -0,0 +1,57 @@
+extern "c" fn puts(s: str) i32
+
+fn half(n: i64) result[i64, str]
+    return ok(n / 2)
+
+fn fail(n: i64) result[i64, str]
+    return err("odd {n}")
+
+fn twice(n: i64) result[i64, str]
+    var h: i64 = half(n)?
+    defer
+        puts("deferred twice({n})")
+    return ok(h * 4)
+
+fn propagate(n: i64) result[i64, str]
+    defer
+        puts("deferred propagate")
+    var x: i64 = fail(n)?
+    return ok(x)
+
+fn first(items: []i64) option[i64]
+    return some(items[0])
+
+fn sum(items: []i64) i64
+    var total: i64 = 0
+    for item in items
+        total = total + item
+    return total
+
+fn main()
+    defer
+        puts("bye")
+    var nums: list[i64] = [1, 2, 3]
+    nums.push(4)
+    var copy = nums
+    copy.push(5)
+    var last = nums.pop()
+    puts("{len(copy)} {last} {len(nums)}")
+    var arr: [3]i64 = [10, 20, 30]
+    for x in arr
+        x = x + 1
+    puts("{arr[0]} {arr[2]} {sum(arr)} {sum(nums)} {sum(arr[1..])}")
+    var r = twice(10)
+    puts("{r.unwrap()} {r.is_ok()}")
+    var e = propagate(3)
+    puts("{e.is_err()} {e.error}")
+    var o = first(copy[2..4])
+    puts("{o.unwrap()} {o.is_some()}")
+    var nothing: option[i64] = none
+    puts("{nothing.unwrap_or(7)}")
+    var s = "héllo"
+    puts("{s[1..3]} {len(s)}")
+    for i in 0..3
+        defer
+            puts("loop {i}")
+        puts("in {i}")
+        continue
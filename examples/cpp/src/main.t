@@ -0,0 +1,17 @@
+cpp
+    #include <cstdio>
+
+    // The blocks outside of the functions can call them too.
+    static long long quarter(long long n) { return half(half(n)); }
+
+fn half(x: i64) i64
+    return x / 2
+
+fn main()
+    var n: i64 = 10
+    // `torch_n` is a reserved name in C++, the block names it as the script does.
+    var torch_n: i64 = 40
+    cpp
+        auto m = half(n);
+        std::printf("half(%lld) = %lld, quarter(%lld) = %lld\n", static_cast<long long>(n),
+                    static_cast<long long>(m), static_cast<long long>(torch_n), quarter(torch_n));
@@ -0,0 +1,24 @@
+extern "c" fn puts(s: str) i32
+
+fn early() i64
+    defer
+        puts("first")
+    defer
+        puts("second")
+    // The deferred code runs after the value of `return`, in reverse order.
+    var n: i64 = 1
+    return n
+
+fn main()
+    defer
+        puts("main")
+    puts("returned {early()}")
+    for i in 0..3
+        defer
+            puts("loop first {i}")
+        defer
+            puts("loop second {i}")
+        puts("body {i}")
+        // `break` runs the deferred code of the loop, not the one of `main`.
+        break
+    puts("end")
@@ -0,0 +1,6 @@
+extern "c" fn puts(s: str) i32
+
+fn main()
+    // The functions of a dependency are named by the path of their module.
+    var greeting = mathx.ops.greet("world")
+    puts("{greeting}, 1 + 2 = {mathx.ops.add(1, 2)}")
@@ -0,0 +1,5 @@
+fn add(a: i32, b: i32) i32
+    return a + b
+
+fn greet(name: str) str
+    return "Hello, " + name
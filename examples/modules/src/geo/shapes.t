@@ -0,0 +1,2 @@
+fn area(w: i64, h: i64) i64
+    return w * h
@@ -0,0 +1,6 @@
+extern "c" fn puts(s: str) i32
+
+fn main()
+    // The other scripts of `src/` are modules of the executable.
+    var area: i64 = modules.geo.shapes.area(3, 4)
+    puts("{modules.util.greet("modules")}, area = {area}")
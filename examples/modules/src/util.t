@@ -0,0 +1,2 @@
+fn greet(name: str) str
+    return "Hello, " + name
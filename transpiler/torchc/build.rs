@@ -0,0 +1,44 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// Commit of the compiler (`TORCH_COMMIT`, `TORCH_COMMIT_DATE`) and its target
+/// (`TORCH_HOST`) for `torch version`.
+///
+/// ---
+/// > _Without git or out of a repository the commit is unknown, the build does not
+/// > fail._
+fn main() {
+    println!(
+        "cargo:rustc-env=TORCH_HOST={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+    if let Some(commit) = git(&["rev-parse", "HEAD"]) {
+        println!("cargo:rustc-env=TORCH_COMMIT={}", commit);
+    }
+    if let Some(date) = git(&["log", "-1", "--format=%cs"]) {
+        println!("cargo:rustc-env=TORCH_COMMIT_DATE={}", date);
+    }
+
+    // Built again after a commit or a checkout.
+    if let Some(dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        let dir: &Path = Path::new(&dir);
+        println!("cargo:rerun-if-changed={}", dir.join("HEAD").display());
+        if let Some(head) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            let head: PathBuf = dir.join(head);
+            // A missing file would build the compiler again every time (packed refs).
+            if head.exists() {
+                println!("cargo:rerun-if-changed={}", head.display());
+            }
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// Output of the git command, `None` if it fails.
+fn git(args: &[&str]) -> Option<String> {
+    let output: Output = Command::new("git").args(args).output().ok()?;
+    let stdout: String = String::from_utf8(output.stdout).ok()?.trim().to_owned();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
@@ -0,0 +1,375 @@
+use crate::{
+    cache::{self, Cache},
+    compile::Unit,
+    jobs,
+    manifest::{Kind, Manifest},
+};
+use async_std::{path::PathBuf, task};
+use colored::Colorize;
+use torchc_cgen::{cgen, CGen, Generated};
+use torchc_cli::Verbosity;
+use torchc_diagnosis::panic;
+use torchc_hike::hike;
+use torchc_lits::lits;
+use torchc_parse::parser;
+use torchc_script::Script;
+
+/// Builds of the packages of a command in the same target folder.
+///
+/// ---
+/// > _The modules of a dependency are generated once for all its dependents._
+#[derive(Debug)]
+pub struct Builder<'b> {
+    /// Root of the project, the paths of the generated code are relative to it.
+    root: &'b PathBuf,
+    /// `.target/` or `.target/dev/`
+    target: &'b PathBuf,
+    /// Folder of the executables.
+    out: &'b PathBuf,
+    settings: Settings,
+    /// Modules of the previous builds.
+    pub generated: Vec<Generated>,
+    /// Modules of the dependencies of the previous builds.
+    deps: Vec<Generated>,
+    /// Modules and objects of the previous commands.
+    cache: Cache,
+}
+impl<'b> Builder<'b> {
+    pub fn new(
+        root: &'b PathBuf,
+        target: &'b PathBuf,
+        out: &'b PathBuf,
+        settings: Settings,
+        cache: Cache,
+    ) -> Self {
+        Self {
+            root,
+            target,
+            out,
+            settings,
+            generated: vec![],
+            deps: vec![],
+            cache,
+        }
+    }
+
+    /// Generate the modules of the package and of its dependencies and compile the
+    /// executable or the libraries.
+    pub async fn build(&mut self, manifest: &Manifest, deps: &[Manifest]) -> Vec<PathBuf> {
+        let backend: cgen::Backend = self
+            .settings
+            .backend
+            .or(manifest.backend)
+            .unwrap_or(cgen::Backend::Cpp);
+        let profile: &cgen::Profile = manifest.profile(self.settings.mode);
+
+        // The scripts of the dependencies are modules of their package (`http.main`).
+        let mut scripts: Vec<Job> = vec![];
+        for dep in deps {
+            scripts.extend(self.scripts(dep, true, "dependency").await);
+        }
+        let deps_len: usize = scripts.len();
+
+        match manifest.package.kind {
+            Kind::Bin => {
+                let src: PathBuf = manifest.src().await;
+                let mut path: PathBuf = src.clone();
+                path.push(&manifest.package.entry);
+                if !path.exists().await {
+                    panic!(
+                        "the entry script {} does not exist in the {} folder of {}",
+                        manifest.package.entry.red().bold(),
+                        manifest.package.src.bold(),
+                        manifest.package.name.bold()
+                    );
+                }
+                // The other scripts are modules of the executable (`app.util`).
+                let mut jobs: Vec<Job> = self
+                    .scripts(manifest, self.settings.workspace, "module")
+                    .await;
+                if let Some(job) = jobs.iter_mut().find(|job| job.path == path) {
+                    job.role = None;
+                }
+                scripts.extend(jobs);
+            }
+            // All the scripts are modules, there is no entry.
+            Kind::Lib => {
+                scripts.extend(
+                    self.scripts(manifest, self.settings.workspace, "library")
+                        .await,
+                );
+            }
+        }
+        if let Some(job) = scripts[deps_len..].iter().find(|job| {
+            scripts[..deps_len]
+                .iter()
+                .any(|dep| dep.module == job.module)
+        }) {
+            panic!(
+                "the module {} of {} is also a module of a dependency",
+                job.module.red().bold(),
+                manifest.package.name.bold()
+            );
+        }
+
+        // Each script names the modules of the direct dependencies of its package.
+        let imports = |package: &Manifest| -> cgen::Imports {
+            scripts[..deps_len]
+                .iter()
+                .filter(|job| {
+                    package
+                        .dependencies
+                        .iter()
+                        .any(|dep| job.role.is_some_and(|(_, name)| name == dep.name))
+                })
+                .map(|job| (job.import.clone(), job.module.clone()))
+                .collect()
+        };
+        // And the other modules of its package, the entry of an executable is not one.
+        let name: &str = &manifest.package.name;
+        let same_package = |a: &Job, b: &Job| -> bool {
+            a.role.map_or(name, |(_, name)| name) == b.role.map_or(name, |(_, name)| name)
+        };
+        let imports: Vec<cgen::Imports> = scripts
+            .iter()
+            .map(|job| {
+                let mut imports: cgen::Imports = imports(
+                    deps.iter()
+                        .find(|dep| job.role.is_some_and(|(_, name)| name == dep.package.name))
+                        .unwrap_or(manifest),
+                );
+                imports.extend(
+                    scripts
+                        .iter()
+                        .filter(|other| {
+                            other.role.is_some()
+                                && other.module != job.module
+                                && same_package(other, job)
+                        })
+                        .map(|other| (other.import.clone(), other.module.clone())),
+                );
+                imports
+            })
+            .collect();
+        for (job, imports) in scripts.iter_mut().zip(imports) {
+            job.imports = imports;
+        }
+
+        torchc_cgen::runtime(self.target, backend).await;
+        // The first error in the order of the scripts is the diagnostic.
+        let results: Vec<Result<Generation, String>> = jobs::run(
+            self.settings.jobs,
+            scripts,
+            |job| panic::catch(|| task::block_on(self.generate(job, backend, profile))),
+            Result::is_err,
+        );
+        let mut generated: Vec<Generated> = vec![];
+        for result in results {
+            let (module, new) = result.unwrap_or_else(|err| panic!("{}", err));
+            if let Some(settings) = new {
+                self.cache.insert(&module, settings).await;
+            }
+            generated.push(module);
+        }
+
+        let mut unit: Unit = Unit {
+            generated: &generated,
+            target: self.target,
+            out: self.out,
+            root: self.root,
+            manifest,
+            deps,
+            backend,
+            profile,
+            jobs: self.settings.jobs,
+            verbosity: self.settings.verbosity,
+            cache: &mut self.cache,
+        };
+        let outputs: Vec<PathBuf> = match manifest.package.kind {
+            Kind::Bin => vec![unit.executable().await],
+            Kind::Lib => unit.library().await,
+        };
+        // The cache of the packages already built is kept if a later one fails.
+        self.cache.save().await;
+        if self.settings.verbosity != Verbosity::Quiet {
+            eprintln!(
+                "{} {} v{} ({}) → {}",
+                "Compiled".green().bold(),
+                manifest.package.name,
+                manifest.package.version,
+                match self.settings.mode {
+                    cgen::Mode::Dev => "dev",
+                    cgen::Mode::Release => "release",
+                },
+                outputs
+                    .iter()
+                    .map(|output| match output.strip_prefix(self.root) {
+                        Ok(rel) => rel.to_string_lossy(),
+                        Err(_) => output.to_string_lossy(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+
+        // The entry of a package is not one of its modules as a dependency.
+        for (i, generated) in generated.into_iter().enumerate() {
+            let new = |previous: &Generated| {
+                previous.module != generated.module || previous.backend != generated.backend
+            };
+            if i < deps_len && self.deps.iter().all(new) {
+                self.deps.push(generated.clone());
+            }
+            if self.generated.iter().all(new) {
+                self.generated.push(generated);
+            }
+        }
+        outputs
+    }
+
+    /// Scripts of a package in the order of their paths.
+    async fn scripts<'p>(
+        &self,
+        package: &'p Manifest,
+        prefix: bool,
+        role: &'static str,
+    ) -> Vec<Job<'p>> {
+        let src: PathBuf = package.src().await;
+        let mut paths: Vec<PathBuf> = hike(&src).await;
+        paths.sort();
+        paths
+            .into_iter()
+            .filter(|script| {
+                script
+                    .extension()
+                    .is_some_and(|ext| ext == lits::extensions::T)
+            })
+            .map(|path| Job {
+                module: match prefix {
+                    true => torchc_cgen::dependency_module(
+                        &package.package.name,
+                        &torchc_cgen::module_name(&path, &src),
+                    ),
+                    false => torchc_cgen::module_name(&path, &src),
+                },
+                import: torchc_cgen::import_path(&package.package.name, &path, &src),
+                src: src.clone(),
+                path,
+                role: Some((role, &package.package.name)),
+                imports: cgen::Imports::new(),
+            })
+            .collect()
+    }
+
+    /// Generated files of the script, from a previous build if it did not change; the
+    /// new ones are returned with their settings and includes for the cache.
+    async fn generate(
+        &self,
+        job: Job<'_>,
+        backend: cgen::Backend,
+        profile: &cgen::Profile,
+    ) -> Generation {
+        let Job {
+            path,
+            module,
+            src,
+            role,
+            imports,
+            import,
+        } = job;
+        if let Some(previous) = self
+            .deps
+            .iter()
+            .find(|previous| previous.module == module && previous.backend == backend)
+        {
+            return (previous.clone(), None);
+        }
+        let settings: u64 = cache::hash(&[
+            module.clone(),
+            String::from(backend.name()),
+            format!("{:?}", profile),
+            format!("{:?}", role),
+            format!("{:?}", imports),
+        ]);
+        if let Some(generated) = self
+            .cache
+            .generated(&path, &module, backend, settings)
+            .await
+        {
+            return (generated, None);
+        }
+
+        let script: Vec<cgen::Stmt> = parse(&path, self.root, &src).await;
+        if let Some((role, package)) = role {
+            // The modules of an executable are named by their path (`app.util`).
+            let name: &str = match role {
+                "module" => &import,
+                _ => package,
+            };
+            if let Some(fn_stmt) = script.iter().find_map(|stmt| match stmt {
+                cgen::Stmt::Fn(Some(fn_stmt)) if fn_stmt.is_main() => Some(fn_stmt),
+                _ => None,
+            }) {
+                panic::Diagnosis::new(&path, self.root).diagnosis(
+                    &format!(
+                        "the {} {} has a {} function",
+                        role,
+                        name.bold(),
+                        lits::cgen::MAIN.bold()
+                    ),
+                    fn_stmt.name.pos,
+                    &mut Script::script(&path).await.unwrap(),
+                );
+            }
+        }
+        let generated: Generated = CGen::new(script, self.target, profile, backend, &imports)
+            .cgen(&path, module, self.root)
+            .await;
+        (generated, Some(settings))
+    }
+}
+
+/// Settings of the command for all its packages.
+#[derive(Debug)]
+pub struct Settings {
+    pub mode: cgen::Mode,
+    /// `--backend`, it overrides the ones of the manifests.
+    pub backend: Option<cgen::Backend>,
+    /// In a workspace the modules have the name of their package (`app.main`).
+    pub workspace: bool,
+    /// `-j`, scripts generated and compiled at the same time.
+    pub jobs: usize,
+    /// `--quiet` or `--verbose`
+    pub verbosity: Verbosity,
+}
+
+/// Generated files of a script, the new ones with their settings for the cache.
+type Generation = (Generated, Option<u64>);
+
+/// Script to generate with the name of its module.
+#[derive(Debug)]
+struct Job<'j> {
+    path: PathBuf,
+    module: String,
+    /// Path of the module in the language for its dependents (`mathx.ops`).
+    import: String,
+    /// `src/` of its package.
+    src: PathBuf,
+    /// `("dependency", "http")`, only the entries of the executables have a `main`
+    /// function.
+    role: Option<(&'static str, &'j str)>,
+    /// Modules of the dependencies of its package.
+    imports: cgen::Imports,
+}
+
+/// Statements of the script of the `src/` folder.
+async fn parse(path: &PathBuf, root: &PathBuf, src: &PathBuf) -> Vec<cgen::Stmt> {
+    let mut script: Script = Script::script(path).await.unwrap();
+    let mut diagnosis: panic::Diagnosis = panic::Diagnosis::new(path, root).with_src(src);
+    let mut expr: cgen::Stmt = cgen::Stmt::Global(None);
+    expr = parser(&mut script, &mut diagnosis, &expr);
+    match expr {
+        cgen::Stmt::Global(global) => global.unwrap_or_default(),
+        _ => vec![],
+    }
+}
@@ -0,0 +1,193 @@
+use async_std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, hash::Hasher, path};
+use torchc_cgen::{cgen, Generated};
+use torchc_lits::lits;
+use xxhash_rust::xxh3::{xxh3_64, Xxh3Default};
+
+/// Commit of the compiler, the builds of two commits of the same version may differ.
+const COMMIT: Option<&str> = option_env!("TORCH_COMMIT");
+
+/// Cache of the incremental builds in the target folder (`torch_cache.json`).
+///
+/// ---
+/// > _A script is generated again when its contents, the headers that it includes or
+/// > the settings of its module change; an object is compiled again when its code,
+/// > those headers or the command change. Another version or commit of torch drops
+/// > the cache._
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    /// Version of torch that wrote the cache.
+    version: String,
+    /// Commit of torch that wrote the cache, none outside of a git checkout.
+    #[serde(default)]
+    commit: Option<String>,
+    /// Generated modules by module and backend (`net.http.cpp`).
+    modules: BTreeMap<String, Module>,
+    /// Hashes of the inputs of the objects, libraries and executables by path.
+    outputs: BTreeMap<path::PathBuf, u64>,
+    /// `.target/torch_cache.json`
+    #[serde(skip)]
+    path: PathBuf,
+}
+/// Generated files of a script with the hashes of their inputs.
+#[derive(Debug, Serialize, Deserialize)]
+struct Module {
+    script: path::PathBuf,
+    /// Hash of the contents of the script.
+    hash: u64,
+    /// Hash of the profile, of the module name and of the others settings.
+    settings: u64,
+    /// Headers of the script (`include "file.h"` and the interfaces of the modules of
+    /// the dependencies) with the hashes of their contents.
+    includes: BTreeMap<path::PathBuf, u64>,
+    files: Vec<path::PathBuf>,
+}
+impl Cache {
+    /// Cache of the target folder, empty if there is none or it is from another
+    /// version or commit of torch.
+    pub async fn load(target: &Path) -> Self {
+        let mut path: PathBuf = target.to_path_buf();
+        path.push(lits::std_resources::dot_target::CACHE);
+        let mut cache: Self = match fs::read_to_string(&path).await {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
+            Err(_) => Self::default(),
+        };
+        if cache.version != env!("CARGO_PKG_VERSION") || cache.commit.as_deref() != COMMIT {
+            cache = Self {
+                version: String::from(env!("CARGO_PKG_VERSION")),
+                commit: COMMIT.map(String::from),
+                ..Self::default()
+            };
+        }
+        cache.path = path;
+        cache
+    }
+    pub async fn save(&self) {
+        fs::write(
+            &self.path,
+            serde_json::to_string(self).unwrap_or_else(|err| panic!("{}", err)) + "\n",
+        )
+        .await
+        .unwrap_or_else(|err| panic!("{}", err));
+    }
+
+    /// Generated files of the script if it, its includes and the settings are the same
+    /// ones of the previous build.
+    pub async fn generated(
+        &self,
+        script: &Path,
+        module: &str,
+        backend: cgen::Backend,
+        settings: u64,
+    ) -> Option<Generated> {
+        let cached: &Module = self.modules.get(&key(module, backend))?;
+        if cached.script.as_path() != AsRef::<path::Path>::as_ref(script)
+            || cached.settings != settings
+        {
+            return None;
+        }
+        if hash_file(script).await? != cached.hash {
+            return None;
+        }
+        for (include, hash) in &cached.includes {
+            if hash_file(include.as_path().into()).await? != *hash {
+                return None;
+            }
+        }
+        for file in &cached.files {
+            if !file.exists() {
+                return None;
+            }
+        }
+        Some(Generated {
+            script: script.to_path_buf(),
+            module: String::from(module),
+            backend,
+            files: cached.files.iter().cloned().map(PathBuf::from).collect(),
+            includes: cached.includes.keys().cloned().map(PathBuf::from).collect(),
+        })
+    }
+    /// Record the generated files of the script and the headers it includes.
+    pub async fn insert(&mut self, generated: &Generated, settings: u64) {
+        let Some(hash) = hash_file(&generated.script).await else {
+            return;
+        };
+        let mut hashes: BTreeMap<path::PathBuf, u64> = BTreeMap::new();
+        for include in &generated.includes {
+            // A missing header is an error of the compiler.
+            if let Some(hash) = hash_file(include).await {
+                hashes.insert(include.clone().into(), hash);
+            }
+        }
+        self.modules.insert(
+            key(&generated.module, generated.backend),
+            Module {
+                script: generated.script.clone().into(),
+                hash,
+                settings,
+                includes: hashes,
+                files: generated.files.iter().cloned().map(Into::into).collect(),
+            },
+        );
+    }
+
+    /// Hash of the inputs of the object of the generated code: the code, the headers
+    /// included by its script and the command.
+    ///
+    /// ---
+    /// > _The headers are hashed again, the interface of a dependency can be generated
+    /// > after the check of its dependent._
+    pub async fn object(&self, source: &Path, command: &[String]) -> u64 {
+        let mut hasher: Xxh3Default = Xxh3Default::new();
+        hasher.write_u64(hash_file(source).await.unwrap_or_default());
+        if let Some(cached) = self.modules.values().find(|cached| {
+            cached
+                .files
+                .iter()
+                .any(|file| file.as_path() == AsRef::<path::Path>::as_ref(source))
+        }) {
+            for include in cached.includes.keys() {
+                hasher.write_u64(
+                    hash_file(include.as_path().into())
+                        .await
+                        .unwrap_or_default(),
+                );
+            }
+        }
+        hasher.write_u64(hash(command));
+        hasher.finish()
+    }
+    /// The output exists and has the same inputs of the previous build.
+    pub async fn fresh(&self, output: &Path, inputs: u64) -> bool {
+        self.outputs.get(AsRef::<path::Path>::as_ref(output)) == Some(&inputs)
+            && output.exists().await
+    }
+    /// Record the inputs of the output after building it.
+    pub fn built(&mut self, output: &Path, inputs: u64) {
+        self.outputs.insert(output.to_path_buf().into(), inputs);
+    }
+}
+
+/// Hash of the strings (settings, commands, ...).
+pub fn hash(strings: &[String]) -> u64 {
+    let mut hasher: Xxh3Default = Xxh3Default::new();
+    for string in strings {
+        hasher.write(string.as_bytes());
+        // `["ab", "c"]` is not `["a", "bc"]`.
+        hasher.write_u8(0);
+    }
+    hasher.finish()
+}
+
+/// `net.http.cpp`
+fn key(module: &str, backend: cgen::Backend) -> String {
+    format!("{}{}{}", module, lits::DOT, backend.name())
+}
+/// Hash of the contents of the file, `None` if it cannot be read.
+async fn hash_file(path: &Path) -> Option<u64> {
+    fs::read(path).await.ok().map(|bytes| xxh3_64(&bytes))
+}
@@ -0,0 +1,458 @@
+use crate::{
+    cache::{self, Cache},
+    jobs,
+    manifest::{Build, Level, LibType, Manifest},
+};
+use async_std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use colored::{ColoredString, Colorize};
+use std::{
+    env, io,
+    process::{Command, Output},
+};
+use torchc_cgen::{cgen, Generated};
+use torchc_cli::Verbosity;
+use torchc_diagnosis::{cc, panic};
+use torchc_lits::lits;
+use torchc_script::Script;
+
+/// Package to compile with its generated code.
+///
+/// ---
+/// > _The diagnostics of the compiler are shown in the scripts through the `#line` of
+/// > the generated code, the errors without script are internal compiler errors._
+#[derive(Debug)]
+pub struct Unit<'u> {
+    /// Modules of the package and of its dependencies.
+    pub generated: &'u [Generated],
+    /// Folder of the generated code.
+    pub target: &'u Path,
+    /// Folder of the executable or of the libraries.
+    pub out: &'u Path,
+    /// Root of the project, the scripts of the `#line` are relative to it.
+    pub root: &'u Path,
+    pub manifest: &'u Manifest,
+    pub deps: &'u [Manifest],
+    pub backend: cgen::Backend,
+    pub profile: &'u cgen::Profile,
+    /// `-j`, compilations at the same time.
+    pub jobs: usize,
+    /// `--verbose` prints the commands of the compilers.
+    pub verbosity: Verbosity,
+    pub cache: &'u mut Cache,
+}
+impl<'u> Unit<'u> {
+    /// Compile the executable of the output folder with the settings of the profile and
+    /// of the manifest, the libraries of the dependencies are linked too.
+    pub async fn executable(&mut self) -> PathBuf {
+        let mut exe: PathBuf = self.out.to_path_buf();
+        exe.push(&self.manifest.package.name);
+        let objects: Vec<(String, u64)> = self.objects(false).await;
+        let mut args: Vec<String> = self.flags(self.manifest);
+        args.push(String::from("-o"));
+        args.push(exe.to_string_lossy().into_owned());
+        args.extend(objects.iter().map(|(object, _)| object.clone()));
+        // The libraries are after the code that uses them.
+        args.extend(libs(self.manifest, self.deps));
+        self.link(&self.compiler(), args, &exe, &objects).await;
+        exe
+    }
+
+    /// Compile the libraries of the output folder (`libname.a`, `libname.so`) and
+    /// write their header (`name.h`) with the exported functions of the modules.
+    ///
+    /// ---
+    /// > _The static library needs the C++ standard library of the compiler to link
+    /// > (`-lstdc++`), the shared one links it._
+    pub async fn library(&mut self) -> Vec<PathBuf> {
+        let objects: Vec<(String, u64)> = self.objects(true).await;
+
+        let mut libs: Vec<PathBuf> = vec![];
+        for ty in &self.manifest.lib.types {
+            let mut lib: PathBuf = self.out.to_path_buf();
+            lib.push(format!(
+                "{}{}{}{}",
+                lits::compilers::LIB_PREFIX,
+                self.manifest.package.name,
+                lits::DOT,
+                match ty {
+                    LibType::Static => lits::extensions::A,
+                    LibType::Shared => lits::extensions::SO,
+                }
+            ));
+            let mut args: Vec<String> = vec![];
+            let program: String = match ty {
+                // `ar rcs libname.a main.o ...`
+                LibType::Static => {
+                    args.push(String::from(lits::compilers::AR_FLAGS));
+                    args.push(lib.to_string_lossy().into_owned());
+                    args.extend(objects.iter().map(|(object, _)| object.clone()));
+                    env::var(lits::compilers::AR)
+                        .unwrap_or_else(|_| String::from(lits::compilers::DEFAULT_AR))
+                }
+                // `c++ -shared -o libname.so main.o ...`
+                LibType::Shared => {
+                    args.extend(self.flags(self.manifest));
+                    args.push(String::from(lits::compilers::SHARED));
+                    args.push(String::from("-o"));
+                    args.push(lib.to_string_lossy().into_owned());
+                    args.extend(objects.iter().map(|(object, _)| object.clone()));
+                    args.extend(self::libs(self.manifest, self.deps));
+                    self.compiler()
+                }
+            };
+            self.link(&program, args, &lib, &objects).await;
+            libs.push(lib);
+        }
+
+        // `name.h`
+        let mut header: String = format!(
+            "// Exported functions of the `{}` library, generated by torch, do not edit.\n",
+            self.manifest.package.name
+        );
+        for file in self.generated.iter().flat_map(|generated| &generated.files) {
+            if file
+                .extension()
+                .is_some_and(|ext| ext == lits::extensions::H)
+            {
+                header.push('\n');
+                header.push_str(
+                    &fs::read_to_string(file)
+                        .await
+                        .unwrap_or_else(|err| panic!("{}", err)),
+                );
+            }
+        }
+        let mut path: PathBuf = self.out.to_path_buf();
+        path.push(format!(
+            "{}{}{}",
+            self.manifest.package.name,
+            lits::DOT,
+            lits::extensions::H
+        ));
+        fs::write(&path, header)
+            .await
+            .unwrap_or_else(|err| panic!("{}", err));
+        libs.push(path);
+        libs
+    }
+
+    /// `$CXX` or `c++`
+    fn compiler(&self) -> String {
+        match self.backend {
+            cgen::Backend::C => env::var(lits::compilers::CC)
+                .unwrap_or_else(|_| String::from(lits::compilers::DEFAULT_CC)),
+            cgen::Backend::Cpp => env::var(lits::compilers::CXX)
+                .unwrap_or_else(|_| String::from(lits::compilers::DEFAULT_CXX)),
+        }
+    }
+    /// Flags of the language, of the profile and of the manifest of the package.
+    fn flags(&self, manifest: &Manifest) -> Vec<String> {
+        let build: &Build = &manifest.build;
+        let mut flags: Vec<String> = vec![String::from(match self.backend {
+            cgen::Backend::C => lits::compilers::C_STD,
+            cgen::Backend::Cpp => lits::compilers::CPP_STD,
+        })];
+        flags.extend(profile(self.profile));
+        flags.extend(
+            match self.backend {
+                cgen::Backend::C => &build.cflags,
+                cgen::Backend::Cpp => &build.cxxflags,
+            }
+            .iter()
+            .cloned(),
+        );
+        flags.extend(build.include.iter().map(|dir| {
+            format!(
+                "{}{}",
+                lits::compilers::INCLUDE,
+                manifest.root.join(dir).to_string_lossy()
+            )
+        }));
+        flags
+    }
+    /// Compile the generated code of each module to its object (`.target/main.o`), the
+    /// objects of the previous builds are reused.
+    ///
+    /// ---
+    /// > _The objects of the libraries are position-independent (`-fPIC`). The modules of
+    /// > a dependency are compiled with the `[build]` flags of its own manifest._
+    async fn objects(&mut self, pic: bool) -> Vec<(String, u64)> {
+        let program: String = self.compiler();
+        let mut objects: Vec<(String, u64)> = vec![];
+        // Objects to compile with their arguments.
+        let mut pending: Vec<(PathBuf, Vec<String>, u64)> = vec![];
+        for (source, package) in self.sources() {
+            let mut object: PathBuf = source.clone();
+            object.set_extension(lits::extensions::O);
+            let mut args: Vec<String> = self.flags(package);
+            if pic {
+                args.push(String::from(lits::compilers::PIC));
+            }
+            args.push(String::from(lits::compilers::OBJECT));
+            args.push(source.to_string_lossy().into_owned());
+            args.push(String::from("-o"));
+            args.push(object.to_string_lossy().into_owned());
+
+            let command: Vec<String> = [program.clone()].into_iter().chain(args.clone()).collect();
+            let inputs: u64 = self.cache.object(&source, &command).await;
+            objects.push((object.to_string_lossy().into_owned(), inputs));
+            if !self.cache.fresh(&object, inputs).await {
+                pending.push((object, args, inputs));
+            }
+        }
+
+        // The diagnostics are reported in the order of the modules.
+        let root: &Path = self.root;
+        let verbose: bool = self.verbosity == Verbosity::Verbose;
+        let outputs: Vec<io::Result<Output>> = jobs::run(
+            self.jobs,
+            pending.iter().map(|(_, args, _)| args).collect(),
+            |args| command(root, &program, args, verbose),
+            |output| !output.as_ref().is_ok_and(|output| output.status.success()),
+        );
+        for ((object, _, inputs), output) in pending.iter().zip(outputs) {
+            self.diagnose(&program, output).await;
+            self.cache.built(object, *inputs);
+        }
+        objects
+    }
+    /// Link the objects to the output (executable or library) if they or the command
+    /// changed.
+    async fn link(
+        &mut self,
+        program: &str,
+        args: Vec<String>,
+        output: &Path,
+        objects: &[(String, u64)],
+    ) {
+        let inputs: u64 = cache::hash(
+            &[String::from(program)]
+                .into_iter()
+                .chain(args.iter().cloned())
+                .chain(objects.iter().map(|(_, inputs)| inputs.to_string()))
+                .collect::<Vec<String>>(),
+        );
+        if self.cache.fresh(output, inputs).await {
+            return;
+        }
+        // Archives are replaced, `ar` adds to the existing ones.
+        if output.exists().await {
+            fs::remove_file(output)
+                .await
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
+        self.invoke(program, &args).await;
+        self.cache.built(output, inputs);
+    }
+    /// Generated code of the modules with the package of their script.
+    fn sources(&self) -> Vec<(PathBuf, &'u Manifest)> {
+        let mut sources: Vec<(PathBuf, &'u Manifest)> = vec![];
+        for generated in self.generated {
+            // `../mathx/src/ops.t` is a module of `mathx`.
+            let package: &'u Manifest = self
+                .deps
+                .iter()
+                .find(|dep| {
+                    generated
+                        .script
+                        .starts_with(dep.root.join(&dep.package.src))
+                })
+                .unwrap_or(self.manifest);
+            sources.extend(
+                generated
+                    .files
+                    .iter()
+                    .filter(|file| {
+                        file.extension().is_some_and(|ext| {
+                            ext == lits::extensions::C || ext == lits::extensions::CPP
+                        })
+                    })
+                    .map(|file| (file.clone(), package)),
+            );
+        }
+        sources
+    }
+
+    /// Run the compiler (or the archiver) and report its diagnostics.
+    async fn invoke(&self, program: &str, args: &[String]) {
+        let verbose: bool = self.verbosity == Verbosity::Verbose;
+        self.diagnose(program, command(self.root, program, args, verbose))
+            .await;
+    }
+    /// Report the diagnostics of the compiler, its errors stop the build.
+    async fn diagnose(&self, program: &str, output: io::Result<Output>) {
+        let output: Output = output
+            .unwrap_or_else(|err| panic!("the compiler {} cannot run: {}", program.bold(), err));
+
+        let stderr: String = String::from_utf8_lossy(&output.stderr).into_owned();
+        let mut errors: usize = 0;
+        for mut diagnostic in cc::parse(&stderr) {
+            // `[lints] warnings = "allow"` or `"deny"`
+            if diagnostic.severity == cc::Severity::Warning {
+                match self.manifest.lints.warnings {
+                    Level::Allow => continue,
+                    Level::Warn => {}
+                    Level::Deny => diagnostic.severity = cc::Severity::Error,
+                }
+            }
+            if diagnostic.severity == cc::Severity::Error {
+                errors += 1;
+            }
+            report(&diagnostic, self.target, self.root).await;
+        }
+        if output.status.success() && errors > 0 {
+            panic!(
+                "the warnings are denied by the lints of {}",
+                lits::std_resources::MANIFEST.bold()
+            );
+        }
+        if !output.status.success() {
+            // Linker errors and others without diagnostics.
+            if errors == 0 {
+                eprintln!(
+                    "{}{} {}\n{}",
+                    lits::EPREFIX.red().bold(),
+                    lits::COLON.bold(),
+                    lits::ICE,
+                    cc::others(&stderr).join("\n")
+                );
+            }
+            panic!(
+                "the generated code cannot be compiled by {}",
+                program.bold()
+            );
+        }
+    }
+}
+
+/// Run the compiler (or the archiver) in the root of the project, `verbose` prints the
+/// command before.
+fn command(root: &Path, program: &str, args: &[String], verbose: bool) -> io::Result<Output> {
+    if verbose {
+        eprintln!("{} {} {}", "Running".cyan().bold(), program, args.join(" "));
+    }
+    Command::new(program).args(args).current_dir(root).output()
+}
+
+/// Folders (`-L`) and libraries (`-l`) of the package and of its dependencies, the
+/// folders are relative to the root of their package.
+fn libs(manifest: &Manifest, deps: &[Manifest]) -> Vec<String> {
+    let mut libs: Vec<String> = vec![];
+    for package in [manifest].into_iter().chain(deps) {
+        libs.extend(package.build.lib_dirs.iter().map(|dir| {
+            format!(
+                "{}{}",
+                lits::compilers::LIB_DIR,
+                package.root.join(dir).to_string_lossy()
+            )
+        }));
+    }
+    // The dependents before their dependencies.
+    for package in [manifest].into_iter().chain(deps.iter().rev()) {
+        libs.extend(
+            package
+                .build
+                .libs
+                .iter()
+                .map(|lib| format!("{}{}", lits::compilers::LIB, lib)),
+        );
+    }
+    libs
+}
+
+/// Flags of the settings of the profile (`-O0 -g -ftrapv`).
+fn profile(profile: &cgen::Profile) -> Vec<String> {
+    let mut flags: Vec<String> = vec![format!(
+        "{}{}",
+        lits::compilers::OPT_LEVEL,
+        profile.opt_level
+    )];
+    if profile.debug {
+        flags.push(String::from(lits::compilers::DEBUG));
+    }
+    if profile.overflow_checks {
+        flags.push(String::from(lits::compilers::TRAPV));
+    }
+    if !profile.assertions {
+        flags.push(String::from(lits::compilers::NDEBUG));
+    }
+    if !profile.sanitizers.is_empty() {
+        flags.push(format!(
+            "{}{}",
+            lits::compilers::SANITIZE,
+            profile.sanitizers.join(",")
+        ));
+    }
+    flags
+}
+
+/// Show the diagnostic in its script or, for the errors of the generated code and
+/// the runtime, as an internal compiler error.
+async fn report(diagnostic: &cc::Diagnostic, target: &Path, cwd: &Path) {
+    let (prefix, colored): (&str, ColoredString) = match diagnostic.severity {
+        cc::Severity::Error => (lits::EPREFIX, lits::EPREFIX.red().bold()),
+        cc::Severity::Warning => (lits::WPREFIX, lits::WPREFIX.yellow().bold()),
+        cc::Severity::Note => (lits::NPREFIX, lits::NPREFIX.cyan().bold()),
+    };
+
+    // `src/file.t`
+    let mut path: PathBuf = cwd.to_path_buf();
+    path.push(&diagnostic.file);
+    if path
+        .extension()
+        .is_some_and(|ext| ext == lits::extensions::T)
+        && path.exists().await
+    {
+        let mut script: Script = Script::script(&path)
+            .await
+            .unwrap_or_else(|err| panic!("{}", err));
+        let diagnosis: panic::Diagnosis = panic::Diagnosis::new(&path, cwd);
+        // `'_T5mathx3ops3sub' was not declared` -> `'mathx.ops.sub' was not declared`
+        let msg: String = cgen::demangle_text(&diagnostic.msg);
+        let rendered: String = diagnosis
+            .render_line(prefix, &msg, diagnostic.line, &mut script)
+            // Line without code (the end of a block).
+            .unwrap_or_else(|| {
+                format!(
+                    "{} {} {}{}{}",
+                    msg,
+                    "→".red().bold(),
+                    diagnostic.file.bold(),
+                    lits::COLON,
+                    diagnostic.line
+                )
+            });
+        return eprintln!("{}{} {}", colored, lits::COLON.bold(), rendered);
+    }
+    if diagnostic.severity != cc::Severity::Error {
+        return;
+    }
+
+    // `.target/dev/main.cpp:12:5`, the `#line` of the generated code have its file name.
+    let mut generated: PathBuf = target.to_path_buf();
+    generated.push(&diagnostic.file);
+    let file: String = match generated.exists().await {
+        true => match generated.strip_prefix(cwd) {
+            Ok(rel) => rel.to_string_lossy().into_owned(),
+            Err(_) => generated.to_string_lossy().into_owned(),
+        },
+        false => diagnostic.file.clone(),
+    };
+    eprintln!(
+        "{}{} {}{} {} {} {}{}{}{}{}",
+        lits::EPREFIX.red().bold(),
+        lits::COLON.bold(),
+        lits::ICE,
+        lits::COLON,
+        diagnostic.msg,
+        "→".red().bold(),
+        file.bold(),
+        lits::COLON,
+        diagnostic.line,
+        lits::COLON,
+        diagnostic.column
+    );
+}
@@ -0,0 +1,107 @@
+use crate::manifest::Manifest;
+use async_std::path::{Path, PathBuf};
+use colored::Colorize;
+use std::{future::Future, pin::Pin};
+use torchc_lits::lits;
+
+/// Manifests of the dependencies of the project, each one after its own dependencies.
+///
+/// ---
+/// > _A package of several dependents is compiled once, the cycles and two packages
+/// > with the same name are errors._
+pub async fn resolve(manifest: &Manifest, cwd: &Path) -> Vec<Manifest> {
+    let mut resolved: Vec<Manifest> = vec![];
+    let mut stack: Vec<(String, PathBuf)> = vec![(
+        manifest.package.name.clone(),
+        canonical(&manifest.root, &manifest.package.name).await,
+    )];
+    visit(manifest, cwd, &mut stack, &mut resolved).await;
+    resolved
+}
+
+/// Packages after the ones they depend on, with their dependencies.
+pub fn order(mut packages: Vec<(Manifest, Vec<Manifest>)>) -> Vec<(Manifest, Vec<Manifest>)> {
+    let mut ordered: Vec<(Manifest, Vec<Manifest>)> = vec![];
+    while !packages.is_empty() {
+        // The first one that does not depend on the others, there are no cycles.
+        let i: usize = packages
+            .iter()
+            .position(|(_, deps)| {
+                !deps
+                    .iter()
+                    .any(|dep| packages.iter().any(|(other, _)| other.root == dep.root))
+            })
+            .unwrap_or_default();
+        ordered.push(packages.remove(i));
+    }
+    ordered
+}
+
+/// Resolve the dependencies of the package, the stack has the packages that depend
+/// on it (`main → net → http`).
+fn visit<'v>(
+    manifest: &'v Manifest,
+    cwd: &'v Path,
+    stack: &'v mut Vec<(String, PathBuf)>,
+    resolved: &'v mut Vec<Manifest>,
+) -> Pin<Box<dyn Future<Output = ()> + 'v>> {
+    Box::pin(async move {
+        for dependency in &manifest.dependencies {
+            let root: PathBuf = canonical(&dependency.root, &dependency.name).await;
+
+            // `a → b → a`
+            if let Some(i) = stack.iter().position(|(_, dir)| *dir == root) {
+                let cycle: Vec<&str> = stack[i..]
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .chain([dependency.name.as_str()])
+                    .collect();
+                panic!("dependency cycle {}", cycle.join(" → ").red().bold());
+            }
+            if resolved.iter().any(|dep| dep.root == root) {
+                continue;
+            }
+
+            let dep: Manifest = Manifest::load(&root, cwd).await.unwrap_or_else(|| {
+                panic!(
+                    "the dependency {} of {} does not have a {}",
+                    dependency.name.red().bold(),
+                    manifest.package.name.bold(),
+                    lits::std_resources::MANIFEST.bold()
+                )
+            });
+            if dep.package.name != dependency.name {
+                panic!(
+                    "the dependency {} of {} is the package {}",
+                    dependency.name.red().bold(),
+                    manifest.package.name.bold(),
+                    dep.package.name.bold()
+                );
+            }
+            if stack.iter().any(|(name, _)| *name == dep.package.name)
+                || resolved
+                    .iter()
+                    .any(|other| other.package.name == dep.package.name)
+            {
+                panic!("two packages are named {}", dep.package.name.red().bold());
+            }
+
+            stack.push((dep.package.name.clone(), root));
+            visit(&dep, cwd, stack, resolved).await;
+            stack.pop();
+            resolved.push(dep);
+        }
+    })
+}
+
+/// Canonical folder of the package, two paths of a package are the same one.
+async fn canonical(root: &PathBuf, name: &str) -> PathBuf {
+    root.canonicalize().await.unwrap_or_else(|err| {
+        panic!(
+            "the package {} is not in {}: {}",
+            name.red().bold(),
+            root.to_string_lossy().bold(),
+            err
+        )
+    })
+}
@@ -0,0 +1,86 @@
+use std::{
+    iter::Enumerate,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    vec::IntoIter,
+};
+
+/// Run the jobs on `threads` threads, the results are in the order of the jobs.
+///
+/// ---
+/// > _After a failed job the next ones are not started and the results end at the
+/// > first failed one, as if the jobs had been run one after the other._
+pub fn run<J: Send, R: Send>(
+    threads: usize,
+    jobs: Vec<J>,
+    job: impl Fn(J) -> R + Sync,
+    failed: impl Fn(&R) -> bool + Sync,
+) -> Vec<R> {
+    // The jobs are started in order, so the ones before a failed job always finish.
+    let queue: Mutex<Enumerate<IntoIter<J>>> = Mutex::new(jobs.into_iter().enumerate());
+    let first_failed: AtomicUsize = AtomicUsize::new(usize::MAX);
+    let results: Mutex<Vec<(usize, R)>> = Mutex::new(vec![]);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let Some((i, next)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                if i > first_failed.load(Ordering::SeqCst) {
+                    break;
+                }
+                let result: R = job(next);
+                if failed(&result) {
+                    first_failed.fetch_min(i, Ordering::SeqCst);
+                }
+                results.lock().unwrap().push((i, result));
+            });
+        }
+    });
+
+    let first_failed: usize = first_failed.into_inner();
+    let mut results: Vec<(usize, R)> = results.into_inner().unwrap();
+    results.retain(|(i, _)| *i <= first_failed);
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Number of jobs of `-j`, by default the number of CPUs.
+pub fn default() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_in_order() {
+        let results: Vec<usize> = run(4, (0..32).collect(), |i| i * 2, |_| false);
+        assert_eq!(results, (0..32).map(|i| i * 2).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn results_end_at_the_first_failure() {
+        let started: AtomicUsize = AtomicUsize::new(0);
+        let results: Vec<usize> = run(
+            1,
+            (0..8).collect(),
+            |i| {
+                started.fetch_add(1, Ordering::SeqCst);
+                i
+            },
+            |i| *i >= 3,
+        );
+        assert_eq!(results, vec![0, 1, 2, 3]);
+        // The jobs after the failed one are not started.
+        assert_eq!(started.into_inner(), 4);
+
+        let results: Vec<usize> = run(8, (0..64).collect(), |i| i, |i| *i % 5 == 4);
+        assert_eq!(results, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -1,77 +1,298 @@
 use async_std::{fs, path::PathBuf};
+use build::{Builder, Settings};
+use cache::Cache;
 use colored::Colorize;
-use torchc_cgen::{cgen, CGen};
-use torchc_cli::Cli;
+use manifest::{Kind, Manifest, Project};
+use std::{
+    io::{self, BufRead},
+    process::{self, Command, ExitStatus},
+};
+use torchc_bindgen::{bindgen, Bindings};
+use torchc_cgen::cgen;
+use torchc_cli::{Cli, Color, NestedSubcmdForBuild, Subcmd, Verbosity};
 use torchc_diagnosis::panic;
 use torchc_hike::hike;
 use torchc_lits::lits;
-use torchc_parse::parser;
-use torchc_script::Script;
+
+mod build;
+mod cache;
+mod compile;
+mod deps;
+mod jobs;
+mod manifest;
+mod new;
+mod target;
+mod version;
 
 #[async_std::main]
 async fn main() {
     panic::default();
 
     let cli: Cli = Cli::parse();
-    println!(
-        "[cmd: `{}`, subcmd: `{:?}`]",
-        String::from_utf8_lossy(&cli.cmd.unwrap()),
-        cli.subcmd.unwrap()
-    );
+    // `--color always|never`, otherwise only in the terminals.
+    match cli.color {
+        Color::Always => colored::control::set_override(true),
+        Color::Never => colored::control::set_override(false),
+        Color::Auto => {}
+    }
+    let quiet: bool = cli.verbosity == Verbosity::Quiet;
+    if cli.verbosity == Verbosity::Verbose {
+        eprintln!(
+            "[cmd: `{}`, subcmd: `{:?}`]",
+            String::from_utf8_lossy(cli.cmd.as_ref().unwrap()),
+            cli.subcmd.as_ref().unwrap()
+        );
+    }
 
-    // Find the language files in the `src/` directory.
+    // `torch manual` or `torch build --help`
+    if let Some(Subcmd::Manual(subcmd)) = &cli.subcmd {
+        print!("{}", torchc_cli::manual(subcmd.as_deref()));
+        return;
+    }
+    // `torch` or `torch version`
+    if let Some(Subcmd::Version { json }) = cli.subcmd {
+        return version::version(json);
+    }
+
+    // `torch demangle`
+    if let Some(Subcmd::Demangle(symbols)) = &cli.subcmd {
+        if symbols.is_empty() {
+            for line in io::stdin().lock().lines() {
+                println!(
+                    "{}",
+                    cgen::demangle_text(&line.unwrap_or_else(|err| panic!("{}", err)))
+                );
+            }
+        }
+        for symbol in symbols {
+            println!("{}", cgen::demangle_text(symbol));
+        }
+        return;
+    }
+
+    // Find the packages of the project.
 
     let cwd: PathBuf = match std::env::current_dir() {
         Ok(cwd) => cwd.into(),
         Err(err) => panic!("{}", err),
     };
-    let mut src: PathBuf = cwd.clone();
-    src.push(lits::std_resources::SRC);
-    if !src.exists().await {
-        panic!(
-            "the {} folder does not exist in the {} directory",
-            lits::std_resources::SRC.red().bold(),
-            match cwd.to_str() {
-                Some(cwd) => cwd.bold(),
-                None => lits::CURRENT.normal(),
+
+    // `torch new name` and `torch init`
+    match &cli.subcmd {
+        Some(Subcmd::New(name)) => {
+            let dir: PathBuf = cwd.join(name);
+            if dir.exists().await {
+                panic!(
+                    "the folder {} already exists, create the package in it with {}",
+                    name.red().bold(),
+                    format!("torch {}", lits::cli::INIT).bold()
+                );
+            }
+            return new::new(&dir, name, quiet).await;
+        }
+        Some(Subcmd::Init) => {
+            let name: String = cwd
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            return new::new(&cwd, &name, quiet).await;
+        }
+        _ => {}
+    }
+
+    let project: Project = Project::find(&cwd, cli.package.as_deref()).await;
+    let root: &PathBuf = &project.root;
+    // `--target-dir dir`, the folder of `.target/` and `target/`.
+    let base: PathBuf = match &cli.target_dir {
+        Some(dir) => cwd.join(dir),
+        None => root.clone(),
+    };
+
+    // `torch clean`
+    if let Some(Subcmd::Clean {
+        dev,
+        release,
+        dry_run,
+    }) = cli.subcmd
+    {
+        target::clean(&base, dev, release, dry_run, quiet).await;
+        return;
+    }
+
+    // `torch bindgen`
+    if let Some(Subcmd::Bindgen) = cli.subcmd {
+        for package in &project.packages {
+            for header in hike(&package.src().await).await.iter().filter(|script| {
+                script
+                    .extension()
+                    .is_some_and(|ext| ext == lits::extensions::H)
+            }) {
+                bindings(header, root).await;
             }
+        }
+        return;
+    }
+
+    // `torch run` and `torch build run`
+    let run: bool = matches!(
+        cli.subcmd,
+        Some(Subcmd::Run | Subcmd::Build(Some(NestedSubcmdForBuild::Run)))
+    );
+    if run && project.packages.len() > 1 {
+        panic!(
+            "the workspace has several packages, select the one to run with {} ({})",
+            lits::cli::PACKAGE_SHORT.bold(),
+            project
+                .packages
+                .iter()
+                .map(|package| package.package.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
         );
     }
-    let scripts: Vec<PathBuf> = hike(&src).await;
+    if let (true, Some(package)) = (run, project.packages.first()) {
+        if package.package.kind == Kind::Lib {
+            panic!(
+                "the package {} is a library, it cannot run",
+                package.package.name.red().bold()
+            );
+        }
+    }
+
+    // `--backend`, otherwise the one of each manifest.
+    let backend: Option<cgen::Backend> = cli.backend.as_ref().map(|name| {
+        cgen::Backend::from_name(name).unwrap_or_else(|| {
+            panic!(
+                "illegal backend {}, expected {} or {}",
+                name.red().bold(),
+                lits::backends::C.bold(),
+                lits::backends::CPP.bold()
+            )
+        })
+    });
+
+    // `-j N`, otherwise one job per CPU.
+    let jobs: usize = match &cli.jobs {
+        Some(n) => match n.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => panic!(
+                "illegal number of jobs {}, expected a positive integer",
+                n.red().bold()
+            ),
+        },
+        None => jobs::default(),
+    };
 
-    let mode: cgen::Mode = cgen::Mode::Dev;
-    let mut dot_target: PathBuf = cwd.clone();
+    // `torch build` and `--release` are optimized, the others are for development.
+    let mode: cgen::Mode = match cli.subcmd {
+        Some(Subcmd::Build(_)) => cgen::Mode::Release,
+        _ if cli.release => cgen::Mode::Release,
+        _ => cgen::Mode::Dev,
+    };
+    let mut dot_target: PathBuf = base.clone();
     {
+        // `.../.target/` or `.../.target/dev/`
         dot_target.push(lits::std_resources::dot_target::NAME);
+        target::create(&dot_target).await;
         if mode == cgen::Mode::Dev {
             dot_target.push(lits::std_resources::dot_target::DEV);
+            target::create(&dot_target).await;
         }
-        // `.../.target/` or `.../.target/dev/`
-        fs::create_dir_all(&dot_target)
-            .await
-            .unwrap_or_else(|err| panic!("{}", err));
     }
+    // The release executables are in `target/`, the development ones with their code.
+    let out: PathBuf = match mode {
+        cgen::Mode::Release => {
+            let mut out: PathBuf = base.clone();
+            out.push(lits::std_resources::TARGET);
+            target::create(&out).await;
+            out
+        }
+        cgen::Mode::Dev => dot_target.clone(),
+    };
 
-    {
-        let mut path: PathBuf = src.clone();
-        path.push("main.t");
-
-        let mut script: Script = Script::script(&path).await.unwrap();
-        let mut diagnosis: panic::Diagnosis = panic::Diagnosis::new(&path, &cwd);
-        let mut expr: cgen::Stmt = cgen::Stmt::Global(None);
-        expr = parser(&mut script, &mut diagnosis, &expr);
-
-        CGen::new(
-            match expr {
-                cgen::Stmt::Global(global) => match global {
-                    Some(program) => program,
-                    None => vec![],
-                },
-                _ => vec![],
-            },
-            &dot_target,
-        )
-        .cgen(&path)
-        .await;
+    // The members of a workspace after the ones they depend on.
+    let mut packages: Vec<(Manifest, Vec<Manifest>)> = vec![];
+    for package in project.packages {
+        let deps: Vec<Manifest> = deps::resolve(&package, &cwd).await;
+        packages.push((package, deps));
+    }
+    let mut builder: Builder = Builder::new(
+        root,
+        &dot_target,
+        &out,
+        Settings {
+            mode,
+            backend,
+            workspace: project.workspace,
+            jobs,
+            verbosity: cli.verbosity,
+        },
+        Cache::load(&dot_target).await,
+    );
+    let mut outputs: Vec<PathBuf> = vec![];
+    for (package, deps) in deps::order(packages) {
+        outputs.extend(builder.build(&package, &deps).await);
+    }
+    torchc_cgen::manifest(&dot_target, root, &builder.generated).await;
+
+    if let (true, Some(exe)) = (run, outputs.first()) {
+        run_exe(exe, &cli.args);
+    }
+}
+
+/// Run the executable with the arguments after `--` and the standard streams of the
+/// terminal, and exit with its status.
+fn run_exe(exe: &PathBuf, args: &[String]) -> ! {
+    let status: ExitStatus = Command::new(exe)
+        .args(args)
+        .status()
+        .unwrap_or_else(|err| panic!("{} cannot run: {}", exe.to_string_lossy().bold(), err));
+    process::exit(status.code().unwrap_or(1))
+}
+
+/// Generate the module with the bindings of the header next to it
+/// (`file.h` -> `file_bindings.t`) and report the skipped declarations.
+async fn bindings(header: &PathBuf, cwd: &PathBuf) {
+    let contents: String = fs::read_to_string(header)
+        .await
+        .unwrap_or_else(|err| panic!("{}", err));
+    let filename: String = header.file_name().unwrap().to_string_lossy().into_owned();
+    let bindings: Bindings = bindgen(&filename, &contents);
+
+    let mut module: PathBuf = header.clone();
+    module.set_file_name(format!(
+        "{}{}{}{}",
+        header.file_stem().unwrap().to_string_lossy(),
+        lits::bindgen::SUFFIX,
+        lits::DOT,
+        lits::extensions::T
+    ));
+    fs::write(&module, &bindings.torch)
+        .await
+        .unwrap_or_else(|err| panic!("{}", err));
+
+    // `src/file.h`
+    let rel = |path: &PathBuf| match path.strip_prefix(cwd) {
+        Ok(rel) => rel.to_string_lossy().into_owned(),
+        Err(_) => path.to_string_lossy().into_owned(),
+    };
+    for skipped in &bindings.skipped {
+        eprintln!(
+            "{}{} skipped {} → {}{}{}, {}",
+            lits::WPREFIX.yellow().bold(),
+            lits::COLON.bold(),
+            skipped.decl.bold(),
+            rel(header),
+            lits::COLON,
+            skipped.line,
+            skipped.reason
+        );
     }
+    println!(
+        "{} → {} ({} bound, {} skipped)",
+        rel(header),
+        rel(&module).bold(),
+        bindings.bound,
+        bindings.skipped.len()
+    );
 }
@@ -0,0 +1,966 @@
+use async_std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use colored::Colorize;
+use pathdiff::diff_paths;
+use serde::Deserialize;
+use std::{collections::BTreeMap, ops::Range};
+use toml::Spanned;
+use torchc_cgen::cgen;
+use torchc_lits::lits;
+
+/// _**Manifest**_ of the project (`torch.toml`).
+///
+/// ```toml
+/// backend = "cpp"
+///
+/// [package]
+/// name = "hello"
+/// version = "0.1.0"
+/// kind = "bin"
+/// entry = "main.t"
+/// src = "src"
+///
+/// # With `kind = "lib"`.
+/// [lib]
+/// types = ["static", "shared"]
+///
+/// [build]
+/// cflags = ["-Wall"]
+/// cxxflags = ["-Wall"]
+/// include = ["include"]
+/// lib-dirs = ["lib"]
+/// libs = ["m"]
+///
+/// [lints]
+/// warnings = "deny"
+///
+/// [dependencies]
+/// strings = { path = "../strings" }
+///
+/// [profile.release]
+/// opt-level = "s"
+/// debug = true
+/// overflow-checks = true
+/// bounds-checks = true
+/// assertions = false
+/// sanitizers = ["address", "undefined"]
+/// ```
+///
+/// ---
+/// > _Without it, the current directory is the root with the default settings._
+#[derive(Debug)]
+pub struct Manifest {
+    /// Folder of the manifest, the paths of the package are relative to it.
+    pub root: PathBuf,
+    pub package: Package,
+    /// `backend = "c"`, `--backend` overrides it.
+    pub backend: Option<cgen::Backend>,
+    pub build: Build,
+    pub lints: Lints,
+    pub lib: Lib,
+    pub dependencies: Vec<Dependency>,
+    dev: cgen::Profile,
+    release: cgen::Profile,
+}
+impl Manifest {
+    /// Manifest of the package of the folder, `None` without `torch.toml`.
+    pub async fn load(dir: &Path, cwd: &Path) -> Option<Self> {
+        let loaded: Loaded = Loaded::read(dir, cwd).await?;
+        let source: Source = loaded.source();
+        Some(Self::parse(
+            dir.to_path_buf(),
+            parse(&source),
+            &source,
+            &Shared::default(),
+        ))
+    }
+
+    /// Folder of the scripts of the package, it exists.
+    pub async fn src(&self) -> PathBuf {
+        let mut src: PathBuf = self.root.clone();
+        src.push(&self.package.src);
+        if !src.exists().await {
+            panic!(
+                "the {} folder of {} does not exist in the {} directory{}",
+                self.package.src.red().bold(),
+                self.package.name.bold(),
+                match self.root.to_str() {
+                    Some(root) => root.bold(),
+                    None => lits::CURRENT.normal(),
+                },
+                // Without manifest it is not a package yet.
+                match self.root.join(lits::std_resources::MANIFEST).exists().await {
+                    true => String::new(),
+                    false => format!(
+                        ", create a package with {} or {}",
+                        format!("torch {} name", lits::cli::NEW).bold(),
+                        format!("torch {}", lits::cli::INIT).bold()
+                    ),
+                }
+            );
+        }
+        src
+    }
+
+    /// Profile of the mode, the settings of `[profile.dev]` or `[profile.release]`
+    /// override the defaults.
+    pub fn profile(&self, mode: cgen::Mode) -> &cgen::Profile {
+        match mode {
+            cgen::Mode::Dev => &self.dev,
+            cgen::Mode::Release => &self.release,
+        }
+    }
+
+    /// Validate the settings of the package, the sections it does not have are the
+    /// ones of its workspace.
+    fn parse(root: PathBuf, file: File, source: &Source, shared: &Shared) -> Self {
+        if file.workspace.is_some() && file.package.is_some() {
+            invalid(
+                source,
+                None,
+                "a workspace is not a package, `workspace` and `package` are exclusive",
+            );
+        }
+        let shared: Shared = Shared::parse(&file, source).or(shared);
+        let package: Package = match file.package {
+            Some(package) => package.validate(source),
+            None if source.contents.is_empty() => Package::default(),
+            None => invalid(source, None, "missing table `package`"),
+        };
+        let lib: Lib = match file.lib {
+            Some(lib) if package.kind != Kind::Lib => invalid(
+                source,
+                Some(lib.span()),
+                "the package is not a library (`kind = \"lib\"`)",
+            ),
+            Some(lib) if lib.get_ref().types.is_empty() => {
+                invalid(source, Some(lib.span()), "the library has no types")
+            }
+            Some(lib) => lib.into_inner(),
+            None => Lib::default(),
+        };
+        let dependencies: Vec<Dependency> = file
+            .dependencies
+            .into_iter()
+            .map(|(name, dependency)| {
+                if !is_name(name.get_ref()) {
+                    invalid(source, Some(name.span()), NAME);
+                }
+                let mut path: PathBuf = root.clone();
+                path.push(dependency.path.get_ref());
+                Dependency {
+                    name: name.into_inner(),
+                    root: path,
+                }
+            })
+            .collect();
+        Self {
+            root,
+            package,
+            backend: shared.backend,
+            build: shared.build.unwrap_or_default(),
+            lints: shared.lints.unwrap_or_default(),
+            lib,
+            dependencies,
+            dev: shared
+                .dev
+                .unwrap_or_else(|| cgen::Profile::new(cgen::Mode::Dev)),
+            release: shared
+                .release
+                .unwrap_or_else(|| cgen::Profile::new(cgen::Mode::Release)),
+        }
+    }
+}
+
+/// _**Project**_ of the command, a package or the members of a workspace.
+///
+/// ```toml
+/// [workspace]
+/// members = ["app", "libs/strings"]
+///
+/// [lints]
+/// warnings = "deny"
+/// ```
+///
+/// ---
+/// > _The manifest is searched upward from the current directory; a workspace above the
+/// > package is its workspace if the package is one of its members._<br>
+/// > _The `backend`, `[build]`, `[lints]` and `[profile.*]` of the workspace are the
+/// > ones of the members that do not have them._
+#[derive(Debug)]
+pub struct Project {
+    /// Folder of the shared `.target/` and `target/`.
+    pub root: PathBuf,
+    /// Packages of the command, all the members or the one of `-p`.
+    pub packages: Vec<Manifest>,
+    /// In a workspace the modules have the name of their package (`app.main`).
+    pub workspace: bool,
+}
+impl Project {
+    /// Project of the directory, `-p` selects one of the packages.
+    pub async fn find(cwd: &PathBuf, select: Option<&str>) -> Self {
+        let mut first: Option<(PathBuf, Loaded)> = None;
+        let mut dir: Option<&Path> = Some(cwd.as_path());
+        while let Some(current) = dir {
+            dir = current.parent();
+            let Some(loaded) = Loaded::read(current, cwd).await else {
+                continue;
+            };
+            let source: Source = loaded.source();
+            let file: File = parse(&source);
+            let Some(workspace) = &file.workspace else {
+                if first.is_none() {
+                    first = Some((current.to_path_buf(), loaded));
+                }
+                continue;
+            };
+            // `[lib]` and `[dependencies]` are of the members.
+            if let Some(lib) = &file.lib {
+                invalid(
+                    &source,
+                    Some(lib.span()),
+                    "a workspace is not a library, `[lib]` is of its members",
+                );
+            }
+            if let Some(name) = file.dependencies.keys().next() {
+                invalid(
+                    &source,
+                    Some(name.span()),
+                    "a workspace has no dependencies, `[dependencies]` are of its members",
+                );
+            }
+
+            let shared: Shared = Shared::parse(&file, &source);
+            let mut members: Vec<Manifest> = vec![];
+            for member in &workspace.members {
+                let mut root: PathBuf = current.to_path_buf();
+                root.push(member.get_ref());
+                let Some(loaded) = Loaded::read(&root, cwd).await else {
+                    invalid(
+                        &source,
+                        Some(member.span()),
+                        &format!(
+                            "the member does not have a {}",
+                            lits::std_resources::MANIFEST
+                        ),
+                    );
+                };
+                let root: PathBuf = root.canonicalize().await.unwrap_or(root);
+                let source: Source = loaded.source();
+                members.push(Manifest::parse(root, parse(&source), &source, &shared));
+            }
+            // The package of the directory is a member of the workspace or not.
+            let select: Option<String> = match &first {
+                None => select.map(String::from),
+                Some((root, _)) => {
+                    let root: PathBuf = root.canonicalize().await.unwrap_or(root.clone());
+                    match members.iter().find(|member| member.root == root) {
+                        Some(member) => Some(String::from(select.unwrap_or(&member.package.name))),
+                        None => break,
+                    }
+                }
+            };
+            return Self {
+                root: current.to_path_buf(),
+                packages: self::select(members, select.as_deref()),
+                workspace: true,
+            };
+        }
+
+        let package: Manifest = match &first {
+            Some((root, loaded)) => {
+                let source: Source = loaded.source();
+                Manifest::parse(root.clone(), parse(&source), &source, &Shared::default())
+            }
+            None => Manifest::parse(
+                cwd.clone(),
+                File::default(),
+                &Source::default(),
+                &Shared::default(),
+            ),
+        };
+        Self {
+            root: package.root.clone(),
+            packages: self::select(vec![package], select),
+            workspace: false,
+        }
+    }
+}
+
+/// Package of `-p` or all of them.
+fn select(packages: Vec<Manifest>, select: Option<&str>) -> Vec<Manifest> {
+    let Some(name) = select else {
+        return packages;
+    };
+    let names: Vec<String> = packages
+        .iter()
+        .map(|package| package.package.name.clone())
+        .collect();
+    let selected: Vec<Manifest> = packages
+        .into_iter()
+        .filter(|package| package.package.name == name)
+        .collect();
+    if selected.is_empty() {
+        panic!(
+            "the package {} does not exist, the packages are {}",
+            name.red().bold(),
+            names.join(", ").bold()
+        );
+    }
+    selected
+}
+
+/// `torch.toml` of a folder with the path of its diagnostics.
+#[derive(Debug)]
+struct Loaded {
+    /// Path relative to the current directory.
+    file: String,
+    contents: String,
+}
+impl Loaded {
+    async fn read(dir: &Path, cwd: &Path) -> Option<Self> {
+        let mut path: PathBuf = dir.to_path_buf();
+        path.push(lits::std_resources::MANIFEST);
+        if !path.exists().await {
+            return None;
+        }
+        let contents: String = fs::read_to_string(&path)
+            .await
+            .unwrap_or_else(|err| panic!("{}", err));
+        let file: String = match diff_paths(&path, cwd) {
+            Some(rel) => rel.to_string_lossy().into_owned(),
+            None => path.to_string_lossy().into_owned(),
+        };
+        Some(Self { file, contents })
+    }
+    fn source(&self) -> Source<'_> {
+        Source {
+            file: &self.file,
+            contents: &self.contents,
+        }
+    }
+}
+
+/// Contents of the manifest.
+fn parse(source: &Source) -> File {
+    match source.contents.is_empty() {
+        true => File::default(),
+        false => toml::from_str(source.contents).unwrap_or_else(|err: toml::de::Error| {
+            invalid(source, err.span(), err.message().trim_end())
+        }),
+    }
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    package: Option<RawPackage>,
+    workspace: Option<RawWorkspace>,
+    backend: Option<Spanned<String>>,
+    build: Option<Build>,
+    lints: Option<Lints>,
+    lib: Option<Spanned<Lib>>,
+    #[serde(default)]
+    profile: Profiles,
+    #[serde(default)]
+    dependencies: BTreeMap<Spanned<String>, RawDependency>,
+}
+
+/// `[workspace]`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawWorkspace {
+    /// Folders of the members, relative to the workspace.
+    members: Vec<Spanned<String>>,
+}
+
+/// Settings of a manifest that the members of a workspace inherit.
+#[derive(Debug, Default, Clone)]
+struct Shared {
+    backend: Option<cgen::Backend>,
+    build: Option<Build>,
+    lints: Option<Lints>,
+    dev: Option<cgen::Profile>,
+    release: Option<cgen::Profile>,
+}
+impl Shared {
+    fn parse(file: &File, source: &Source) -> Self {
+        Self {
+            backend: file.backend.as_ref().map(|name| {
+                cgen::Backend::from_name(name.get_ref()).unwrap_or_else(|| {
+                    invalid(
+                        source,
+                        Some(name.span()),
+                        &format!(
+                            "illegal backend `{}`, expected `{}` or `{}`",
+                            name.get_ref(),
+                            lits::backends::C,
+                            lits::backends::CPP
+                        ),
+                    )
+                })
+            }),
+            build: file.build.clone(),
+            lints: file.lints.clone(),
+            dev: file
+                .profile
+                .dev
+                .as_ref()
+                .map(|dev| dev.profile(cgen::Mode::Dev, source)),
+            release: file
+                .profile
+                .release
+                .as_ref()
+                .map(|release| release.profile(cgen::Mode::Release, source)),
+        }
+    }
+    /// Settings of the package or, without them, the ones of the workspace.
+    fn or(self, workspace: &Shared) -> Self {
+        Self {
+            backend: self.backend.or(workspace.backend),
+            build: self.build.or_else(|| workspace.build.clone()),
+            lints: self.lints.or_else(|| workspace.lints.clone()),
+            dev: self.dev.or_else(|| workspace.dev.clone()),
+            release: self.release.or_else(|| workspace.release.clone()),
+        }
+    }
+}
+
+/// `[package]`
+#[derive(Debug)]
+pub struct Package {
+    /// Name of the package and of its executable or libraries.
+    pub name: String,
+    /// `MAJOR.MINOR.PATCH`
+    pub version: String,
+    pub kind: Kind,
+    /// Script of the `main` function, relative to `src`.
+    pub entry: String,
+    /// Folder of the scripts, relative to the root.
+    pub src: String,
+}
+impl Default for Package {
+    fn default() -> Self {
+        Self {
+            name: String::from(lits::std_resources::dot_target::EXE),
+            version: String::from(lits::std_resources::VERSION),
+            kind: Kind::Bin,
+            entry: String::from(lits::std_resources::ENTRY),
+            src: String::from(lits::std_resources::SRC),
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPackage {
+    name: Spanned<String>,
+    version: Spanned<String>,
+    #[serde(default)]
+    kind: Kind,
+    entry: Option<Spanned<String>>,
+    src: Option<Spanned<String>>,
+}
+impl RawPackage {
+    fn validate(self, source: &Source) -> Package {
+        if !is_name(self.name.get_ref()) {
+            invalid(source, Some(self.name.span()), NAME);
+        }
+        // `0.1.0`
+        let version: &str = self.version.get_ref();
+        let components: Vec<&str> = version.split(lits::DOT).collect();
+        if components.len() != 3
+            || components.iter().any(|component| {
+                component.is_empty() || !component.bytes().all(|b| b.is_ascii_digit())
+            })
+        {
+            invalid(
+                source,
+                Some(self.version.span()),
+                "the version is `MAJOR.MINOR.PATCH` (`0.1.0`)",
+            );
+        }
+        if let Some(entry) = &self.entry {
+            if !entry
+                .get_ref()
+                .ends_with(&format!("{}{}", lits::DOT, lits::extensions::T))
+            {
+                invalid(
+                    source,
+                    Some(entry.span()),
+                    &format!("the entry is a `.{}` script", lits::extensions::T),
+                );
+            }
+        }
+        for path in [&self.entry, &self.src].into_iter().flatten() {
+            if path.get_ref().is_empty() || std::path::Path::new(path.get_ref()).is_absolute() {
+                invalid(
+                    source,
+                    Some(path.span()),
+                    "the path is relative to the root of the project",
+                );
+            }
+        }
+        let default: Package = Package::default();
+        Package {
+            name: self.name.into_inner(),
+            version: self.version.into_inner(),
+            kind: self.kind,
+            entry: self.entry.map_or(default.entry, Spanned::into_inner),
+            src: self.src.map_or(default.src, Spanned::into_inner),
+        }
+    }
+}
+
+/// Output of a package (`kind = "lib"`).
+#[derive(Debug, Default, PartialEq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum Kind {
+    /// Executable of the entry script, the other scripts are its modules.
+    #[default]
+    Bin,
+    /// Libraries of all the scripts, without `main`.
+    Lib,
+}
+
+/// `[lib]`, the libraries of a `lib` package.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lib {
+    /// `types = ["static", "shared"]`
+    #[serde(default = "Lib::types")]
+    pub types: Vec<LibType>,
+}
+impl Default for Lib {
+    fn default() -> Self {
+        Self {
+            types: Self::types(),
+        }
+    }
+}
+impl Lib {
+    fn types() -> Vec<LibType> {
+        vec![LibType::Static, LibType::Shared]
+    }
+}
+/// Type of a library.
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum LibType {
+    /// `libname.a`
+    Static,
+    /// `libname.so`
+    Shared,
+}
+
+/// Rule of the names of the packages.
+pub const NAME: &str =
+    "the name of a package has ASCII letters, digits, `_` and `-`, and it does not start with a digit";
+/// Name of a package (`hello`, `net-http`).
+pub fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// `[dependencies]`, the local Torch projects (`strings = { path = "../strings" }`).
+#[derive(Debug)]
+pub struct Dependency {
+    pub name: String,
+    /// Folder of its manifest.
+    pub root: PathBuf,
+}
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawDependency {
+    /// Relative to the root of the dependent.
+    path: Spanned<String>,
+}
+
+/// `[build]`, settings of the compiler.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Build {
+    /// Flags of the C compiler (`--backend c`).
+    #[serde(default)]
+    pub cflags: Vec<String>,
+    /// Flags of the C++ compiler.
+    #[serde(default)]
+    pub cxxflags: Vec<String>,
+    /// Folders of the headers (`-I`), relative to the root.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Folders of the libraries (`-L`), relative to the root.
+    #[serde(default)]
+    pub lib_dirs: Vec<String>,
+    /// Libraries of the executable (`-l`).
+    #[serde(default)]
+    pub libs: Vec<String>,
+}
+
+/// `[lints]`
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lints {
+    /// Warnings of the compiler in the scripts.
+    #[serde(default)]
+    pub warnings: Level,
+}
+/// Level of a lint.
+#[derive(Debug, Default, PartialEq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum Level {
+    /// They are not shown.
+    Allow,
+    /// They are shown.
+    #[default]
+    Warn,
+    /// They are errors.
+    Deny,
+}
+
+/// `[profile.dev]` and `[profile.release]`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profiles {
+    dev: Option<RawProfile>,
+    release: Option<RawProfile>,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawProfile {
+    /// `opt-level = 3` or `opt-level = "s"`
+    opt_level: Option<Spanned<toml::Value>>,
+    debug: Option<bool>,
+    overflow_checks: Option<bool>,
+    bounds_checks: Option<bool>,
+    assertions: Option<bool>,
+    sanitizers: Option<Vec<Spanned<String>>>,
+}
+impl RawProfile {
+    fn profile(&self, mode: cgen::Mode, source: &Source) -> cgen::Profile {
+        let mut profile: cgen::Profile = cgen::Profile::new(mode);
+        if let Some(opt_level) = &self.opt_level {
+            profile.opt_level = match opt_level.get_ref() {
+                toml::Value::Integer(level) => level.to_string(),
+                toml::Value::String(level) => level.clone(),
+                _ => String::new(),
+            };
+            if !lits::profiles::OPT_LEVELS.contains(&profile.opt_level.as_str()) {
+                invalid(
+                    source,
+                    Some(opt_level.span()),
+                    &format!(
+                        "illegal {}, expected one of {}",
+                        lits::profiles::OPT_LEVEL,
+                        lits::profiles::OPT_LEVELS.join(", ")
+                    ),
+                );
+            }
+        }
+        profile.debug = self.debug.unwrap_or(profile.debug);
+        profile.overflow_checks = self.overflow_checks.unwrap_or(profile.overflow_checks);
+        profile.bounds_checks = self.bounds_checks.unwrap_or(profile.bounds_checks);
+        profile.assertions = self.assertions.unwrap_or(profile.assertions);
+        if let Some(sanitizers) = &self.sanitizers {
+            for sanitizer in sanitizers {
+                if !lits::profiles::SANITIZERS.contains(&sanitizer.get_ref().as_str()) {
+                    invalid(
+                        source,
+                        Some(sanitizer.span()),
+                        &format!(
+                            "illegal {}, expected one of {}",
+                            lits::profiles::SANITIZERS_KEY,
+                            lits::profiles::SANITIZERS.join(", ")
+                        ),
+                    );
+                }
+            }
+            profile.sanitizers = sanitizers
+                .iter()
+                .map(|sanitizer| sanitizer.get_ref().clone())
+                .collect();
+        }
+        profile
+    }
+}
+
+/// Manifest file with the path of its diagnostics.
+#[derive(Debug, Default)]
+struct Source<'src> {
+    file: &'src str,
+    contents: &'src str,
+}
+
+/// Error of the manifest at the position of the span (`msg → torch.toml:3:11`).
+fn invalid(source: &Source, span: Option<Range<usize>>, msg: &str) -> ! {
+    let Some(span) = span else {
+        panic!("invalid {}: {}", source.file.red().bold(), msg);
+    };
+    let before: &str = &source.contents[..span.start.min(source.contents.len())];
+    let line: usize = before.matches('\n').count() + 1;
+    let column: usize = match before.rfind('\n') {
+        Some(i) => before[i + 1..].chars().count() + 1,
+        None => before.chars().count() + 1,
+    };
+    panic!(
+        "{} {} {}{}{}{}{}",
+        msg,
+        "→".red().bold(),
+        source.file.bold(),
+        lits::COLON,
+        line,
+        lits::COLON,
+        column
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::task;
+
+    /// Source of the inline manifest.
+    fn source(contents: &str) -> Source<'_> {
+        Source {
+            file: lits::std_resources::MANIFEST,
+            contents,
+        }
+    }
+    /// Manifest of the package, with the settings of its workspace.
+    fn manifest(contents: &str, workspace: &str) -> Manifest {
+        let workspace: Source = source(workspace);
+        let shared: Shared = Shared::parse(&parse(&workspace), &workspace);
+        let source: Source = source(contents);
+        Manifest::parse(PathBuf::from("pkg"), parse(&source), &source, &shared)
+    }
+    /// Message of the panic of the closure, without colors.
+    fn error<T>(f: impl FnOnce() -> T) -> String {
+        colored::control::set_override(false);
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+            Ok(_) => panic!("expecting an error"),
+            Err(err) => err.downcast::<String>().map_or(String::new(), |msg| *msg),
+        }
+    }
+    /// Project of the folder of the files, written in a temporary folder.
+    fn project(test: &str, files: &[(&str, &str)], cwd: &str, select: Option<&str>) -> Project {
+        let dir: std::path::PathBuf =
+            std::env::temp_dir().join(format!("torch_manifest_{}_{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (path, contents) in files {
+            let path: std::path::PathBuf = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        let cwd: PathBuf = PathBuf::from(match cwd {
+            "" => dir.clone(),
+            cwd => dir.join(cwd),
+        });
+        let project: std::thread::Result<Project> =
+            std::panic::catch_unwind(|| task::block_on(Project::find(&cwd, select)));
+        let _ = std::fs::remove_dir_all(&dir);
+        project.unwrap_or_else(|err| std::panic::resume_unwind(err))
+    }
+
+    const PACKAGE: &str = "[package]\nname = \"app\"\nversion = \"0.1.0\"\n";
+
+    #[test]
+    fn packages() {
+        let app: Manifest = manifest(
+            "backend = \"c\"\n\n\
+             [package]\nname = \"net-http\"\nversion = \"1.2.3\"\nkind = \"lib\"\nsrc = \"scripts\"\n\n\
+             [lib]\ntypes = [\"static\"]\n\n\
+             [dependencies]\nstrings = { path = \"../strings\" }\n\n\
+             [profile.release]\nopt-level = \"s\"\nsanitizers = [\"address\", \"undefined\"]\n",
+            "",
+        );
+        assert_eq!(app.package.name, "net-http");
+        assert_eq!(app.package.kind, Kind::Lib);
+        assert_eq!(app.package.src, "scripts");
+        assert_eq!(app.package.entry, lits::std_resources::ENTRY);
+        assert_eq!(app.backend, Some(cgen::Backend::C));
+        assert_eq!(app.lib.types, [LibType::Static]);
+        assert_eq!(app.dependencies[0].name, "strings");
+        assert_eq!(app.dependencies[0].root, PathBuf::from("pkg/../strings"));
+        let release: &cgen::Profile = app.profile(cgen::Mode::Release);
+        assert_eq!(release.opt_level, "s");
+        assert_eq!(release.sanitizers, ["address", "undefined"]);
+        assert!(!release.debug);
+        // The integers are opt-levels too.
+        let app: Manifest = manifest(&format!("{}[profile.dev]\nopt-level = 2\n", PACKAGE), "");
+        assert_eq!(app.profile(cgen::Mode::Dev).opt_level, "2");
+        assert!(app.profile(cgen::Mode::Dev).debug);
+    }
+
+    #[test]
+    fn inheritance() {
+        const WORKSPACE: &str = "backend = \"c\"\n\n\
+             [workspace]\nmembers = [\"app\"]\n\n\
+             [build]\nlibs = [\"m\"]\n\n\
+             [lints]\nwarnings = \"deny\"\n\n\
+             [profile.release]\nopt-level = 1\n";
+        let app: Manifest = manifest(PACKAGE, WORKSPACE);
+        assert_eq!(app.backend, Some(cgen::Backend::C));
+        assert_eq!(app.build.libs, ["m"]);
+        assert_eq!(app.lints.warnings, Level::Deny);
+        assert_eq!(app.profile(cgen::Mode::Release).opt_level, "1");
+        assert_eq!(app.profile(cgen::Mode::Dev).opt_level, "0");
+
+        // The sections of the package replace the ones of the workspace.
+        let app: Manifest = manifest(
+            &format!(
+                "backend = \"cpp\"\n{}\n[build]\ncflags = [\"-Wall\"]\n\n[profile.release]\ndebug = true\n",
+                PACKAGE
+            ),
+            WORKSPACE,
+        );
+        assert_eq!(app.backend, Some(cgen::Backend::Cpp));
+        assert!(app.build.libs.is_empty());
+        assert_eq!(app.build.cflags, ["-Wall"]);
+        assert_eq!(app.lints.warnings, Level::Deny);
+        assert_eq!(app.profile(cgen::Mode::Release).opt_level, "3");
+        assert!(app.profile(cgen::Mode::Release).debug);
+    }
+
+    #[test]
+    fn invalid_packages() {
+        for (contents, msg) in [
+            (
+                "[package]\nname = \"app\"\nversion = \"0.1\"\n",
+                "the version is `MAJOR.MINOR.PATCH` (`0.1.0`) → torch.toml:3:11",
+            ),
+            (
+                "[package]\nname = \"1app\"\nversion = \"0.1.0\"\n",
+                &format!("{} → torch.toml:2:8", NAME),
+            ),
+            (
+                &format!("{}entry = \"main.c\"\n", PACKAGE),
+                "the entry is a `.t` script → torch.toml:4:9",
+            ),
+            (
+                &format!("{}src = \"/src\"\n", PACKAGE),
+                "the path is relative to the root of the project → torch.toml:4:7",
+            ),
+            (
+                &format!("backend = \"rust\"\n{}", PACKAGE),
+                "illegal backend `rust`, expected `c` or `cpp` → torch.toml:1:11",
+            ),
+            (
+                &format!("{}\n[lib]\ntypes = [\"static\"]\n", PACKAGE),
+                "the package is not a library (`kind = \"lib\"`) → torch.toml:5:1",
+            ),
+            (
+                &format!("{}\n[profile.release]\nopt-level = 4\n", PACKAGE),
+                "illegal opt-level, expected one of 0, 1, 2, 3, s, z → torch.toml:6:13",
+            ),
+            (
+                &format!(
+                    "{}\n[profile.dev]\nsanitizers = [\"address\", \"race\"]\n",
+                    PACKAGE
+                ),
+                "illegal sanitizers, expected one of address, undefined, thread, leak, memory \
+                 → torch.toml:6:26",
+            ),
+            (
+                &format!(
+                    "{}\n[dependencies]\n\"a b\" = {{ path = \"../a\" }}\n",
+                    PACKAGE
+                ),
+                &format!("{} → torch.toml:6:1", NAME),
+            ),
+            (
+                "[build]\nlibs = [\"m\"]\n",
+                "invalid torch.toml: missing table `package`",
+            ),
+            (
+                &format!("{}\n[workspace]\nmembers = []\n", PACKAGE),
+                "invalid torch.toml: a workspace is not a package",
+            ),
+        ] {
+            let err: String = error(|| manifest(contents, ""));
+            assert!(err.starts_with(msg), "{}\n{}", err, msg);
+        }
+    }
+
+    #[test]
+    fn workspaces() {
+        const WORKSPACE: (&str, &str) = (
+            "torch.toml",
+            "[workspace]\nmembers = [\"app\", \"libs/strings\"]\n",
+        );
+        let files: [(&str, &str); 3] = [
+            WORKSPACE,
+            ("app/torch.toml", PACKAGE),
+            (
+                "libs/strings/torch.toml",
+                "[package]\nname = \"strings\"\nversion = \"0.1.0\"\nkind = \"lib\"\n",
+            ),
+        ];
+        let names = |project: &Project| -> Vec<String> {
+            project
+                .packages
+                .iter()
+                .map(|package| package.package.name.clone())
+                .collect()
+        };
+
+        // All the members from the workspace, the one of `-p` or the one of the directory.
+        let all: Project = project("all", &files, "", None);
+        assert!(all.workspace);
+        assert_eq!(names(&all), ["app", "strings"]);
+        assert_eq!(
+            names(&project("select", &files, "", Some("strings"))),
+            ["strings"]
+        );
+        assert_eq!(names(&project("member", &files, "app", None)), ["app"]);
+        assert_eq!(
+            names(&project("member_select", &files, "app", Some("strings"))),
+            ["strings"]
+        );
+        let err: String = error(|| project("unknown", &files, "", Some("http")));
+        assert_eq!(
+            err,
+            "the package http does not exist, the packages are app, strings"
+        );
+
+        // A package outside of the members is not of the workspace.
+        let outside: Project = project(
+            "outside",
+            &[files[0], files[1], files[2], ("tools/torch.toml", PACKAGE)],
+            "tools",
+            None,
+        );
+        assert!(!outside.workspace);
+
+        for (contents, msg) in [
+            (
+                "[workspace]\nmembers = [\"app\"]\n\n[lib]\ntypes = [\"static\"]\n",
+                "a workspace is not a library, `[lib]` is of its members → torch.toml:4:1",
+            ),
+            (
+                "[workspace]\nmembers = [\"app\"]\n\n[dependencies]\nstrings = { path = \"strings\" }\n",
+                "a workspace has no dependencies, `[dependencies]` are of its members \
+                 → torch.toml:5:1",
+            ),
+            (
+                "[workspace]\nmembers = [\"app\", \"http\"]\n",
+                "the member does not have a torch.toml → torch.toml:2:19",
+            ),
+        ] {
+            let err: String = error(|| {
+                project(
+                    "invalid",
+                    &[("torch.toml", contents), ("app/torch.toml", PACKAGE)],
+                    "",
+                    None,
+                )
+            });
+            assert!(err.starts_with(msg), "{}\n{}", err, msg);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::manifest;
+use async_std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use colored::Colorize;
+use torchc_lits::lits;
+
+/// `src/main.t` of a new package.
+const MAIN: &str = r#"extern "c" fn puts(s: str) i32
+
+fn main()
+    puts("Hello, world!")
+"#;
+/// `.gitignore` of a new package, the generated files.
+const GITIGNORE: &str = "/.target/\n/target/\n";
+
+/// Create the package in the folder (`torch new`, `torch init`): the manifest, a
+/// hello world and the `.gitignore` of the generated files.
+///
+/// ---
+/// > _The existing files are never overwritten, nothing is created if one of them
+/// > exists._
+pub async fn new(dir: &Path, name: &str, quiet: bool) {
+    if !manifest::is_name(name) {
+        panic!(
+            "illegal package name {}, {}",
+            name.red().bold(),
+            manifest::NAME
+        );
+    }
+
+    let manifest: PathBuf = dir.join(lits::std_resources::MANIFEST);
+    let src: PathBuf = dir.join(lits::std_resources::SRC);
+    let main: PathBuf = src.join(lits::std_resources::ENTRY);
+    let gitignore: PathBuf = dir.join(lits::std_resources::GITIGNORE);
+    for file in [&manifest, &main, &gitignore] {
+        if file.exists().await {
+            panic!(
+                "{} already exists, it is not overwritten",
+                match file.strip_prefix(dir) {
+                    Ok(rel) => rel.to_string_lossy(),
+                    Err(_) => file.to_string_lossy(),
+                }
+                .red()
+                .bold()
+            );
+        }
+    }
+
+    fs::create_dir_all(&src)
+        .await
+        .unwrap_or_else(|err| panic!("{}", err));
+    for (file, contents) in [
+        (
+            &manifest,
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+        ),
+        (&main, String::from(MAIN)),
+        (&gitignore, String::from(GITIGNORE)),
+    ] {
+        fs::write(file, contents)
+            .await
+            .unwrap_or_else(|err| panic!("{}", err));
+    }
+    if !quiet {
+        eprintln!("{} package {}", "Created".green().bold(), name.bold());
+    }
+}
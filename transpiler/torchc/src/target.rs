@@ -0,0 +1,147 @@
+use async_std::{
+    fs,
+    path::{Path, PathBuf},
+    stream::StreamExt,
+};
+use colored::Colorize;
+use std::{future::Future, pin::Pin};
+use torchc_lits::lits;
+
+/// Create a folder of the generated files (`.target/`, `.target/dev/`, `target/`) with
+/// the marker of the compiler.
+///
+/// ---
+/// > _The marker is only written in the folders created by the compiler, a `target/`
+/// > of the user is never removed by `torch clean`._
+pub async fn create(dir: &Path) {
+    if dir.exists().await {
+        return;
+    }
+    fs::create_dir_all(dir)
+        .await
+        .unwrap_or_else(|err| panic!("{}", err));
+    fs::write(
+        dir.join(lits::std_resources::dot_target::MARKER),
+        lits::std_resources::dot_target::MARKER_CONTENTS,
+    )
+    .await
+    .unwrap_or_else(|err| panic!("{}", err));
+}
+
+/// Remove the generated files of the development and release builds of the project
+/// (`torch clean`), without the modes both are removed. `root` is the folder of
+/// `.target/` and `target/`, the root of the project or `--target-dir`.
+///
+/// ---
+/// > _The release code is in `.target/` next to `dev/`, and its executables and
+/// > libraries in `target/`._
+pub async fn clean(root: &Path, dev: bool, release: bool, dry_run: bool, quiet: bool) {
+    let (dev, release): (bool, bool) = match (dev, release) {
+        (false, false) => (true, true),
+        modes => modes,
+    };
+    let dot_target: PathBuf = root.join(lits::std_resources::dot_target::NAME);
+
+    // Folders with the entry that is kept (`.target/dev/` of `--release`).
+    let mut folders: Vec<(PathBuf, Option<&str>)> = vec![];
+    match (dev, release) {
+        (true, true) => folders.push((dot_target, None)),
+        (true, false) => {
+            folders.push((dot_target.join(lits::std_resources::dot_target::DEV), None))
+        }
+        _ => folders.push((dot_target, Some(lits::std_resources::dot_target::DEV))),
+    }
+    if release {
+        folders.push((root.join(lits::std_resources::TARGET), None));
+    }
+
+    let rel = |path: &Path| match path.strip_prefix(root) {
+        Ok(rel) => rel.to_string_lossy().into_owned(),
+        Err(_) => path.to_string_lossy().into_owned(),
+    };
+    let mut removed: usize = 0;
+    for (folder, kept) in folders {
+        if !folder.exists().await {
+            continue;
+        }
+        if !folder
+            .join(lits::std_resources::dot_target::MARKER)
+            .exists()
+            .await
+        {
+            eprintln!(
+                "{}{} {} is not removed, it was not created by torch (no {})",
+                lits::WPREFIX.yellow().bold(),
+                lits::COLON.bold(),
+                rel(&folder).bold(),
+                lits::std_resources::dot_target::MARKER
+            );
+            continue;
+        }
+
+        // `.target/` or its entries without `dev/`.
+        let mut entries: Vec<PathBuf> = vec![];
+        match kept {
+            None => entries.push(folder.clone()),
+            Some(kept) => {
+                let mut dir = fs::read_dir(&folder)
+                    .await
+                    .unwrap_or_else(|err| panic!("{}", err));
+                while let Some(entry) = dir.next().await {
+                    let path: PathBuf = entry.unwrap_or_else(|err| panic!("{}", err)).path();
+                    let name = path.file_name().unwrap_or_default();
+                    if name != kept && name != lits::std_resources::dot_target::MARKER {
+                        entries.push(path);
+                    }
+                }
+            }
+        }
+
+        for entry in entries {
+            let files: Vec<PathBuf> = walk(entry.clone()).await;
+            if dry_run {
+                for file in &files {
+                    println!("{}", rel(file));
+                }
+            } else if entry.is_dir().await {
+                fs::remove_dir_all(&entry)
+                    .await
+                    .unwrap_or_else(|err| panic!("{}", err));
+            } else {
+                fs::remove_file(&entry)
+                    .await
+                    .unwrap_or_else(|err| panic!("{}", err));
+            }
+            removed += files.len();
+        }
+    }
+    if quiet {
+        return;
+    }
+    eprintln!(
+        "{} {} files",
+        match dry_run {
+            true => "Would remove".yellow().bold(),
+            false => "Removed".green().bold(),
+        },
+        removed
+    );
+}
+
+/// Files of the folder and of its subfolders, or the file itself.
+fn walk(path: PathBuf) -> Pin<Box<dyn Future<Output = Vec<PathBuf>>>> {
+    Box::pin(async move {
+        if !path.is_dir().await {
+            return vec![path];
+        }
+        let mut files: Vec<PathBuf> = vec![];
+        let mut dir = fs::read_dir(&path)
+            .await
+            .unwrap_or_else(|err| panic!("{}", err));
+        while let Some(entry) = dir.next().await {
+            files.extend(walk(entry.unwrap_or_else(|err| panic!("{}", err)).path()).await);
+        }
+        files.sort();
+        files
+    })
+}
@@ -0,0 +1,112 @@
+use serde_json::{json, Value};
+use std::{env, process::Command};
+use torchc_lits::lits;
+
+/// Version of the compiler from its manifest.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Version of the language, `MAJOR.MINOR` of the compiler.
+const LANGUAGE: &str = concat!(
+    env!("CARGO_PKG_VERSION_MAJOR"),
+    ".",
+    env!("CARGO_PKG_VERSION_MINOR")
+);
+/// Commit of the compiler, set by `build.rs` if it was built in the repository.
+const COMMIT: Option<&str> = option_env!("TORCH_COMMIT");
+const COMMIT_DATE: Option<&str> = option_env!("TORCH_COMMIT_DATE");
+const HOST: &str = env!("TORCH_HOST");
+
+/// C/C++ compiler of the generated code.
+struct Toolchain {
+    /// `c` or `cpp`
+    backend: &'static str,
+    /// `CC` or `cc`
+    program: String,
+    /// First line of `cc --version`, `None` if it cannot run.
+    version: Option<String>,
+}
+impl Toolchain {
+    fn detect(backend: &'static str, var: &str, default: &str) -> Self {
+        let program: String = env::var(var).unwrap_or_else(|_| String::from(default));
+        let version: Option<String> = Command::new(&program)
+            .arg(lits::compilers::VERSION)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .next()
+                    .map(|line| String::from(line.trim()))
+            });
+        Self {
+            backend,
+            program,
+            version,
+        }
+    }
+}
+
+/// Print the versions of the compiler and of the language, the commit it was built
+/// from and the C/C++ compilers (`torch version`), in JSON with `--json`.
+pub fn version(json: bool) {
+    let toolchains: [Toolchain; 2] = [
+        Toolchain::detect(
+            lits::backends::C,
+            lits::compilers::CC,
+            lits::compilers::DEFAULT_CC,
+        ),
+        Toolchain::detect(
+            lits::backends::CPP,
+            lits::compilers::CXX,
+            lits::compilers::DEFAULT_CXX,
+        ),
+    ];
+
+    if json {
+        let compilers: serde_json::Map<String, Value> = toolchains
+            .iter()
+            .map(|toolchain| {
+                (
+                    String::from(toolchain.backend),
+                    json!({
+                        "program": toolchain.program,
+                        "version": toolchain.version,
+                    }),
+                )
+            })
+            .collect();
+        return println!(
+            "{}",
+            json!({
+                "version": VERSION,
+                "language": LANGUAGE,
+                "commit": COMMIT,
+                "commit_date": COMMIT_DATE,
+                "host": HOST,
+                "compilers": compilers,
+            })
+        );
+    }
+
+    // `torch 0.1.0 (117be78c6 2026-10-19)`
+    match COMMIT {
+        Some(commit) => println!(
+            "torch {} ({} {})",
+            VERSION,
+            &commit[..commit.len().min(9)],
+            COMMIT_DATE.unwrap_or_default()
+        ),
+        None => println!("torch {}", VERSION),
+    }
+    println!("language: {}", LANGUAGE);
+    println!("commit: {}", COMMIT.unwrap_or("unknown"));
+    println!("host: {}", HOST);
+    for toolchain in &toolchains {
+        println!(
+            "{}: {} ({})",
+            toolchain.backend,
+            toolchain.version.as_deref().unwrap_or("not found"),
+            toolchain.program
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// Package `pk` with the scripts of its `src/` folder in a temporary folder.
+fn package(test: &str, scripts: &[(&str, &str)]) -> PathBuf {
+    let dir: PathBuf = env::temp_dir().join(format!("torch_build_{}_{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("torch.toml"),
+        "[package]\nname = \"pk\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    for (name, script) in scripts {
+        fs::write(dir.join("src").join(name), script).unwrap();
+    }
+    dir
+}
+
+/// Build the package with `-j jobs`, the generated files are in `dir/target_dir`.
+fn build(dir: &Path, jobs: usize) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_torch"))
+        .args(["build", "--quiet", "-j", &jobs.to_string(), "--target-dir"])
+        .arg(dir.join("target_dir"))
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+/// Whether the target folder has a generated file of that name.
+fn generated(dir: &Path, name: &str) -> bool {
+    fn find(dir: &Path, name: &str) -> bool {
+        fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .any(|entry| {
+                let path: PathBuf = entry.path();
+                match path.is_dir() {
+                    true => find(&path, name),
+                    false => path.file_name().is_some_and(|file| file == name),
+                }
+            })
+    }
+    find(&dir.join("target_dir"), name)
+}
+
+#[test]
+fn generation_stops_at_the_first_failure() {
+    let dir: PathBuf = package(
+        "generation",
+        &[
+            ("a.t", "fn main()\n    return\n"),
+            ("b.t", "fn main()\n    return\n"),
+            ("main.t", "fn main()\n    return\n"),
+        ],
+    );
+
+    // One job at a time, the scripts after the failed one are not generated.
+    let output: Output = build(&dir, 1);
+    let stderr: String = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert!(!output.status.success());
+    assert!(stderr.contains("pk.a"), "{}", stderr);
+    assert!(!stderr.contains("pk.b"), "{}", stderr);
+    assert!(!generated(&dir, "b.cpp"));
+
+    // In parallel, the diagnostic is the one of the first script whatever the order
+    // in which the jobs end.
+    for _ in 0..5 {
+        assert_eq!(String::from_utf8_lossy(&build(&dir, 8).stderr), stderr);
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn compilation_stops_at_the_first_failure() {
+    let dir: PathBuf = package(
+        "compilation",
+        &[
+            (
+                "a.t",
+                "fn f() i64\n    cpp\n        #error first\n    return 1\n",
+            ),
+            (
+                "b.t",
+                "fn g() i64\n    cpp\n        #error second\n    return 1\n",
+            ),
+            ("main.t", "fn main()\n    return\n"),
+        ],
+    );
+
+    // All the scripts are generated, the objects are compiled in parallel and the
+    // diagnostics are reported in the order of the modules.
+    for _ in 0..5 {
+        let output: Output = build(&dir, 8);
+        let stderr: String = String::from_utf8_lossy(&output.stderr).into_owned();
+        assert!(!output.status.success());
+        assert!(stderr.contains("#error first → src/a.t"), "{}", stderr);
+        assert!(!stderr.contains("second"), "{}", stderr);
+        assert!(generated(&dir, "b.cpp"));
+        assert!(!generated(&dir, "pk"));
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
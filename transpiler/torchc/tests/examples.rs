@@ -0,0 +1,101 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// Run the package of `examples/` with its generated files in a temporary folder, the
+/// arguments are the ones of `torch run`.
+fn run(example: &str, args: &[&str]) -> Output {
+    let dir: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples")
+        .join(example);
+    let target: PathBuf = env::temp_dir().join(format!(
+        "torch_example_{}_{}",
+        example.replace('/', "_"),
+        std::process::id()
+    ));
+    let output: Output = Command::new(env!("CARGO_BIN_EXE_torch"))
+        .args(["run", "--quiet", "--target-dir"])
+        .arg(&target)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    let _ = fs::remove_dir_all(&target);
+    output
+}
+
+#[test]
+fn dependencies() {
+    let output: Output = run("dependencies/app", &[]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Hello, world, 1 + 2 = 3\n"
+    );
+}
+
+#[test]
+fn cpp_blocks() {
+    let output: Output = run("cpp", &[]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "half(10) = 5, quarter(40) = 10\n"
+    );
+}
+
+#[test]
+fn modules() {
+    let output: Output = run("modules", &[]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Hello, modules, area = 12\n"
+    );
+}
+
+#[test]
+fn c_backend() {
+    let output: Output = run("c", &[]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "5 4 3\n11 31 63 6 52\ndeferred twice(10)\n20 true\ndeferred propagate\ntrue odd 3\n3 true\n7\né 6\nin 0\nloop 0\nin 1\nloop 1\nin 2\nloop 2\nbye\n"
+    );
+}
+
+#[test]
+fn defer() {
+    for backend in ["cpp", "c"] {
+        let output: Output = run("defer", &["--backend", backend]);
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "second\nfirst\nreturned 1\nbody 0\nloop second 0\nloop first 0\nend\nmain\n",
+            "{}",
+            backend
+        );
+    }
+}
@@ -0,0 +1,592 @@
+use crate::{
+    lex::{Define, Tok, Token},
+    Skipped,
+};
+use std::collections::HashMap;
+use torchc_lits::lits;
+
+/// Names that cannot be declared in the language: its keywords and built-in types.
+fn reserved(name: &str) -> bool {
+    lits::token_table::KEYWORDS.contains(&name) || lits::types::ALL.contains(&name)
+}
+
+/// Types of `<stdint.h>` and `<stddef.h>` with the same C++ type as the language.
+const STDINT: [(&str, &str); 10] = [
+    ("int8_t", lits::types::I8),
+    ("int16_t", lits::types::I16),
+    ("int32_t", lits::types::I32),
+    ("int64_t", lits::types::I64),
+    ("uint8_t", lits::types::U8),
+    ("uint16_t", lits::types::U16),
+    ("uint32_t", lits::types::U32),
+    ("uint64_t", lits::types::U64),
+    ("size_t", lits::types::U64),
+    ("ssize_t", lits::types::I64),
+];
+
+/// Qualifiers that do not change the type.
+const QUALIFIERS: [&str; 5] = ["const", "volatile", "restrict", "__restrict", "extern"];
+
+/// Type named by a `typedef`.
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum Alias {
+    /// Type of the language (`typedef unsigned int id_t`).
+    Ty(&'static str),
+    /// Kind of the unsupported type (`typedef struct {...} point`).
+    Tag(&'static str),
+}
+
+/// Declaration of the header (`...;` or a function definition `...{...}`).
+pub struct Decl {
+    pub tokens: Vec<Token>,
+    /// Function definition (`type name(...) {...}`)?
+    pub body: bool,
+}
+
+/// It splits the tokens into the declarations of the root of the header.
+///
+/// ---
+/// > _The `extern "C" {...}` blocks of the C++ guards are transparent._
+pub fn split(tokens: &[Token]) -> Vec<Decl> {
+    let mut decls: Vec<Decl> = vec![];
+    let mut decl: Vec<Token> = vec![];
+    let mut depth: usize = 0;
+    let mut body: bool = false;
+
+    for token in tokens {
+        match &token.tok {
+            // `extern "C" {`
+            Tok::Punct('{')
+                if depth == 0
+                    && decl.len() == 2
+                    && decl[0].tok == Tok::Id(String::from("extern"))
+                    && decl[1].tok == Tok::Lit(String::from("\"C\"")) =>
+            {
+                decl.clear();
+                continue;
+            }
+            // `}` of `extern "C" {`
+            Tok::Punct('}') if depth == 0 => continue,
+            Tok::Punct(';') if depth == 0 => {
+                if !decl.is_empty() {
+                    decls.push(Decl {
+                        tokens: std::mem::take(&mut decl),
+                        body: false,
+                    });
+                }
+                continue;
+            }
+            Tok::Punct('{') if depth == 0 => {
+                body = decl
+                    .last()
+                    .is_some_and(|token| token.tok == Tok::Punct(')'));
+                depth += 1;
+            }
+            Tok::Punct('(' | '[' | '{') => depth += 1,
+            Tok::Punct(')' | ']') => depth = depth.saturating_sub(1),
+            Tok::Punct('}') => {
+                depth -= 1;
+                // The function definitions do not end with `;`.
+                if depth == 0 && body {
+                    decl.push(token.clone());
+                    decls.push(Decl {
+                        tokens: std::mem::take(&mut decl),
+                        body: true,
+                    });
+                    body = false;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        decl.push(token.clone());
+    }
+    decls
+}
+
+/// Generator of the bindings of a header.
+#[derive(Default)]
+pub struct Gen {
+    /// `extern "c" const NAME: type`
+    pub consts: Vec<String>,
+    /// `// name: type` of the `typedef`
+    pub aliases: Vec<String>,
+    /// `extern "c" fn name(arg1: type, ...) type`
+    pub fns: Vec<String>,
+    pub skipped: Vec<Skipped>,
+    /// Names of the `typedef`.
+    types: HashMap<String, Alias>,
+}
+impl Gen {
+    /// `#define NAME value`
+    pub fn define(&mut self, define: &Define) {
+        let value: &[Tok] = match &define.value {
+            // Function-like macro.
+            None => {
+                return self.skip(
+                    define.line,
+                    format!("macro '{}'", define.name),
+                    "function-like macros are not supported",
+                )
+            }
+            // Include guards and flags.
+            Some(value) if value.is_empty() => return,
+            Some(value) => value,
+        };
+        match int(value) {
+            Some((value, unsigned)) => {
+                let ty: &str = match (unsigned, value) {
+                    (true, value) if value <= u32::MAX as i128 => lits::types::U32,
+                    (true, _) => lits::types::U64,
+                    (false, value) if i32::try_from(value).is_ok() => lits::types::I32,
+                    (false, _) => lits::types::I64,
+                };
+                self.constant(define.line, &define.name, ty)
+            }
+            None => self.skip(
+                define.line,
+                format!("macro '{}'", define.name),
+                "only the integer constants are supported",
+            ),
+        }
+    }
+
+    /// Declaration of the root of the header.
+    pub fn decl(&mut self, decl: &Decl) {
+        let line: usize = decl.tokens[0].line;
+        let mut tokens: &[Token] = &decl.tokens;
+        if id(&tokens[0]) == Some("extern") {
+            tokens = &tokens[1..];
+        }
+        let first: Option<&str> = tokens.first().and_then(id);
+
+        if decl.body {
+            return self.skip(
+                line,
+                format!("function '{}'", fn_name(tokens)),
+                "the function definitions are not supported, only the prototypes",
+            );
+        }
+        match first {
+            Some("typedef") => self.typedef(line, &tokens[1..]),
+            Some("static" | "inline") => self.skip(
+                line,
+                format!("function '{}'", fn_name(tokens)),
+                "the static and inline declarations have no linkage",
+            ),
+            Some(kind @ ("struct" | "union"))
+                if tokens.len() <= 2 || tokens[2].tok == Tok::Punct('{') =>
+            {
+                self.skip(
+                    line,
+                    format!("{} '{}'", kind, tokens.get(1).and_then(id).unwrap_or("")),
+                    &format!("{}s are not supported by the language yet", kind),
+                )
+            }
+            Some("enum") if tokens.iter().any(|token| token.tok == Tok::Punct('{')) => {
+                let name: &str = tokens.get(1).and_then(id).unwrap_or("");
+                self.enumerators(line, name, tokens);
+            }
+            _ if tokens.iter().any(|token| token.tok == Tok::Punct('(')) => {
+                self.function(line, tokens)
+            }
+            _ => self.skip(
+                line,
+                format!(
+                    "variable '{}'",
+                    tokens.iter().rev().find_map(id).unwrap_or("")
+                ),
+                "the global variables are not supported",
+            ),
+        }
+    }
+
+    /// `typedef type name`
+    fn typedef(&mut self, line: usize, tokens: &[Token]) {
+        let name: &str = match tokens.last().and_then(id) {
+            Some(name) => name,
+            // `typedef type (*name)(...)`
+            None => {
+                return match fn_ptr_name(tokens) {
+                    Some(name) => self.skip(
+                        line,
+                        format!("typedef '{}'", name),
+                        "the function pointers are not supported",
+                    ),
+                    // `typedef type name[N]`
+                    None => self.skip(
+                        line,
+                        format!(
+                            "typedef '{}'",
+                            tokens.iter().rev().find_map(id).unwrap_or("")
+                        ),
+                        "the array types are not supported",
+                    ),
+                };
+            }
+        };
+        let decl: String = format!("typedef '{}'", name);
+        match tokens.first().and_then(id) {
+            Some("struct") => {
+                self.types.insert(String::from(name), Alias::Tag("struct"));
+                self.skip(line, decl, "structs are not supported by the language yet")
+            }
+            Some("union") => {
+                self.types.insert(String::from(name), Alias::Tag("union"));
+                self.skip(line, decl, "unions are not supported by the language yet")
+            }
+            Some("enum") => {
+                self.types.insert(String::from(name), Alias::Tag("enum"));
+                self.enumerators(line, name, tokens);
+            }
+            _ => match ty(&tokens[..tokens.len() - 1], &self.types) {
+                Ok(Some(ty)) => {
+                    self.types.insert(String::from(name), Alias::Ty(ty));
+                    self.aliases.push(format!("// {}: {}", name, ty));
+                }
+                Ok(None) => self.skip(line, decl, "'void' has no type in the language"),
+                Err(reason) => self.skip(line, decl, &reason),
+            },
+        }
+    }
+
+    /// `enum name { NAME, NAME = value, ... }`
+    ///
+    /// ---
+    /// > _The enumerators are bound as constants, the type of the enumeration is
+    /// > distinct in C++ and it is not bound._
+    fn enumerators(&mut self, line: usize, name: &str, tokens: &[Token]) {
+        let open: usize = tokens
+            .iter()
+            .position(|token| token.tok == Tok::Punct('{'))
+            .unwrap_or(tokens.len());
+        let close: usize = tokens
+            .iter()
+            .rposition(|token| token.tok == Tok::Punct('}'))
+            .unwrap_or(tokens.len());
+        if open >= close {
+            return self.skip(
+                line,
+                format!("enum '{}'", name),
+                "expecting the enumerators",
+            );
+        }
+        for enumerator in tokens[open + 1..close].split(|token| token.tok == Tok::Punct(',')) {
+            if let Some(token) = enumerator.first() {
+                if let Some(name) = id(token) {
+                    self.constant(token.line, name, lits::types::I32);
+                }
+            }
+        }
+    }
+
+    /// `type name(type arg1, type arg2, ...)`
+    fn function(&mut self, line: usize, tokens: &[Token]) {
+        let open: usize = tokens
+            .iter()
+            .position(|token| token.tok == Tok::Punct('('))
+            .unwrap();
+        // `type (*name)(...)`
+        if let Some(name) = fn_ptr_name(tokens) {
+            return self.skip(
+                line,
+                format!("variable '{}'", name),
+                "the function pointers are not supported",
+            );
+        }
+        let name: &str = match open.checked_sub(1).and_then(|i| id(&tokens[i])) {
+            Some(name) => name,
+            None => return self.skip(line, String::from("function"), "expecting the name"),
+        };
+        let decl: String = format!("function '{}'", name);
+        if reserved(name) {
+            return self.skip(
+                line,
+                decl,
+                &format!("'{}' is reserved by the language", name),
+            );
+        }
+
+        let ret: Option<&str> = match ty(&tokens[..open - 1], &self.types) {
+            Ok(ret) => ret,
+            Err(reason) => return self.skip(line, decl, &reason),
+        };
+
+        // `(type arg1, type arg2, ...)`
+        let mut close: usize = open;
+        let mut depth: usize = 0;
+        for (i, token) in tokens.iter().enumerate().skip(open) {
+            match token.tok {
+                Tok::Punct('(') => depth += 1,
+                Tok::Punct(')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = i;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let args: &[Token] = &tokens[open + 1..close];
+        let mut params: Vec<String> = vec![];
+        // `()` and `(void)` have no parameters.
+        if !(args.is_empty() || args.len() == 1 && id(&args[0]) == Some("void")) {
+            for (i, arg) in args.split(|token| token.tok == Tok::Punct(',')).enumerate() {
+                match param(arg, i, &self.types) {
+                    Ok(param) => params.push(param),
+                    Err(reason) => return self.skip(line, decl, &reason),
+                }
+            }
+        }
+
+        let mut sig: String = format!(
+            "{} \"{}\" {} {}({})",
+            lits::token_table::EXTERN,
+            lits::abi::C,
+            lits::token_table::FN,
+            name,
+            params.join(", ")
+        );
+        if let Some(ret) = ret {
+            sig.push(' ');
+            sig.push_str(ret);
+        }
+        self.fns.push(sig);
+    }
+
+    /// `extern "c" const NAME: type`
+    fn constant(&mut self, line: usize, name: &str, ty: &str) {
+        if reserved(name) {
+            return self.skip(
+                line,
+                format!("constant '{}'", name),
+                &format!("'{}' is reserved by the language", name),
+            );
+        }
+        self.consts.push(format!(
+            "{} \"{}\" {} {}: {}",
+            lits::token_table::EXTERN,
+            lits::abi::C,
+            lits::token_table::CONST,
+            name,
+            ty
+        ));
+    }
+
+    fn skip(&mut self, line: usize, decl: String, reason: &str) {
+        self.skipped.push(Skipped {
+            line,
+            decl,
+            reason: String::from(reason),
+        });
+    }
+}
+
+/// `type name` of a parameter, the unnamed parameters are called `argN`.
+fn param(tokens: &[Token], i: usize, types: &HashMap<String, Alias>) -> Result<String, String> {
+    if tokens.iter().any(|token| token.tok == Tok::Ellipsis) {
+        return Err(String::from("the variadic functions are not supported"));
+    }
+    if tokens.iter().any(|token| token.tok == Tok::Punct('[')) {
+        return Err(String::from("the array parameters are not supported"));
+    }
+    if tokens.iter().any(|token| token.tok == Tok::Punct('(')) {
+        return Err(String::from("the function pointers are not supported"));
+    }
+    let (ty_tokens, name): (&[Token], Option<&str>) = match tokens.split_last() {
+        Some((last, rest)) if !rest.is_empty() => match id(last) {
+            Some(name) if !is_type_word(name, types) => (rest, Some(name)),
+            _ => (tokens, None),
+        },
+        _ => (tokens, None),
+    };
+    let name: String = match name {
+        Some(name) if !reserved(name) => String::from(name),
+        _ => format!("arg{}", i),
+    };
+    match ty(ty_tokens, types)? {
+        Some(ty) => Ok(format!("{}: {}", name, ty)),
+        None => Err(String::from("'void' has no type in the language")),
+    }
+}
+
+/// Type of the language of the C type (`None` for `void`).
+///
+/// ---
+/// > _The types are only bound when their C++ type is the same as the type of the
+/// > language, otherwise the declarations would conflict (`char`, `long long`, ...)._
+fn ty(tokens: &[Token], types: &HashMap<String, Alias>) -> Result<Option<&'static str>, String> {
+    let words: Vec<&str> = tokens
+        .iter()
+        .filter_map(id)
+        .filter(|word| !QUALIFIERS.contains(word))
+        .collect();
+    let spelling: String = words.join(" ");
+    let ptrs: usize = tokens
+        .iter()
+        .filter(|token| token.tok == Tok::Punct('*'))
+        .count();
+
+    if let Some(kind @ ("struct" | "union" | "enum")) = words.first().copied() {
+        return Err(format!(
+            "{} types are not supported by the language yet",
+            kind
+        ));
+    }
+    if ptrs > 0 {
+        // `const char *`
+        let star: usize = tokens
+            .iter()
+            .position(|token| token.tok == Tok::Punct('*'))
+            .unwrap();
+        let constant: bool = tokens[..star]
+            .iter()
+            .any(|token| id(token) == Some("const"));
+        if ptrs == 1 && constant && spelling == "char" {
+            return Ok(Some(lits::types::STR));
+        }
+        return Err(format!(
+            "'{}{}' has no type in the language",
+            spelling,
+            " *".repeat(ptrs)
+        ));
+    }
+
+    if let [word] = words[..] {
+        match types.get(word) {
+            Some(Alias::Ty(ty)) => return Ok(Some(ty)),
+            Some(Alias::Tag(kind)) => {
+                return Err(format!(
+                    "{} types are not supported by the language yet",
+                    kind
+                ))
+            }
+            None => {}
+        }
+        if let Some((_, ty)) = STDINT.iter().find(|(name, _)| *name == word) {
+            return Ok(Some(ty));
+        }
+    }
+
+    let count = |word: &str| words.iter().filter(|w| **w == word).count();
+    let unsigned: bool = count("unsigned") > 0;
+    let known: usize = count("signed")
+        + count("unsigned")
+        + count("char")
+        + count("short")
+        + count("int")
+        + count("long")
+        + count("float")
+        + count("double")
+        + count("void")
+        + count("bool");
+    if words.is_empty() || known != words.len() {
+        return Err(format!("unknown type '{}'", spelling));
+    }
+    Ok(Some(match (count("long"), unsigned) {
+        _ if count("void") > 0 => return Ok(None),
+        _ if count("bool") > 0 => lits::types::BOOL,
+        _ if count("float") > 0 => lits::types::F32,
+        (0, _) if count("double") > 0 => lits::types::F64,
+        _ if count("char") > 0 && count("signed") == 0 && !unsigned => {
+            return Err(String::from(
+                "'char' has no type in the language, its sign depends on the platform",
+            ))
+        }
+        _ if count("char") > 0 && unsigned => lits::types::U8,
+        _ if count("char") > 0 => lits::types::I8,
+        _ if count("short") > 0 && unsigned => lits::types::U16,
+        _ if count("short") > 0 => lits::types::I16,
+        (0, true) => lits::types::U32,
+        (0, false) => lits::types::I32,
+        (1, _) if count("double") > 0 => {
+            return Err(String::from("'long double' has no type in the language"))
+        }
+        (1, true) => lits::types::U64,
+        (1, false) => lits::types::I64,
+        _ => {
+            return Err(format!(
+                "'{}' has no type in the language, use '{}int64_t'",
+                spelling,
+                if unsigned { "u" } else { "" }
+            ))
+        }
+    }))
+}
+
+/// Word of a C type (`int`, `unsigned`, `size_t`, ...).
+fn is_type_word(word: &str, types: &HashMap<String, Alias>) -> bool {
+    QUALIFIERS.contains(&word)
+        || [
+            "signed", "unsigned", "char", "short", "int", "long", "float", "double", "void", "bool",
+        ]
+        .contains(&word)
+        || types.contains_key(word)
+        || STDINT.iter().any(|(name, _)| *name == word)
+}
+
+/// Integer literal of a macro (`10`, `-0x1F`, `(1u << 0)` is not supported), and if
+/// it is unsigned.
+fn int(value: &[Tok]) -> Option<(i128, bool)> {
+    let value: &[Tok] = match value {
+        [Tok::Punct('('), inner @ .., Tok::Punct(')')] => inner,
+        value => value,
+    };
+    let (negative, lit): (bool, &str) = match value {
+        [Tok::Num(lit)] => (false, lit),
+        [Tok::Punct('-'), Tok::Num(lit)] => (true, lit),
+        [Tok::Punct('+'), Tok::Num(lit)] => (false, lit),
+        _ => return None,
+    };
+    let digits: &str = lit.trim_end_matches(['u', 'U', 'l', 'L']);
+    let unsigned: bool = lit[digits.len()..].contains(['u', 'U']);
+    let value: i128 = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        i128::from_str_radix(hex, 16).ok()?
+    } else if let Some(bin) = digits
+        .strip_prefix("0b")
+        .or_else(|| digits.strip_prefix("0B"))
+    {
+        i128::from_str_radix(bin, 2).ok()?
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        i128::from_str_radix(&digits[1..], 8).ok()?
+    } else {
+        digits.parse().ok()?
+    };
+    Some((if negative { -value } else { value }, unsigned))
+}
+
+fn id(token: &Token) -> Option<&str> {
+    match &token.tok {
+        Tok::Id(id) => Some(id),
+        _ => None,
+    }
+}
+
+/// Name of a function declaration or definition (the identifier before `(`).
+fn fn_name(tokens: &[Token]) -> &str {
+    tokens
+        .iter()
+        .position(|token| token.tok == Tok::Punct('('))
+        .and_then(|open| open.checked_sub(1))
+        .and_then(|i| id(&tokens[i]))
+        .unwrap_or("")
+}
+
+/// `(*name)`
+fn fn_ptr_name(tokens: &[Token]) -> Option<&str> {
+    tokens.windows(3).find_map(|window| match window {
+        [Token {
+            tok: Tok::Punct('('),
+            ..
+        }, Token {
+            tok: Tok::Punct('*'),
+            ..
+        }, name] => id(name),
+        _ => None,
+    })
+}
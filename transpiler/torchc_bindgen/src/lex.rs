@@ -0,0 +1,161 @@
+/// Token of a C header.
+#[derive(Debug, PartialEq, Clone)]
+#[repr(u8)]
+pub enum Tok {
+    /// `name`
+    Id(String),
+    /// `123`, `0x1Fu`, `1.5f`
+    Num(String),
+    /// `"..."`, `'.'`
+    Lit(String),
+    /// `...`
+    Ellipsis,
+    /// `(`, `*`, `;`, ...
+    Punct(char),
+}
+
+/// Token and its line (`1`-based).
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub tok: Tok,
+    pub line: usize,
+}
+
+/// Object-like macro (`#define NAME value`).
+#[derive(Debug)]
+pub struct Define {
+    pub name: String,
+    /// `None` for the function-like macros (`#define NAME(...) ...`).
+    pub value: Option<Vec<Tok>>,
+    pub line: usize,
+}
+
+/// It splits the header into tokens and macros, the other preprocessor directives and
+/// the comments are ignored.
+pub fn lex(contents: &str) -> (Vec<Token>, Vec<Define>) {
+    let mut tokens: Vec<Token> = vec![];
+    let mut defines: Vec<Define> = vec![];
+    let chars: Vec<char> = contents.chars().collect();
+    let mut i: usize = 0;
+    let mut line: usize = 1;
+    // Only whitespace since the start of the line?
+    let mut line_start: bool = true;
+
+    while i < chars.len() {
+        let c: char = chars[i];
+        match c {
+            '\n' => {
+                line += 1;
+                line_start = true;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            // `// ...`
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            // `/* ... */`
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 2;
+            }
+            // `#directive ...`, with the `\` line continuations.
+            '#' if line_start => {
+                let directive_line: usize = line;
+                let mut directive: String = String::new();
+                i += 1;
+                while i < chars.len() && chars[i] != '\n' {
+                    if chars[i] == '\\' && chars.get(i + 1) == Some(&'\n') {
+                        line += 1;
+                        i += 2;
+                        continue;
+                    }
+                    directive.push(chars[i]);
+                    i += 1;
+                }
+                if let Some(define) = define(&directive, directive_line) {
+                    defines.push(define);
+                }
+            }
+            _ => {
+                line_start = false;
+                let (tok, len): (Tok, usize) = tok(&chars[i..]);
+                tokens.push(Token { tok, line });
+                i += len;
+            }
+        }
+    }
+    (tokens, defines)
+}
+
+/// `define NAME value`
+fn define(directive: &str, line: usize) -> Option<Define> {
+    let directive: &str = directive.trim_start();
+    let rest: &str = directive.strip_prefix("define")?;
+    if !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let rest: &str = rest.trim_start();
+    let name_len: usize = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let name: String = rest[..name_len].to_string();
+    let rest: &str = &rest[name_len..];
+
+    // The function-like macros have the `(` right after the name.
+    if rest.starts_with('(') {
+        return Some(Define {
+            name,
+            value: None,
+            line,
+        });
+    }
+    let (tokens, _) = lex(rest);
+    Some(Define {
+        name,
+        value: Some(tokens.into_iter().map(|token| token.tok).collect()),
+        line,
+    })
+}
+
+/// Token at the start of the characters and its length.
+fn tok(chars: &[char]) -> (Tok, usize) {
+    let c: char = chars[0];
+    if c.is_ascii_alphabetic() || c == '_' {
+        let len: usize = chars
+            .iter()
+            .position(|c| !(c.is_ascii_alphanumeric() || *c == '_'))
+            .unwrap_or(chars.len());
+        return (Tok::Id(chars[..len].iter().collect()), len);
+    }
+    if c.is_ascii_digit() || c == '.' && chars.get(1).is_some_and(char::is_ascii_digit) {
+        let len: usize = chars
+            .iter()
+            .position(|c| !(c.is_ascii_alphanumeric() || *c == '.'))
+            .unwrap_or(chars.len());
+        return (Tok::Num(chars[..len].iter().collect()), len);
+    }
+    if c == '"' || c == '\'' {
+        let mut len: usize = 1;
+        while len < chars.len() && chars[len] != c && chars[len] != '\n' {
+            if chars[len] == '\\' {
+                len += 1;
+            }
+            len += 1;
+        }
+        let len: usize = (len + 1).min(chars.len());
+        return (Tok::Lit(chars[..len].iter().collect()), len);
+    }
+    if chars.starts_with(&['.', '.', '.']) {
+        return (Tok::Ellipsis, 3);
+    }
+    (Tok::Punct(c), 1)
+}
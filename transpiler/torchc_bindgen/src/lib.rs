@@ -0,0 +1,72 @@
+use torchc_lits::lits;
+
+mod decl;
+mod lex;
+
+/// Torch module generated from a C header.
+#[derive(Debug)]
+pub struct Bindings {
+    /// Source of the module.
+    pub torch: String,
+    /// Number of the bound declarations.
+    pub bound: usize,
+    /// Declarations without bindings.
+    pub skipped: Vec<Skipped>,
+}
+
+/// Declaration of the header without bindings.
+#[derive(Debug)]
+pub struct Skipped {
+    /// Line of the declaration in the header (`1`-based).
+    pub line: usize,
+    /// Kind and name of the declaration (`function 'printf'`).
+    pub decl: String,
+    /// Why it has no bindings.
+    pub reason: String,
+}
+
+/// It parses the simple C declarations of the header and generates their
+/// `extern "c"` bindings.
+///
+/// ---
+/// - The functions with primitive types and `const char *` (`str`).
+/// - The integer constants of `#define` and the enumerators.
+/// - The `typedef` of primitive types, resolved in the bindings.
+///
+/// > _The other declarations are skipped and reported, `header` is the path of the
+/// > `include` relative to the module._
+pub fn bindgen(header: &str, contents: &str) -> Bindings {
+    let (tokens, defines) = lex::lex(contents);
+
+    let mut gen: decl::Gen = decl::Gen::default();
+    for define in &defines {
+        gen.define(define);
+    }
+    for decl in decl::split(&tokens) {
+        gen.decl(&decl);
+    }
+
+    let mut torch: String = format!(
+        "// Bindings of `{}` generated by `torch {}`, do not edit.\n\n{} \"{}\"\n",
+        header,
+        lits::cli::BINDGEN,
+        lits::token_table::INCLUDE,
+        header
+    );
+    for section in [&gen.aliases, &gen.consts, &gen.fns] {
+        if !section.is_empty() {
+            torch.push('\n');
+            for line in section {
+                torch.push_str(line);
+                torch.push('\n');
+            }
+        }
+    }
+
+    gen.skipped.sort_by_key(|skipped| skipped.line);
+    Bindings {
+        torch,
+        bound: gen.consts.len() + gen.fns.len(),
+        skipped: gen.skipped,
+    }
+}
@@ -0,0 +1,164 @@
+use super::{c_type, push_id, push_lit, Fn, Type};
+use torchc_lits::lits;
+
+impl Fn {
+    /// Generate the C wrapper of the exported function.
+    ///
+    /// ---
+    /// > _It has C linkage in `torch::exports`, the symbol is the unmangled name
+    /// > while the function of the language keeps its C++ name._<br>
+    /// > _The returned strings are copies allocated with `malloc`._
+    pub fn cgen_export(&self, cpp: &mut String, module: &str) {
+        let ret: bool = self.ret.as_ref().is_some_and(Type::is_str);
+
+        // `extern "C" type name(type arg1, type arg2, ...) {`
+        cpp.push_str(&format!(
+            "namespace {}::{} {{\nextern \"C\" ",
+            lits::cgen::NAMESPACE,
+            lits::cgen::EXPORTS_NAMESPACE
+        ));
+        if ret {
+            cpp.push_str("char *");
+        } else {
+            c_type(cpp, self.ret.as_ref());
+            cpp.push(' ');
+        }
+        push_lit(cpp, &self.name);
+        cpp.push('(');
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                cpp.push_str(", ");
+            }
+            c_type(cpp, Some(&param.ty));
+            if !cpp.ends_with('*') {
+                cpp.push(' ');
+            }
+            push_id(cpp, &param.name);
+        }
+
+        // `return ::_T4main4name(arg1, arg2, ...);`
+        cpp.push_str(") {\n    return ::");
+        cpp.push_str(&self.symbol(module));
+        cpp.push('(');
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                cpp.push_str(", ");
+            }
+            if param.ty.is_str() {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::str::from_c(");
+                push_id(cpp, &param.name);
+                cpp.push(')');
+            } else {
+                push_id(cpp, &param.name);
+            }
+        }
+        cpp.push(')');
+        if ret {
+            cpp.push_str(".to_c()");
+        }
+        cpp.push_str(";\n}\n}\n");
+    }
+
+    /// Generate the C prototype of the exported function for the header.
+    fn cgen_prototype(&self, h: &mut String) {
+        match &self.ret {
+            Some(ret) if ret.is_str() => h.push_str("char *"),
+            Some(ret) => {
+                h_type(h, ret);
+                h.push(' ');
+            }
+            None => h.push_str("void "),
+        }
+        push_lit(h, &self.name);
+        h.push('(');
+        if self.params.is_empty() {
+            h.push_str("void");
+        }
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                h.push_str(", ");
+            }
+            h_type(h, &param.ty);
+            if !h.ends_with('*') {
+                h.push(' ');
+            }
+            push_id(h, &param.name);
+        }
+        h.push_str(");\n");
+    }
+}
+
+/// Generate the C header with the exported functions of the script.
+///
+/// ---
+/// > _The exported values are primitives and strings, the language has no structs
+/// > to describe yet._
+pub fn header(script: &str, stem: &str, fns: &[&Fn]) -> String {
+    // `TORCH_MAIN_H`
+    let guard: String = format!(
+        "{}_{}_H",
+        lits::cgen::NAMESPACE.to_ascii_uppercase(),
+        stem.chars()
+            .map(|c| if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            })
+            .collect::<String>()
+    );
+
+    let mut h: String = format!(
+        "// Exported functions of `{}`, generated by torch, do not edit.\n\
+         #ifndef {}\n#define {}\n\n\
+         #include <stdbool.h>\n#include <stdint.h>\n\n\
+         #ifdef __cplusplus\nextern \"C\" {{\n#endif\n\n",
+        script, guard, guard
+    );
+    if fns
+        .iter()
+        .any(|fn_stmt| fn_stmt.ret.as_ref().is_some_and(Type::is_str))
+    {
+        h.push_str(
+            "// The returned strings are allocated with `malloc`, release them with `free`.\n",
+        );
+    }
+    for fn_stmt in fns {
+        fn_stmt.cgen_prototype(&mut h);
+    }
+    h.push_str(&format!(
+        "\n#ifdef __cplusplus\n}}\n#endif\n\n#endif // {}\n",
+        guard
+    ));
+    h
+}
+
+/// C type of the header (`str` is `const char *`).
+fn h_type(h: &mut String, ty: &Type) {
+    if ty.is_str() {
+        return h.push_str("const char *");
+    }
+    let id: String = match ty {
+        Type::Id(id) => match id.lit() {
+            Some(lit) => format!("{}", lit),
+            None => String::from(lits::cgen::DEFAULT_ID),
+        },
+        _ => String::from(lits::cgen::DEFAULT_ID),
+    };
+    h.push_str(match id.as_str() {
+        lits::types::I8 => "int8_t",
+        lits::types::I16 => "int16_t",
+        lits::types::I32 => "int32_t",
+        lits::types::I64 => "int64_t",
+        lits::types::U8 => "uint8_t",
+        lits::types::U16 => "uint16_t",
+        lits::types::U32 => "uint32_t",
+        lits::types::U64 => "uint64_t",
+        lits::types::F32 => "float",
+        lits::types::F64 => "double",
+        lits::types::BOOL => "bool",
+        // `char32_t` of `<uchar.h>`.
+        lits::types::CHAR => "uint_least32_t",
+        id => id,
+    });
+}
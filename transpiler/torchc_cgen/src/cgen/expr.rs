@@ -0,0 +1,301 @@
+use super::{indent, mangle, push_id, push_lit, Ctx};
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+
+/// Language expressions.
+#[derive(Debug, Clone)]
+#[repr(u8)]
+pub enum Expr {
+    /// `123`, `1.5`, `"..."`, `'.'`, `true`, `false`
+    Lit(Token),
+    /// `name`
+    Id(Token),
+    /// `[expr, expr, ...]`
+    Array(Vec<Self>),
+    /// `-expr`, `!expr`
+    Unary(Token, Box<Self>),
+    /// `expr + expr`, `expr == expr`, ...
+    Binary(Box<Self>, Token, Box<Self>),
+    /// `expr..expr`
+    Range(Box<Self>, Box<Self>),
+    /// `"...{expr}..."`
+    ///
+    /// The pieces are string literals and the interpolated expressions.
+    Format(Vec<Self>),
+    /// `expr(expr, expr, ...)`
+    Call(Box<Self>, Vec<Self>),
+    /// `expr.name`
+    Member(Box<Self>, Token),
+    /// `expr[expr]`
+    ///
+    /// The position is the one of `[`, it is used by the bounds checks.
+    Index(Box<Self>, Box<Self>, Pos),
+    /// `expr[expr..expr]`, `expr[..expr]`, `expr[expr..]`
+    ///
+    /// The position is the one of `[`, it is used by the bounds checks.
+    Slice(Box<Self>, Option<Box<Self>>, Option<Box<Self>>, Pos),
+    /// `expr?`
+    ///
+    /// The position is the one of `?`, it identifies the propagation.
+    Try(Box<Self>, Pos),
+}
+impl Expr {
+    /// Position of the first token of the expression (`None` for `[]`).
+    pub fn pos(&self) -> Option<&Pos> {
+        match self {
+            Expr::Lit(token) | Expr::Id(token) => Some(&token.pos),
+            Expr::Array(items) | Expr::Format(items) => items.first().and_then(Self::pos),
+            Expr::Unary(op, _) => Some(&op.pos),
+            Expr::Binary(expr, ..)
+            | Expr::Range(expr, _)
+            | Expr::Call(expr, _)
+            | Expr::Member(expr, _)
+            | Expr::Index(expr, ..)
+            | Expr::Slice(expr, ..)
+            | Expr::Try(expr, _) => expr.pos(),
+        }
+    }
+
+    /// Names of a path (`mathx.ops.add`), `None` if it is not one.
+    pub fn path(&self) -> Option<Vec<String>> {
+        match self {
+            Expr::Id(id) => Some(vec![format!("{}", id.lit()?)]),
+            Expr::Member(expr, name) => {
+                let mut path: Vec<String> = expr.path()?;
+                path.push(format!("{}", name.lit()?));
+                Some(path)
+            }
+            _ => None,
+        }
+    }
+
+    /// Generate C/C++ expression code.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx) {
+        match self {
+            Expr::Lit(lit) => match &lit.lexeme {
+                Table::True => cpp.push_str("true"),
+                Table::False => cpp.push_str("false"),
+                // `'x'` -> `U'x'`
+                Table::CharLit(_) => {
+                    cpp.push('U');
+                    push_lit(cpp, lit);
+                }
+                // `"..."` -> `torch::str("...")`
+                Table::StringLit(_) => {
+                    cpp.push_str(lits::cgen::NAMESPACE);
+                    cpp.push_str("::str(");
+                    push_lit(cpp, lit);
+                    cpp.push(')');
+                }
+                _ => push_lit(cpp, lit),
+            },
+            Expr::Id(id) => {
+                let name: String = match id.lit() {
+                    Some(lit) => format!("{}", lit),
+                    None => String::from(lits::cgen::DEFAULT_ID),
+                };
+                // Built-ins live in the runtime namespace.
+                if is_builtin(id) {
+                    cpp.push_str(lits::cgen::NAMESPACE);
+                    cpp.push_str("::");
+                    cpp.push_str(&name);
+                } else if ctx.externs.contains(&name) {
+                    cpp.push_str(&format!(
+                        "{}::{}::{}",
+                        lits::cgen::NAMESPACE,
+                        lits::cgen::C_NAMESPACE,
+                        name
+                    ));
+                } else if ctx.natives.contains(&name) {
+                    cpp.push_str(&name);
+                } else if ctx.fns.contains(&name) {
+                    cpp.push_str(&mangle(ctx.module, &name));
+                } else {
+                    push_id(cpp, id);
+                }
+            }
+            // `torch::array{expr, expr, ...}`
+            Expr::Array(items) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::array");
+                Self::cgen_items(items, cpp, ctx);
+            }
+            Expr::Unary(op, expr) => {
+                cpp.push('(');
+                push_lit(cpp, op);
+                expr.cgen(cpp, ctx);
+                cpp.push(')');
+            }
+            Expr::Binary(lhs, op, rhs) => {
+                cpp.push('(');
+                lhs.cgen(cpp, ctx);
+                cpp.push(' ');
+                push_lit(cpp, op);
+                cpp.push(' ');
+                rhs.cgen(cpp, ctx);
+                cpp.push(')');
+            }
+            // `torch::range{expr, expr}`
+            Expr::Range(lo, hi) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::range{");
+                lo.cgen(cpp, ctx);
+                cpp.push_str(", ");
+                hi.cgen(cpp, ctx);
+                cpp.push('}');
+            }
+            // `torch::format(expr, expr, ...)`
+            Expr::Format(pieces) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::format(");
+                for (i, piece) in pieces.iter().enumerate() {
+                    if i > 0 {
+                        cpp.push_str(", ");
+                    }
+                    piece.cgen(cpp, ctx);
+                }
+                cpp.push(')');
+            }
+            Expr::Call(callee, args) => {
+                callee.cgen(cpp, ctx);
+                cpp.push('(');
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        cpp.push_str(", ");
+                    }
+                    arg.cgen(cpp, ctx);
+                }
+                cpp.push(')');
+            }
+            Expr::Member(expr, name) => match ctx.import(self) {
+                // `mathx.ops.add` -> `_T5mathx3ops3add`
+                Some(symbol) => cpp.push_str(&symbol),
+                None => {
+                    expr.cgen(cpp, ctx);
+                    cpp.push('.');
+                    push_lit(cpp, name);
+                }
+            },
+            // `torch::at(expr, expr, torch::pos{...})`
+            Expr::Index(expr, i, pos) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::at(");
+                expr.cgen(cpp, ctx);
+                cpp.push_str(", ");
+                i.cgen(cpp, ctx);
+                cpp.push_str(", ");
+                ctx.cgen_pos(cpp, pos);
+                cpp.push(')');
+            }
+            // `torch_try_line_grapheme.value`
+            Expr::Try(_, pos) => {
+                cpp.push_str(&try_tmp(pos));
+                cpp.push_str(".value");
+            }
+            // `torch::subslice(expr, expr, expr, torch::pos{...})`
+            Expr::Slice(expr, lo, hi, pos) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::subslice(");
+                expr.cgen(cpp, ctx);
+                cpp.push_str(", ");
+                match lo {
+                    Some(lo) => lo.cgen(cpp, ctx),
+                    None => cpp.push('0'),
+                }
+                cpp.push_str(", ");
+                match hi {
+                    Some(hi) => hi.cgen(cpp, ctx),
+                    None => {
+                        cpp.push_str(lits::cgen::NAMESPACE);
+                        cpp.push_str("::len(");
+                        expr.cgen(cpp, ctx);
+                        cpp.push(')');
+                    }
+                }
+                cpp.push_str(", ");
+                ctx.cgen_pos(cpp, pos);
+                cpp.push(')');
+            }
+        }
+    }
+
+    /// Generate the propagations (`expr?`) of the expression before its statement.
+    ///
+    /// ---
+    /// `auto torch_try_line_grapheme = expr;`<br>
+    /// `if (!torch_try_line_grapheme) return torch::propagate(...);`
+    ///
+    /// > _Without exceptions, the propagations are evaluated in order before the statement
+    /// > that contains them and the expressions refer to their values._
+    pub fn cgen_tries(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        match self {
+            Expr::Lit(_) | Expr::Id(_) => {}
+            Expr::Array(items) | Expr::Format(items) => {
+                for item in items {
+                    item.cgen_tries(cpp, ctx, depth);
+                }
+            }
+            Expr::Unary(_, expr) | Expr::Member(expr, _) => expr.cgen_tries(cpp, ctx, depth),
+            Expr::Binary(lhs, _, rhs) | Expr::Range(lhs, rhs) | Expr::Index(lhs, rhs, _) => {
+                lhs.cgen_tries(cpp, ctx, depth);
+                rhs.cgen_tries(cpp, ctx, depth);
+            }
+            Expr::Call(callee, args) => {
+                callee.cgen_tries(cpp, ctx, depth);
+                for arg in args {
+                    arg.cgen_tries(cpp, ctx, depth);
+                }
+            }
+            Expr::Slice(expr, lo, hi, _) => {
+                expr.cgen_tries(cpp, ctx, depth);
+                for bound in [lo, hi].into_iter().flatten() {
+                    bound.cgen_tries(cpp, ctx, depth);
+                }
+            }
+            Expr::Try(expr, pos) => {
+                expr.cgen_tries(cpp, ctx, depth);
+
+                // Each line is the one of `?`, as the statement that follows.
+                let tmp: String = try_tmp(pos);
+                ctx.cgen_line(cpp, pos);
+                indent(cpp, depth);
+                cpp.push_str(&format!("auto {} = ", tmp));
+                expr.cgen(cpp, ctx);
+                cpp.push_str(";\n");
+                ctx.cgen_line(cpp, pos);
+                indent(cpp, depth);
+                cpp.push_str(&format!(
+                    "if (!{}) return {}::propagate(std::move({}));\n",
+                    tmp,
+                    lits::cgen::NAMESPACE,
+                    tmp
+                ));
+                ctx.cgen_line(cpp, pos);
+            }
+        }
+    }
+
+    /// Generate the braced list of items (`{expr, expr, ...}`).
+    pub fn cgen_items(items: &[Self], cpp: &mut String, ctx: &Ctx) {
+        cpp.push('{');
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                cpp.push_str(", ");
+            }
+            item.cgen(cpp, ctx);
+        }
+        cpp.push('}');
+    }
+}
+
+/// Is the identifier a built-in (`len`, `ok`, `none`, ...)?
+fn is_builtin(id: &Token) -> bool {
+    match id.lit() {
+        Some(lit) => lits::builtins::ALL.contains(&format!("{}", lit).as_str()),
+        None => false,
+    }
+}
+/// Temporary of the propagation (`torch_try_line_grapheme`).
+pub(crate) fn try_tmp(pos: &Pos) -> String {
+    format!("{}{}_{}", lits::cgen::TRY_PREFIX, pos.line, pos.grapheme)
+}
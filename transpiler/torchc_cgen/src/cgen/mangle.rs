@@ -0,0 +1,323 @@
+use torchc_lits::lits;
+
+/// Reserved words of C++ and C.
+const KEYWORDS: [&str; 102] = [
+    // C++
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "atomic_cancel",
+    "atomic_commit",
+    "atomic_noexcept",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char8_t",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "concept",
+    "const",
+    "consteval",
+    "constexpr",
+    "constinit",
+    "const_cast",
+    "continue",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "final",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "import",
+    "inline",
+    "int",
+    "long",
+    "module",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "override",
+    "private",
+    "protected",
+    "public",
+    "reflexpr",
+    "register",
+    "reinterpret_cast",
+    "requires",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "synchronized",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+    // C
+    "restrict",
+];
+/// Names of the standard library that are macros or that the generated code uses (`std`).
+const LIBRARY: [&str; 6] = ["std", "assert", "errno", "stdin", "stdout", "stderr"];
+
+/// Symbol of a function of the module (`half` of `net.http` -> `_T3net4http4half`).
+///
+/// ---
+/// > _Each component is its length in bytes and its name, as the C++ mangling of the
+/// > namespaces, so the module paths and the reserved words never clash._
+pub fn mangle(module: &str, name: &str) -> String {
+    let mut symbol: String = String::from(lits::cgen::MANGLE_PREFIX);
+    for component in module.split(lits::DOT).chain([name]) {
+        symbol.push_str(&format!("{}{}", component.len(), component));
+    }
+    symbol
+}
+
+/// Name of a variable, constant or parameter; the reserved ones have the mangling of a
+/// single component (`class` -> `_T5class`).
+///
+/// ---
+/// > _The runtime names (`torch...`) and the names reserved to the compilers (`__name`,
+/// > `_Name`, so the symbols) are reserved too._
+pub fn escape(name: &str) -> String {
+    let reserved: bool = KEYWORDS.contains(&name)
+        || LIBRARY.contains(&name)
+        || name.starts_with("__")
+        || name
+            .strip_prefix('_')
+            .is_some_and(|name| name.starts_with(|c: char| c.is_ascii_uppercase()))
+        || name
+            .get(..lits::cgen::NAMESPACE.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(lits::cgen::NAMESPACE));
+    if reserved {
+        format!("{}{}{}", lits::cgen::MANGLE_PREFIX, name.len(), name)
+    } else {
+        String::from(name)
+    }
+}
+
+/// Is it a reserved word of C++ or C (`class`, `new`, ...)?
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// Name of the symbol (`_T3net4http4half` -> `net.http.half`), `None` if it is not
+/// a symbol of the language.
+///
+/// ---
+/// > _The components of the module are unescaped (`web_2dkit` -> `web-kit`), the name
+/// > is an identifier of the script._
+pub fn demangle(symbol: &str) -> Option<String> {
+    match components(symbol) {
+        Some((name, len)) if len == symbol.len() => Some(name),
+        _ => None,
+    }
+}
+
+/// Demangle the symbols of a text (a stack trace, ...), also inside the C++ symbols
+/// (`_Z12_T4main4halfi` -> `main.half`).
+pub fn demangle_text(text: &str) -> String {
+    let mut demangled: String = String::with_capacity(text.len());
+    let mut i: usize = 0;
+    while i < text.len() {
+        let rest: &str = &text[i..];
+        let boundary: bool = !text[..i].ends_with(is_id);
+        if boundary {
+            if let Some((name, len)) = components(rest).or_else(|| cpp_symbol(rest)) {
+                demangled.push_str(&name);
+                i += len;
+                continue;
+            }
+        }
+        let c: char = rest.chars().next().unwrap_or_default();
+        demangled.push(c);
+        i += c.len_utf8();
+    }
+    demangled
+}
+
+/// Components of the mangled name at the start of the text, with its length.
+fn components(text: &str) -> Option<(String, usize)> {
+    let mut i: usize = text
+        .strip_prefix(lits::cgen::MANGLE_PREFIX)
+        .map(|_| lits::cgen::MANGLE_PREFIX.len())?;
+    let mut names: Vec<String> = vec![];
+    while let Some((len, digits)) = number(&text[i..]) {
+        let Some(name) = text.get(i + digits..i + digits + len) else {
+            break;
+        };
+        names.push(String::from(name));
+        i += digits + len;
+    }
+    let len: usize = names.len();
+    for name in names.iter_mut().take(len.saturating_sub(1)) {
+        *name = unescape(name);
+    }
+    match names.is_empty() {
+        true => None,
+        false => Some((names.join(lits::DOT), i)),
+    }
+}
+/// Component of a module as it was in its path (`web_2dkit` -> `web-kit`), the escaping
+/// of `module_name` undone; the text is kept if it is not an escaped component.
+fn unescape(component: &str) -> String {
+    let mut bytes: Vec<u8> = vec![];
+    let mut rest: &[u8] = component.as_bytes();
+    while let Some((&byte, next)) = rest.split_first() {
+        if byte != b'_' {
+            bytes.push(byte);
+            rest = next;
+            continue;
+        }
+        // `__` or `_xx`
+        match next {
+            [b'_', next @ ..] => {
+                bytes.push(b'_');
+                rest = next;
+            }
+            [high, low, next @ ..] if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                let hex: [u8; 2] = [*high, *low];
+                let hex: &str = std::str::from_utf8(&hex).unwrap_or_default();
+                bytes.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                rest = next;
+            }
+            _ => return String::from(component),
+        }
+    }
+    String::from_utf8(bytes).unwrap_or_else(|_| String::from(component))
+}
+/// C++ symbol of a function of the language (`_Z12_T4main4halfi`), its parameters are
+/// omitted.
+fn cpp_symbol(text: &str) -> Option<(String, usize)> {
+    let rest: &str = text.strip_prefix("_Z")?;
+    let (len, digits) = number(rest)?;
+    let (name, mangled) = components(rest.get(digits..digits + len)?)?;
+    if mangled != len {
+        return None;
+    }
+    // `i`, `N5torch3strE`, ...
+    let params: usize = rest[digits + len..]
+        .find(|c: char| !is_id(c))
+        .unwrap_or(rest.len() - digits - len);
+    Some((name, 2 + digits + len + params))
+}
+/// Decimal number at the start of the text, with its number of digits.
+fn number(text: &str) -> Option<(usize, usize)> {
+    let digits: usize = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    // `0` is not the length of a name.
+    match text[..digits].parse::<usize>() {
+        Ok(len) if len > 0 => Some((len, digits)),
+        _ => None,
+    }
+}
+/// Character of the C/C++ identifiers.
+fn is_id(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for (module, name) in [("main", "half"), ("net.http", "get"), ("main", "new")] {
+            let symbol: String = mangle(module, name);
+            assert_eq!(demangle(&symbol), Some(format!("{}.{}", module, name)));
+        }
+        for name in ["class", "new", "std", "__x", "_X", "torch_len"] {
+            let escaped: String = escape(name);
+            assert_ne!(escaped, name);
+            assert_eq!(demangle(&escaped).as_deref(), Some(name));
+        }
+        assert_eq!(escape("half"), "half");
+        assert_eq!(demangle("half"), None);
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            demangle_text("'_T5class' was not declared in '_T4main3new'"),
+            "'class' was not declared in 'main.new'"
+        );
+        assert_eq!(
+            demangle_text("in _Z11_T4main3newN5torch3strE (main.cpp:12)"),
+            "in main.new (main.cpp:12)"
+        );
+        // Not at the start of an identifier.
+        assert_eq!(demangle_text("x_T5class"), "x_T5class");
+    }
+
+    #[test]
+    fn escaped_modules() {
+        // The modules of `module_name`, the name keeps its underscores.
+        for (module, script) in [
+            ("web_2dkit.net.http", "web-kit.net.http"),
+            ("a__b", "a_b"),
+            ("_c3_a9", "é"),
+        ] {
+            assert_eq!(
+                demangle(&mangle(module, "torch_len")),
+                Some(format!("{}.torch_len", script))
+            );
+        }
+        assert_eq!(
+            demangle_text("undefined reference to `_T9web_2dkit4main3get'"),
+            "undefined reference to `web-kit.main.get'"
+        );
+        // Not an escaped component.
+        assert_eq!(demangle(&mangle("a_b", "f")).as_deref(), Some("a_b.f"));
+    }
+}
@@ -0,0 +1,990 @@
+pub use export::header;
+pub(crate) use expr::try_tmp;
+pub use expr::Expr;
+pub use mangle::{demangle, demangle_text, escape, is_keyword, mangle};
+use pathdiff::diff_paths;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use torchc_diagnosis::panic::Diagnosis;
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+pub use ty::Type;
+
+mod export;
+mod expr;
+mod mangle;
+mod ty;
+
+/// Transpilation mode.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Mode {
+    /// Ready for production.
+    Release,
+    /// Debugging and/or testing.
+    Dev,
+}
+
+/// Language of the generated code.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Backend {
+    /// C11, for the targets that only have a C compiler.
+    C,
+    /// C++20.
+    Cpp,
+}
+impl Backend {
+    /// Backend of the name of `--backend` and of the manifest (`c`, `cpp`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            lits::backends::C => Some(Self::C),
+            lits::backends::CPP => Some(Self::Cpp),
+            _ => None,
+        }
+    }
+    /// Name of the backend (`c`, `cpp`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::C => lits::backends::C,
+            Self::Cpp => lits::backends::CPP,
+        }
+    }
+}
+
+/// Settings of the compilation of a mode, the `[profile.dev]` and `[profile.release]`
+/// of the manifest override the defaults.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub mode: Mode,
+    /// Optimization level of the compiler (`0`, `1`, `2`, `3`, `s`, `z`).
+    pub opt_level: String,
+    /// Debug information for the debuggers.
+    pub debug: bool,
+    /// Trap on the overflow of the signed integers.
+    pub overflow_checks: bool,
+    /// Bounds checks of the indexes and slices of the runtime.
+    pub bounds_checks: bool,
+    /// `assert` of the C/C++ code (`NDEBUG` without them).
+    pub assertions: bool,
+    /// Sanitizers of the compiler (`address`, `undefined`, ...).
+    pub sanitizers: Vec<String>,
+}
+impl Profile {
+    /// Default settings of the mode, everything checked in `Dev` and optimized in
+    /// `Release`.
+    pub fn new(mode: Mode) -> Self {
+        let dev: bool = mode == Mode::Dev;
+        Self {
+            mode,
+            opt_level: String::from(if dev { "0" } else { "3" }),
+            debug: dev,
+            overflow_checks: dev,
+            bounds_checks: dev,
+            assertions: dev,
+            sanitizers: vec![],
+        }
+    }
+}
+
+/// Modules of the dependencies that a script can name, by their path in the language
+/// (`mathx.ops`) with their module name (`mathx.ops`, `web__kit.net`).
+pub type Imports = BTreeMap<String, String>;
+
+/// Code generation context of a script.
+#[derive(Debug)]
+pub struct Ctx<'ctx> {
+    pub profile: &'ctx Profile,
+    /// Script path as it is shown to the user (`src/file.t`).
+    pub script: &'ctx str,
+    /// `extern "c"` functions, they are called through the runtime.
+    pub externs: &'ctx HashSet<String>,
+    /// Names of the `extern` declarations, they are not escaped.
+    pub natives: &'ctx HashSet<String>,
+    /// Functions of the script, they are mangled.
+    pub fns: &'ctx HashSet<String>,
+    /// Module of the script (`net.http`).
+    pub module: &'ctx str,
+    /// Modules of the dependencies of its package.
+    pub imports: &'ctx Imports,
+    /// Modules of the dependencies named by the script, their interfaces are included.
+    pub imported: RefCell<BTreeSet<String>>,
+    /// Variables, constants and parameters in scope with their names in C/C++, the `cpp`
+    /// blocks name them.
+    pub scope: RefCell<Vec<(String, String)>>,
+    /// Diagnosis of the script, for the constructions that a backend cannot generate.
+    pub diagnosis: &'ctx Diagnosis<'ctx>,
+}
+impl Ctx<'_> {
+    /// Symbol of the function of a dependency named by its path
+    /// (`mathx.ops.add` -> `_T5mathx3ops3add`), `None` if it is not one.
+    pub fn import(&self, expr: &Expr) -> Option<String> {
+        let mut path: Vec<String> = expr.path()?;
+        let name: String = path.pop()?;
+        let module: &String = self.imports.get(&path.join(lits::DOT))?;
+        self.imported.borrow_mut().insert(module.clone());
+        Some(mangle(module, &name))
+    }
+    /// Generate the inclusions of the interfaces of the named modules
+    /// (`#include "mathx.ops_interface.h"`).
+    pub fn cgen_imports(&self, cpp: &mut String) {
+        let imported = self.imported.borrow();
+        for module in imported.iter() {
+            cpp.push_str(&format!(
+                "#include \"{}{}{}{}\"\n",
+                module,
+                lits::cgen::INTERFACE_SUFFIX,
+                lits::DOT,
+                lits::extensions::H
+            ));
+        }
+        if !imported.is_empty() {
+            cpp.push('\n');
+        }
+    }
+    /// Generate the `#line` of the script position, the compilers and debuggers report
+    /// the following line in the script (`#line line "src/file.t"`).
+    pub fn cgen_line(&self, cpp: &mut String, pos: &Pos) {
+        cpp.push_str(&format!("#line {} {}\n", pos.line, c_string(self.script)));
+    }
+    /// Generate the script position for the runtime (`torch::pos{"src/file.t", line, grapheme}`).
+    pub fn cgen_pos(&self, cpp: &mut String, pos: &Pos) {
+        cpp.push_str(&format!(
+            "{}::pos{{{}, {}, {}}}",
+            lits::cgen::NAMESPACE,
+            c_string(self.script),
+            pos.line,
+            pos.grapheme
+        ));
+    }
+}
+
+/// Language statements.
+#[derive(Debug, Clone)]
+#[repr(u8)]
+pub enum Stmt {
+    Global(Option<Vec<Self>>),
+    Include(Option<Include>),
+    Extern(Option<Extern>),
+    Fn(Option<Fn>),
+    Var(Option<Var>),
+    Const(Option<Const>),
+    Assign(Option<Assign>),
+    Expr(Option<Expr>),
+    For(Option<For>),
+    Return(Option<Return>),
+    /// `break`
+    Break(Option<Token>),
+    /// `continue`
+    Continue(Option<Token>),
+    Defer(Option<Defer>),
+    Cpp(Option<Cpp>),
+}
+impl Stmt {
+    /// Position of the statement in the script (the functions have their own `#line`).
+    pub fn pos(&self) -> Option<&Pos> {
+        match self {
+            Stmt::Var(Some(var)) => Some(&var.name.pos),
+            Stmt::Const(Some(const_stmt)) => Some(&const_stmt.name.pos),
+            Stmt::Assign(Some(assign)) => assign.target.pos(),
+            Stmt::Expr(Some(expr)) => expr.pos(),
+            Stmt::For(Some(for_stmt)) => Some(&for_stmt.name.pos),
+            Stmt::Return(Some(return_stmt)) => Some(&return_stmt.pos),
+            Stmt::Break(Some(token)) | Stmt::Continue(Some(token)) => Some(&token.pos),
+            Stmt::Defer(Some(defer)) => Some(&defer.pos),
+            _ => None,
+        }
+    }
+    /// Generate C/C++ code of the statements of a body.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        if let Some(pos) = self.pos() {
+            ctx.cgen_line(cpp, pos);
+        }
+        match self {
+            Stmt::Fn(Some(fn_stmt)) => fn_stmt.cgen(cpp, ctx),
+            Stmt::Var(Some(var)) => var.cgen(cpp, ctx, depth),
+            Stmt::Const(Some(const_stmt)) => const_stmt.cgen(cpp, ctx, depth),
+            Stmt::Assign(Some(assign)) => assign.cgen(cpp, ctx, depth),
+            Stmt::Expr(Some(expr)) => {
+                expr.cgen_tries(cpp, ctx, depth);
+                // `expr?` alone is only its propagation.
+                if let Expr::Try(..) = expr {
+                    return;
+                }
+                indent(cpp, depth);
+                expr.cgen(cpp, ctx);
+                cpp.push_str(";\n");
+            }
+            Stmt::For(Some(for_stmt)) => for_stmt.cgen(cpp, ctx, depth),
+            Stmt::Return(Some(return_stmt)) => return_stmt.cgen(cpp, ctx, depth),
+            Stmt::Break(Some(_)) => {
+                indent(cpp, depth);
+                cpp.push_str("break;\n");
+            }
+            Stmt::Continue(Some(_)) => {
+                indent(cpp, depth);
+                cpp.push_str("continue;\n");
+            }
+            Stmt::Defer(Some(defer)) => defer.cgen(cpp, ctx, depth),
+            Stmt::Cpp(Some(cpp_stmt)) => cpp_stmt.cgen(cpp, ctx, depth),
+            _ => {}
+        }
+    }
+}
+
+/// Indentation of the generated code.
+pub(crate) fn indent(cpp: &mut String, depth: usize) {
+    cpp.push_str(&"    ".repeat(depth));
+}
+/// C string literal of the text (`"src/file.t"`) for the `#line` and `#include`
+/// directives.
+///
+/// ---
+/// > _The quotes and backslashes are escaped, the other non-printable characters are
+/// > in octal with their three digits so that no following digit extends them._
+pub fn c_string(text: &str) -> String {
+    let mut lit: String = String::with_capacity(text.len() + 2);
+    lit.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => lit.push_str("\\\""),
+            '\\' => lit.push_str("\\\\"),
+            ' '..='~' => lit.push(ch),
+            _ if ch.is_ascii() => lit.push_str(&format!("\\{:03o}", ch as u8)),
+            _ => lit.push(ch),
+        }
+    }
+    lit.push('"');
+    lit
+}
+/// Generate the body of a statement (`{...}`), its declarations go out of scope after it.
+fn body(stmts: &[Stmt], cpp: &mut String, ctx: &Ctx, depth: usize) {
+    let len: usize = ctx.scope.borrow().len();
+    cpp.push_str("{\n");
+    for stmt in stmts {
+        stmt.cgen(cpp, ctx, depth + 1);
+    }
+    indent(cpp, depth);
+    cpp.push_str("}\n");
+    ctx.scope.borrow_mut().truncate(len);
+}
+/// Add the name of a variable, constant or parameter to the scope.
+fn declare(ctx: &Ctx, name: &Token) {
+    if let Some(lit) = name.lit() {
+        let name: String = format!("{}", lit);
+        let escaped: String = escape(&name);
+        ctx.scope.borrow_mut().push((name, escaped));
+    }
+}
+/// Push the token literal.
+pub(crate) fn push_lit(cpp: &mut String, token: &Token) {
+    match token.lit() {
+        Some(lit) => cpp.push_str(&format!("{}", lit)),
+        None => cpp.push_str(lits::cgen::DEFAULT_ID),
+    }
+}
+/// Push the name of a variable, constant or parameter (see [`escape`]).
+pub(crate) fn push_id(cpp: &mut String, token: &Token) {
+    match token.lit() {
+        Some(lit) => cpp.push_str(&escape(&format!("{}", lit))),
+        None => cpp.push_str(lits::cgen::DEFAULT_ID),
+    }
+}
+
+/// **Statement:**
+///
+/// `include "file.h"`
+///
+/// ---
+/// > _The header is from `src/`, it is included with C linkage._
+#[derive(Debug, Clone)]
+pub struct Include {
+    pub path: Token,
+    /// Path of the header in the file system.
+    pub header: PathBuf,
+}
+impl Include {
+    /// Generate the C++ inclusion relative to the target folder.
+    pub fn cgen(&self, cpp: &mut String, target: &Path) {
+        cpp.push_str("extern \"C\" {\n");
+        self.cgen_c(cpp, target);
+        cpp.push_str("}\n");
+    }
+    /// Generate the C inclusion relative to the target folder (`#include "..."`).
+    pub fn cgen_c(&self, c: &mut String, target: &Path) {
+        let header: PathBuf = match diff_paths(&self.header, target) {
+            Some(header) => header,
+            None => self.header.clone(),
+        };
+        c.push_str("#include ");
+        c.push_str(&c_string(&header.to_string_lossy()));
+        c.push('\n');
+    }
+}
+
+/// Linkage of the `extern` function.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Abi {
+    /// `extern "c"`
+    C,
+    /// `extern "cpp"`
+    Cpp,
+}
+
+/// **Statement:**
+///
+/// `extern "c" fn name(arg1: type, arg2: type, ...) type`<br>
+/// `extern "c" const NAME: type`
+///
+/// ---
+/// > _The function is defined in the C/C++ files of `src/`, it has no body._
+#[derive(Debug, Clone)]
+pub struct Extern {
+    pub abi: Abi,
+    pub decl: ExternDecl,
+}
+/// Declarations of the `extern` statement.
+#[derive(Debug, Clone)]
+#[repr(u8)]
+pub enum ExternDecl {
+    /// `fn name(arg1: type, arg2: type, ...) type`
+    Fn(Fn),
+    /// `const NAME: type`
+    ///
+    /// ---
+    /// > _Macro or enumerator of a header, it has no value in the language._
+    Const(Param),
+}
+impl Extern {
+    /// Generate the C/C++ declaration.
+    ///
+    /// ---
+    /// > _With C linkage the strings are `const char *`, the calls go through a function
+    /// > of `torch::c` with the types of the language that converts them._<br>
+    /// > _The constants are not declared, the header already defines them (a macro
+    /// > would expand its own declaration)._
+    pub fn cgen(&self, cpp: &mut String) {
+        let sig: &Fn = match &self.decl {
+            ExternDecl::Fn(sig) => sig,
+            ExternDecl::Const(_) => return,
+        };
+        let name: String = match sig.name.lit() {
+            Some(lit) => format!("{}", lit),
+            None => String::from(lits::cgen::DEFAULT_ID),
+        };
+        if self.abi == Abi::Cpp {
+            sig.cgen_signature(cpp, &name);
+            cpp.push_str(";\n");
+            return;
+        }
+
+        // `extern "C" type name(type arg1, type arg2, ...);`
+        cpp.push_str("extern \"C\" ");
+        c_type(cpp, sig.ret.as_ref());
+        if !cpp.ends_with('*') {
+            cpp.push(' ');
+        }
+        push_lit(cpp, &sig.name);
+        cpp.push('(');
+        for (i, param) in sig.params.iter().enumerate() {
+            if i > 0 {
+                cpp.push_str(", ");
+            }
+            c_type(cpp, Some(&param.ty));
+            if !cpp.ends_with('*') {
+                cpp.push(' ');
+            }
+            push_id(cpp, &param.name);
+        }
+        cpp.push_str(");\n");
+
+        // `namespace torch::c { inline type name(torch::str arg1, ...) {...} }`
+        let ret: bool = sig.ret.as_ref().is_some_and(Type::is_str);
+        cpp.push_str(&format!(
+            "namespace {}::{} {{\ninline ",
+            lits::cgen::NAMESPACE,
+            lits::cgen::C_NAMESPACE
+        ));
+        sig.cgen_signature(cpp, &name);
+        cpp.push_str(" {\n    return ");
+        if ret {
+            cpp.push_str(lits::cgen::NAMESPACE);
+            cpp.push_str("::str::from_c(");
+        }
+        cpp.push_str("::");
+        push_lit(cpp, &sig.name);
+        cpp.push('(');
+        for (i, param) in sig.params.iter().enumerate() {
+            if i > 0 {
+                cpp.push_str(", ");
+            }
+            push_id(cpp, &param.name);
+            if param.ty.is_str() {
+                cpp.push_str(".c_str()");
+            }
+        }
+        cpp.push(')');
+        if ret {
+            cpp.push(')');
+        }
+        cpp.push_str(";\n}\n}\n");
+    }
+}
+/// C type of the `extern "c"` functions (`str` is `const char *`).
+fn c_type(cpp: &mut String, ty: Option<&Type>) {
+    match ty {
+        Some(ty) if ty.is_str() => cpp.push_str("const char *"),
+        Some(ty) => ty.cgen(cpp),
+        None => cpp.push_str("void"),
+    }
+}
+
+/// **Statement:**
+///
+/// `export const fn name(arg1: type, arg2: type, ...) type`<br>
+/// &nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;`...`
+#[derive(Debug, Clone)]
+pub struct Fn {
+    /// `export fn`, it is also declared with C linkage for the C/C++ files.
+    pub export: bool,
+    /// `const fn`, it can be evaluated at compile time.
+    pub constant: bool,
+    pub name: Token,
+    pub params: Vec<Param>,
+    pub ret: Option<Type>,
+    pub body: Vec<Stmt>,
+}
+impl Default for Fn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Fn {
+    pub fn new() -> Self {
+        Self {
+            export: false,
+            constant: false,
+            name: Token::new(),
+            params: vec![],
+            ret: None,
+            body: vec![],
+        }
+    }
+    /// Is it the entry point of the program?
+    pub fn is_main(&self) -> bool {
+        match self.name.lit() {
+            Some(lit) => format!("{}", lit) == lits::cgen::MAIN,
+            None => false,
+        }
+    }
+    /// Symbol of the function of the module, `main` is the entry point of C/C++.
+    pub fn symbol(&self, module: &str) -> String {
+        match self.name.lit() {
+            Some(_) if self.is_main() => String::from(lits::cgen::MAIN),
+            Some(lit) => mangle(module, &format!("{}", lit)),
+            None => String::from(lits::cgen::DEFAULT_ID),
+        }
+    }
+    /// Generate the C/C++ function signature (`type name(type arg1, type arg2, ...)`).
+    pub fn cgen_signature(&self, cpp_fn: &mut String, name: &str) {
+        // Return type.
+        if self.is_main() {
+            cpp_fn.push_str("int");
+        } else {
+            match &self.ret {
+                Some(ret) => ret.cgen(cpp_fn),
+                None => cpp_fn.push_str("void"),
+            }
+        }
+
+        // Function name.
+        cpp_fn.push(' ');
+        cpp_fn.push_str(name);
+
+        // Function parameters.
+        cpp_fn.push('(');
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                cpp_fn.push_str(", ");
+            }
+            param.ty.cgen(cpp_fn);
+            cpp_fn.push(' ');
+            push_id(cpp_fn, &param.name);
+        }
+        cpp_fn.push(')');
+    }
+    /// Generate C/C++ function code.
+    ///
+    /// ---
+    /// > _The function is between `#line` directives, the code after it is reported
+    /// > in the generated file again._
+    pub fn cgen(&self, cpp_fn: &mut String, ctx: &Ctx) {
+        ctx.cgen_line(cpp_fn, &self.name.pos);
+        self.cgen_signature(cpp_fn, &self.symbol(ctx.module));
+
+        // Function body.
+        cpp_fn.push(' ');
+        let len: usize = ctx.scope.borrow().len();
+        for param in &self.params {
+            declare(ctx, &param.name);
+        }
+        body(&self.body, cpp_fn, ctx, 0);
+        ctx.scope.borrow_mut().truncate(len);
+        cpp_fn.push_str(lits::cgen::LINE_RESTORE);
+        cpp_fn.push('\n');
+    }
+}
+/// Function parameter (`name: type`).
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: Token,
+    pub ty: Type,
+}
+
+/// **Statement:**
+///
+/// `var name: type = expr`
+#[derive(Debug, Clone)]
+pub struct Var {
+    pub name: Token,
+    pub ty: Option<Type>,
+    pub value: Option<Expr>,
+}
+impl Var {
+    /// Generate C/C++ variable code.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        if let Some(value) = &self.value {
+            value.cgen_tries(cpp, ctx, depth);
+        }
+        indent(cpp, depth);
+        match &self.ty {
+            Some(ty) => ty.cgen(cpp),
+            None => cpp.push_str("auto"),
+        }
+        cpp.push(' ');
+        push_id(cpp, &self.name);
+        match (&self.ty, &self.value) {
+            // The items of the literal are converted to the declared type.
+            (Some(_), Some(Expr::Array(items))) => Expr::cgen_items(items, cpp, ctx),
+            (_, Some(value)) => {
+                cpp.push_str(" = ");
+                value.cgen(cpp, ctx);
+            }
+            (_, None) => cpp.push_str("{}"),
+        }
+        cpp.push_str(";\n");
+        declare(ctx, &self.name);
+    }
+}
+
+/// **Statement:**
+///
+/// `const name: type = expr`
+///
+/// ---
+/// > _The value is folded at compile time, only its literal is generated._
+#[derive(Debug, Clone)]
+pub struct Const {
+    pub name: Token,
+    pub ty: Option<Type>,
+    pub value: Expr,
+}
+impl Const {
+    /// Generate C/C++ constant code.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        indent(cpp, depth);
+        // The strings are owned by the runtime, they are not literal types.
+        match &self.value {
+            Expr::Lit(token) if token.is(&Table::StringLit(None)) => cpp.push_str("const "),
+            _ => cpp.push_str("constexpr "),
+        }
+        match &self.ty {
+            Some(ty) => ty.cgen(cpp),
+            None => cpp.push_str("auto"),
+        }
+        cpp.push(' ');
+        push_id(cpp, &self.name);
+        cpp.push_str(" = ");
+        self.value.cgen(cpp, ctx);
+        cpp.push_str(";\n");
+        declare(ctx, &self.name);
+    }
+}
+
+/// **Statement:**
+///
+/// `expr = expr`
+#[derive(Debug, Clone)]
+pub struct Assign {
+    pub target: Expr,
+    pub value: Expr,
+}
+impl Assign {
+    /// Generate C/C++ assignment code.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        self.target.cgen_tries(cpp, ctx, depth);
+        self.value.cgen_tries(cpp, ctx, depth);
+        indent(cpp, depth);
+        self.target.cgen(cpp, ctx);
+        cpp.push_str(" = ");
+        self.value.cgen(cpp, ctx);
+        cpp.push_str(";\n");
+    }
+}
+
+/// **Statement:**
+///
+/// `for name in expr`<br>
+/// &nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;`...`
+#[derive(Debug, Clone)]
+pub struct For {
+    pub name: Token,
+    pub iter: Expr,
+    pub body: Vec<Stmt>,
+}
+impl For {
+    /// Generate C/C++ range-based loop code.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        self.iter.cgen_tries(cpp, ctx, depth);
+        indent(cpp, depth);
+        cpp.push_str("for (auto &&");
+        push_id(cpp, &self.name);
+        cpp.push_str(" : ");
+        self.iter.cgen(cpp, ctx);
+        cpp.push_str(") ");
+        let len: usize = ctx.scope.borrow().len();
+        declare(ctx, &self.name);
+        body(&self.body, cpp, ctx, depth);
+        ctx.scope.borrow_mut().truncate(len);
+    }
+}
+
+/// **Statement:**
+///
+/// `return expr`
+#[derive(Debug, Clone)]
+pub struct Return {
+    /// Position of `return`.
+    pub pos: Pos,
+    pub value: Option<Expr>,
+}
+impl Return {
+    /// Generate C/C++ return code.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        if let Some(value) = &self.value {
+            value.cgen_tries(cpp, ctx, depth);
+        }
+        indent(cpp, depth);
+        cpp.push_str("return");
+        if let Some(value) = &self.value {
+            cpp.push(' ');
+            value.cgen(cpp, ctx);
+        }
+        cpp.push_str(";\n");
+    }
+}
+
+/// **Statement:**
+///
+/// `defer`<br>
+/// &nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;`...`
+///
+/// ---
+/// > _The body runs at the exit of the scope, in reverse order of the `defer` statements
+/// > and also on `return`, `break` and `continue`._
+#[derive(Debug, Clone)]
+pub struct Defer {
+    /// Position of `defer`, it identifies the guard.
+    pub pos: Pos,
+    pub body: Vec<Stmt>,
+}
+impl Defer {
+    /// Generate the C++ scope guard (`torch::defer torch_defer_line_grapheme{[&] {...}};`).
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        indent(cpp, depth);
+        cpp.push_str(&format!(
+            "{}::defer {}{}_{}{{[&] ",
+            lits::cgen::NAMESPACE,
+            lits::cgen::DEFER_PREFIX,
+            self.pos.line,
+            self.pos.grapheme
+        ));
+        body(&self.body, cpp, ctx, depth);
+        // `}\n` of the body to `}};\n`.
+        cpp.pop();
+        cpp.push_str("};\n");
+    }
+}
+
+/// **Statement:**
+///
+/// `cpp`<br>
+/// &nbsp;&nbsp;&nbsp;&nbsp;`...`
+///
+/// ---
+/// > _The indented lines are C++ (C with the C backend), they name the functions and
+/// > variables of the script as the script does: the functions are renamed to their
+/// > symbols (`half` of `main` -> `_T4main4half`) and the variables with reserved names
+/// > to their escaped names (`std` -> `_T3std`)._<br>
+/// > _The comments, strings, `#include` lines, members (`x.half`) and qualified names
+/// > (`ns::half`) are not renamed, nor the keywords of C/C++ (a variable `new`)._
+#[derive(Debug, Clone)]
+pub struct Cpp {
+    /// Position of `cpp`.
+    pub pos: Pos,
+    /// Line of the script where the code starts.
+    pub line: usize,
+    pub code: String,
+}
+impl Cpp {
+    /// Does the code name the function? The blocks outside of the functions go before
+    /// the prototypes and declare the functions they call.
+    pub fn calls(&self, fn_stmt: &Fn) -> bool {
+        let Some(name) = fn_stmt.name.lit() else {
+            return false;
+        };
+        let name: String = format!("{}", name);
+        !fn_stmt.is_main()
+            && identifiers(&self.code)
+                .into_iter()
+                .any(|id| self.code[id] == name)
+    }
+    /// Names of the script in C/C++, the variables in scope shadow the functions.
+    fn renames(ctx: &Ctx) -> HashMap<String, String> {
+        let mut renames: HashMap<String, String> = ctx
+            .fns
+            .iter()
+            .filter(|name| name.as_str() != lits::cgen::MAIN)
+            .map(|name| (name.clone(), mangle(ctx.module, name)))
+            .collect();
+        for (name, renamed) in ctx.scope.borrow().iter() {
+            renames.insert(name.clone(), renamed.clone());
+        }
+        renames.retain(|name, renamed| name != renamed && !is_keyword(name));
+        renames
+    }
+    /// Generate the C++ lines between `#line` directives, the compiler diagnoses them
+    /// in the script.
+    pub fn cgen(&self, cpp: &mut String, ctx: &Ctx, depth: usize) {
+        let code: String = rename(&self.code, &Self::renames(ctx));
+        cpp.push_str(&format!("#line {} {}\n", self.line, c_string(ctx.script)));
+        for line in code.lines() {
+            if !line.is_empty() {
+                indent(cpp, depth);
+                cpp.push_str(line);
+            }
+            cpp.push('\n');
+        }
+        cpp.push_str(lits::cgen::LINE_RESTORE);
+        cpp.push('\n');
+    }
+}
+/// Rename the identifiers of the C/C++ code (see [`identifiers`]).
+fn rename(code: &str, renames: &HashMap<String, String>) -> String {
+    let mut renamed: String = String::with_capacity(code.len());
+    let mut at: usize = 0;
+    for id in identifiers(code) {
+        if let Some(name) = renames.get(&code[id.clone()]) {
+            renamed.push_str(&code[at..id.start]);
+            renamed.push_str(name);
+            at = id.end;
+        }
+    }
+    renamed.push_str(&code[at..]);
+    renamed
+}
+/// Ranges of the identifiers of the C/C++ code that can name the script.
+///
+/// ---
+/// > _The comments, strings, chars, numbers and `#include` lines are skipped, so are the
+/// > members (`x.half`, `x->half`) and the qualified names (`ns::half`, `half::x`)._
+fn identifiers(code: &str) -> Vec<Range<usize>> {
+    let bytes: &[u8] = code.as_bytes();
+    let mut ids: Vec<Range<usize>> = vec![];
+    let mut i: usize = 0;
+    // Only blanks since the start of the line.
+    let mut line_start: bool = true;
+    let end_of_line = |i: usize| code[i..].find('\n').map_or(bytes.len(), |n| i + n);
+    while i < bytes.len() {
+        let byte: u8 = bytes[i];
+        match byte {
+            b'\n' => {
+                line_start = true;
+                i += 1;
+                continue;
+            }
+            b' ' | b'\t' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            // `#include <...>`
+            b'#' if line_start => {
+                let directive: &str = code[i + 1..].trim_start_matches([' ', '\t']);
+                if directive.starts_with("include") || directive.starts_with("import") {
+                    i = end_of_line(i);
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        line_start = false;
+        if code[i..].starts_with("//") {
+            i = end_of_line(i);
+        } else if code[i..].starts_with("/*") {
+            i = code[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+        } else if byte == b'"' || byte == b'\'' {
+            i = quoted(bytes, i);
+        } else if byte.is_ascii_digit()
+            || byte == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+        {
+            // `1'000`, `1e+5`, `0x1p-3f`
+            i += 1;
+            while let Some(&byte) = bytes.get(i) {
+                let sign: bool = matches!(byte, b'+' | b'-')
+                    && matches!(bytes[i - 1], b'e' | b'E' | b'p' | b'P');
+                if !(is_id_byte(byte) || byte == b'.' || byte == b'\'' || sign) {
+                    break;
+                }
+                i += 1;
+            }
+        } else if is_id_byte(byte) {
+            let start: usize = i;
+            while bytes.get(i).copied().is_some_and(is_id_byte) {
+                i += 1;
+            }
+            let id: &str = &code[start..i];
+            // `u8"..."`, `L'x'`, `R"x(...)x"`
+            match bytes.get(i) {
+                Some(b'"') if ["R", "LR", "uR", "UR", "u8R"].contains(&id) => {
+                    i = raw(code, i);
+                    continue;
+                }
+                Some(b'"' | b'\'') if ["L", "u", "U", "u8"].contains(&id) => {
+                    i = quoted(bytes, i);
+                    continue;
+                }
+                _ => {}
+            }
+            let before: &str = code[..start].trim_end();
+            let member: bool = before.ends_with('.') && !before.ends_with("...")
+                || before.ends_with("->")
+                || before.strip_suffix("::").is_some_and(|before| {
+                    before
+                        .trim_end()
+                        .ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '>')
+                });
+            let qualifier: bool = code[i..].trim_start().starts_with("::");
+            if !member && !qualifier {
+                ids.push(start..i);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    ids
+}
+/// Byte of the identifiers (the non-ASCII ones are UTF-8).
+fn is_id_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+/// End of the string or char at `i` (`"..."`, `'.'`), or of its line if it is unclosed.
+fn quoted(bytes: &[u8], i: usize) -> usize {
+    let quote: u8 = bytes[i];
+    let mut j: usize = i + 1;
+    while let Some(&byte) = bytes.get(j) {
+        match byte {
+            b'\\' => j += 2,
+            b'\n' => return j,
+            _ if byte == quote => return j + 1,
+            _ => j += 1,
+        }
+    }
+    bytes.len()
+}
+/// End of the raw string at `i` (`"delim(...)delim"`).
+fn raw(code: &str, i: usize) -> usize {
+    let Some(open) = code[i..].find('(').map(|n| i + n) else {
+        return quoted(code.as_bytes(), i);
+    };
+    let close: String = format!("){}\"", &code[i + 1..open]);
+    code[open..]
+        .find(&close)
+        .map_or(code.len(), |n| open + n + close.len())
+}
+
+/// Replace the placeholders that return to the generated file (`file.cpp`) by the
+/// `#line` of the following line.
+pub fn restore_lines(cpp: &str, filename: &str) -> String {
+    let mut restored: String = String::with_capacity(cpp.len());
+    for (i, line) in cpp.lines().enumerate() {
+        if line == lits::cgen::LINE_RESTORE {
+            // `i` is `0`-based, the next line is `i + 2`.
+            restored.push_str(&format!("#line {} {}", i + 2, c_string(filename)));
+        } else {
+            restored.push_str(line);
+        }
+        restored.push('\n');
+    }
+    restored
+}
+
+/// Source map of the generated file: its lines (`1`-based) reported in the script by the
+/// `#line` directives, with their lines in the script.
+///
+/// ---
+/// ```json
+/// {"file": "main.cpp", "lines": [[12, 3], [13, 4], ...], "script": "src/main.t"}
+/// ```
+pub fn source_map(code: &str, script: &str, filename: &str) -> serde_json::Value {
+    // `#line 3 "src/file.t"`
+    let file: String = c_string(script);
+    let mut line: Option<usize> = None;
+    let mut lines: Vec<[usize; 2]> = vec![];
+    for (i, generated) in code.lines().enumerate() {
+        if let Some(directive) = generated.strip_prefix("#line ") {
+            line = match directive.split_once(' ') {
+                Some((n, directive_file)) if directive_file == file => n.parse().ok(),
+                _ => None,
+            };
+            continue;
+        }
+        if let Some(n) = line.as_mut() {
+            lines.push([i + 1, *n]);
+            *n += 1;
+        }
+    }
+    serde_json::json!({
+        "file": filename,
+        "lines": lines,
+        "script": script,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpp_renames() {
+        let renames: HashMap<String, String> = HashMap::from([
+            (String::from("half"), String::from("_T4main4half")),
+            (String::from("std"), String::from("_T3std")),
+        ]);
+        let code: &str = r#"#include "half.h"
+auto m = half(std); // half
+std::printf("half %s", R"x(half)x", x.half, p->half, ns::half, half::x, 1e+5half);
+/* half */ f(half, args...);"#;
+        assert_eq!(
+            rename(code, &renames),
+            r#"#include "half.h"
+auto m = _T4main4half(_T3std); // half
+std::printf("half %s", R"x(half)x", x.half, p->half, ns::half, half::x, 1e+5half);
+/* half */ f(_T4main4half, args...);"#
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use torchc_lex::Token;
+use torchc_lits::lits;
+
+/// Language types.
+#[derive(Debug, Clone)]
+#[repr(u8)]
+pub enum Type {
+    /// `i32`, `bool`, `name`, ...
+    Id(Token),
+    /// `[n]type`
+    Array(Box<Self>, usize),
+    /// `[]type`
+    Slice(Box<Self>),
+    /// `list[type]`
+    List(Box<Self>),
+    /// `result[type, type]`
+    Result(Box<Self>, Box<Self>),
+    /// `option[type]`
+    Option(Box<Self>),
+}
+impl Type {
+    /// Is it the owned string (`str`)?
+    pub fn is_str(&self) -> bool {
+        match self {
+            Type::Id(id) => match id.lit() {
+                Some(lit) => format!("{}", lit) == lits::types::STR,
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Generate C/C++ type code.
+    pub fn cgen(&self, cpp: &mut String) {
+        match self {
+            Type::Id(id) => {
+                let id: String = match id.lit() {
+                    Some(lit) => format!("{}", lit),
+                    None => String::from(lits::cgen::DEFAULT_ID),
+                };
+                // `torch::str`
+                if id == lits::types::STR {
+                    cpp.push_str(lits::cgen::NAMESPACE);
+                    cpp.push_str("::str");
+                    return;
+                }
+                cpp.push_str(match id.as_str() {
+                    lits::types::I8 => "std::int8_t",
+                    lits::types::I16 => "std::int16_t",
+                    lits::types::I32 => "std::int32_t",
+                    lits::types::I64 => "std::int64_t",
+                    lits::types::U8 => "std::uint8_t",
+                    lits::types::U16 => "std::uint16_t",
+                    lits::types::U32 => "std::uint32_t",
+                    lits::types::U64 => "std::uint64_t",
+                    lits::types::F32 => "float",
+                    lits::types::F64 => "double",
+                    lits::types::BOOL => "bool",
+                    lits::types::CHAR => "char32_t",
+                    id => id,
+                });
+            }
+            // `torch::array<type, n>`
+            Type::Array(ty, n) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::array<");
+                ty.cgen(cpp);
+                cpp.push_str(&format!(", {}>", n));
+            }
+            // `torch::slice<type>`
+            Type::Slice(ty) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::slice<");
+                ty.cgen(cpp);
+                cpp.push('>');
+            }
+            // `torch::list<type>`
+            Type::List(ty) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::list<");
+                ty.cgen(cpp);
+                cpp.push('>');
+            }
+            // `torch::result<type, type>`
+            Type::Result(ty, err) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::result<");
+                ty.cgen(cpp);
+                cpp.push_str(", ");
+                err.cgen(cpp);
+                cpp.push('>');
+            }
+            // `torch::option<type>`
+            Type::Option(ty) => {
+                cpp.push_str(lits::cgen::NAMESPACE);
+                cpp.push_str("::option<");
+                ty.cgen(cpp);
+                cpp.push('>');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cgen::{Fn, Param};
+    use torchc_lex::{Pos, Table};
+
+    fn id(name: &str) -> Token {
+        Token {
+            lexeme: Table::Id(Some(name.as_bytes().into())),
+            pos: Pos::default(),
+        }
+    }
+    fn cgen(ty: &Type) -> String {
+        let mut cpp: String = String::new();
+        ty.cgen(&mut cpp);
+        cpp
+    }
+
+    #[test]
+    fn types() {
+        let i64: Type = Type::Id(id("i64"));
+        let str: Type = Type::Id(id("str"));
+        for (ty, cpp) in [
+            (i64.clone(), "std::int64_t"),
+            (str.clone(), "torch::str"),
+            (Type::Id(id("char")), "char32_t"),
+            (
+                Type::Array(Box::new(i64.clone()), 3),
+                "torch::array<std::int64_t, 3>",
+            ),
+            (
+                Type::Slice(Box::new(str.clone())),
+                "torch::slice<torch::str>",
+            ),
+            (
+                Type::List(Box::new(Type::Slice(Box::new(i64.clone())))),
+                "torch::list<torch::slice<std::int64_t>>",
+            ),
+            (
+                Type::Result(Box::new(i64.clone()), Box::new(str.clone())),
+                "torch::result<std::int64_t, torch::str>",
+            ),
+            (
+                Type::Option(Box::new(Type::List(Box::new(str)))),
+                "torch::option<torch::list<torch::str>>",
+            ),
+        ] {
+            assert_eq!(cgen(&ty), cpp);
+        }
+    }
+
+    #[test]
+    fn signatures() {
+        let i64: Type = Type::Id(id("i64"));
+        let mut fn_stmt: Fn = Fn::new();
+        fn_stmt.name = id("first");
+        fn_stmt.params = vec![
+            Param {
+                name: id("items"),
+                ty: Type::Slice(Box::new(i64.clone())),
+            },
+            Param {
+                name: id("class"),
+                ty: Type::Id(id("i32")),
+            },
+        ];
+        fn_stmt.ret = Some(Type::Option(Box::new(i64)));
+        let mut cpp: String = String::new();
+        fn_stmt.cgen_signature(&mut cpp, &fn_stmt.symbol("main"));
+        assert_eq!(
+            cpp,
+            "torch::option<std::int64_t> _T4main5first(torch::slice<std::int64_t> items, \
+             std::int32_t _T5class)"
+        );
+
+        // Without a return type.
+        fn_stmt.ret = None;
+        fn_stmt.params.clear();
+        let mut cpp: String = String::new();
+        fn_stmt.cgen_signature(&mut cpp, &fn_stmt.symbol("main"));
+        assert_eq!(cpp, "void _T4main5first()");
+    }
+}
@@ -0,0 +1,723 @@
+use super::{lit, ty::Ty, Binding, Lower};
+use crate::cgen::{c_string, indent, push_lit, try_tmp, Expr};
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+
+impl Lower<'_> {
+    /// Type of the value of the expression, `None` if the script does not say it (the
+    /// functions of the dependencies, `ok(...)`, ...).
+    pub(super) fn type_of(&self, expr: &Expr) -> Option<Ty> {
+        match expr {
+            Expr::Lit(token) => Some(Ty::id(match &token.lexeme {
+                Table::StringLit(_) => lits::types::STR,
+                Table::CharLit(_) => lits::types::CHAR,
+                Table::True | Table::False => lits::types::BOOL,
+                Table::FloatLit(_) => lits::types::F64,
+                // `int` as in C/C++, the literals that do not fit are `long`.
+                _ if is_int(&lit(token)) => lits::types::I32,
+                _ => lits::types::I64,
+            })),
+            Expr::Id(id) => self.lookup(&lit(id)).map(|local| local.ty),
+            Expr::Array(items) => Some(Ty::Array(
+                Box::new(self.type_of(items.first()?)?),
+                items.len(),
+            )),
+            Expr::Unary(op, expr) => match lit(op).as_str() {
+                "!" => Some(Ty::id(lits::types::BOOL)),
+                _ => self.type_of(expr),
+            },
+            Expr::Binary(lhs, op, rhs) => match lit(op).as_str() {
+                "==" | "!=" | "<" | "<=" | ">" | ">=" | "&&" | "||" => {
+                    Some(Ty::id(lits::types::BOOL))
+                }
+                _ => match (self.type_of(lhs), self.type_of(rhs)) {
+                    (Some(lhs), Some(rhs)) => Some(Ty::arithmetic(lhs, rhs)),
+                    (lhs, rhs) => lhs.or(rhs),
+                },
+            },
+            Expr::Range(..) => None,
+            Expr::Format(_) => Some(Ty::id(lits::types::STR)),
+            Expr::Call(callee, _) => match callee.as_ref() {
+                Expr::Id(id) => match self.builtin(id) {
+                    Some(lits::builtins::LEN) => Some(Ty::id(lits::types::I64)),
+                    Some(_) => None,
+                    None => self.fns.get(&lit(id))?.1.clone(),
+                },
+                Expr::Member(object, name) if self.ctx.import(callee).is_none() => {
+                    self.method_type(&self.type_of(object)?, &lit(name))
+                }
+                _ => None,
+            },
+            Expr::Member(object, name) => {
+                if self.ctx.import(expr).is_some() {
+                    return None;
+                }
+                match (self.type_of(object)?, lit(name).as_str()) {
+                    (Ty::Result(..), lits::members::OK) | (Ty::Option(_), lits::members::SOME) => {
+                        Some(Ty::id(lits::types::BOOL))
+                    }
+                    (Ty::Result(value, _) | Ty::Option(value), lits::members::VALUE) => {
+                        Some(*value)
+                    }
+                    (Ty::Result(_, error), lits::members::ERROR) => Some(*error),
+                    (Ty::Slice(_) | Ty::List(_), lits::members::LEN) => {
+                        Some(Ty::id(lits::types::I64))
+                    }
+                    _ => None,
+                }
+            }
+            // The bytes of the strings are `char` as in C++.
+            Expr::Index(object, ..) => match self.type_of(object)? {
+                ty if ty.is(lits::types::STR) => Some(Ty::id(lits::types::I8)),
+                ty => ty.item().cloned(),
+            },
+            Expr::Slice(object, ..) => match self.type_of(object)? {
+                ty if ty.is(lits::types::STR) => Some(ty),
+                ty => Some(Ty::Slice(Box::new(ty.item()?.clone()))),
+            },
+            Expr::Try(expr, _) => match self.type_of(expr)? {
+                Ty::Result(value, _) | Ty::Option(value) => Some(*value),
+                _ => None,
+            },
+        }
+    }
+    /// Type returned by the method of the built-in type.
+    fn method_type(&self, ty: &Ty, name: &str) -> Option<Ty> {
+        match (ty, name) {
+            (Ty::Id(id), lits::members::LEN | lits::members::GRAPHEMES)
+                if id == lits::types::STR =>
+            {
+                Some(Ty::id(lits::types::I64))
+            }
+            (Ty::Id(id), lits::members::IS_CHAR_BOUNDARY) if id == lits::types::STR => {
+                Some(Ty::id(lits::types::BOOL))
+            }
+            (Ty::Array(..) | Ty::Slice(_) | Ty::List(_), lits::members::LEN) => {
+                Some(Ty::id(lits::types::I64))
+            }
+            (Ty::List(item), lits::members::POP) => Some(*item.clone()),
+            (Ty::Result(..), lits::members::IS_OK | lits::members::IS_ERR)
+            | (Ty::Option(_), lits::members::IS_SOME | lits::members::IS_NONE) => {
+                Some(Ty::id(lits::types::BOOL))
+            }
+            (
+                Ty::Result(value, _) | Ty::Option(value),
+                lits::members::UNWRAP | lits::members::UNWRAP_OR,
+            ) => Some(*value.clone()),
+            _ => None,
+        }
+    }
+    /// Built-in named by the identifier (`len`, `ok`, ...), `None` if it is not one or a
+    /// declaration shadows it.
+    pub(super) fn builtin(&self, id: &Token) -> Option<&'static str> {
+        let name: String = lit(id);
+        let builtin: &str = lits::builtins::ALL
+            .into_iter()
+            .find(|builtin| *builtin == name)?;
+        match self.lookup(&name) {
+            Some(_) => None,
+            None => Some(builtin),
+        }
+    }
+    /// Does the expression designate an object that C can address (`x`, `x.y`, `x[i]`)?
+    /// The constants are copied.
+    fn is_object(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Id(id) => self
+                .lookup(&lit(id))
+                .is_some_and(|local| local.binding != Binding::Const),
+            Expr::Member(object, _) => self.ctx.import(expr).is_none() && self.is_object(object),
+            Expr::Index(object, ..) => !self
+                .type_of(object)
+                .is_some_and(|ty| ty.is(lits::types::STR)),
+            _ => false,
+        }
+    }
+    /// Type of the expression that C needs, it is diagnosed if the script does not say it.
+    pub(super) fn expect_type(&self, expr: &Expr) -> Ty {
+        match self.type_of(expr) {
+            Some(ty) => ty,
+            None => self.diagnose(
+                "the C backend does not know the type of the expression, declare it",
+                expr.pos(),
+            ),
+        }
+    }
+    /// Position of the expression for the runtime (`(torch_pos){"src/file.t", line, grapheme}`).
+    fn pos(&self, c: &mut String, pos: &Pos) {
+        c.push_str(&format!(
+            "(torch_pos){{{}, {}, {}}}",
+            c_string(self.ctx.script),
+            pos.line,
+            pos.grapheme
+        ));
+    }
+
+    /// Generate the expression as a value of the type.
+    ///
+    /// ---
+    /// > _`ok(...)`, `err(...)`, `some(...)`, `none` and the array literals take the type
+    /// > (compound literals), the arrays and lists are viewed as slices and the lists are
+    /// > copied, as the conversions and copies of C++._
+    pub(super) fn value(&self, c: &mut String, expr: &Expr, ty: &Ty) {
+        let c_ty: String = self.ty(ty);
+        match (expr, ty) {
+            // `((R){.ok = true, .value = expr})`, `((R){.ok = false, .error = expr})`
+            (Expr::Call(callee, args), Ty::Result(value, error)) if args.len() == 1 => {
+                let (flag, field, ty) = match callee.as_ref() {
+                    Expr::Id(id) => match self.builtin(id) {
+                        Some(lits::builtins::OK) => ("true", lits::members::VALUE, value),
+                        Some(lits::builtins::ERR) => ("false", lits::members::ERROR, error),
+                        _ => return self.converted(c, expr, ty),
+                    },
+                    _ => return self.converted(c, expr, ty),
+                };
+                c.push_str(&format!(
+                    "(({}){{.{} = {}, .{} = ",
+                    c_ty,
+                    lits::members::OK,
+                    flag,
+                    field
+                ));
+                self.value(c, &args[0], ty);
+                c.push_str("})");
+            }
+            // `((O){.some = true, .value = expr})`
+            (Expr::Call(callee, args), Ty::Option(value))
+                if args.len() == 1
+                    && matches!(callee.as_ref(), Expr::Id(id)
+                        if self.builtin(id) == Some(lits::builtins::SOME)) =>
+            {
+                c.push_str(&format!(
+                    "(({}){{.{} = true, .{} = ",
+                    c_ty,
+                    lits::members::SOME,
+                    lits::members::VALUE
+                ));
+                self.value(c, &args[0], value);
+                c.push_str("})");
+            }
+            // `((O){.some = false})`
+            (Expr::Id(id), Ty::Option(_)) if self.builtin(id) == Some(lits::builtins::NONE) => {
+                c.push_str(&format!("(({}){{.{} = false}})", c_ty, lits::members::SOME))
+            }
+            // `((A){{expr, expr, ...}})`
+            (Expr::Array(items), Ty::Array(item, len)) => {
+                if items.len() != *len {
+                    self.diagnose(
+                        &format!("the array has {} items instead of {}", items.len(), len),
+                        expr.pos(),
+                    );
+                }
+                c.push_str(&format!("(({}){{", c_ty));
+                self.items(c, items, item);
+                c.push_str("})");
+            }
+            // `((S){0})`, `((L){0})`
+            (Expr::Array(items), Ty::Slice(_) | Ty::List(_)) if items.is_empty() => {
+                c.push_str(&format!("(({}){{0}})", c_ty))
+            }
+            // `((S){(T[]){expr, expr, ...}, n})`
+            (Expr::Array(items), Ty::Slice(item)) => {
+                c.push_str(&format!("(({}){{({}[])", c_ty, item.c()));
+                self.items(c, items, item);
+                c.push_str(&format!(", {}}})", items.len()));
+            }
+            // `L_from((S){(T[]){expr, expr, ...}, n})`, the items are copied.
+            (Expr::Array(items), Ty::List(item)) => {
+                let slice: String = self.ty(&Ty::Slice(item.clone()));
+                c.push_str(&format!("{}_from(({}){{({}[])", c_ty, slice, item.c()));
+                self.items(c, items, item);
+                c.push_str(&format!(", {}}})", items.len()));
+            }
+            _ => self.converted(c, expr, ty),
+        }
+    }
+    /// Generate the value of the expression converted to the type (see [`Lower::value`]).
+    fn converted(&self, c: &mut String, expr: &Expr, ty: &Ty) {
+        match (self.type_of(expr), ty) {
+            (Some(from @ (Ty::Array(..) | Ty::List(_))), Ty::Slice(_)) => {
+                self.slice(c, expr, &from)
+            }
+            (Some(from), _) if from.is_deep() && self.is_object(expr) => {
+                c.push_str(&format!("{}_copy(", self.ty(&from)));
+                self.expr(c, expr);
+                c.push(')');
+            }
+            _ => self.expr(c, expr),
+        }
+    }
+    /// Generate the braced items of the type (`{expr, expr, ...}`).
+    pub(super) fn items(&self, c: &mut String, items: &[Expr], ty: &Ty) {
+        c.push('{');
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                c.push_str(", ");
+            }
+            self.value(c, item, ty);
+        }
+        c.push('}');
+    }
+    /// Generate the collection as a slice (`torch_slice_i32`).
+    pub(super) fn slice(&self, c: &mut String, expr: &Expr, ty: &Ty) {
+        match ty {
+            // `A_slice(&expr)`
+            Ty::Array(..) => {
+                c.push_str(&format!("{}_slice(", self.ty(ty)));
+                self.addr(c, expr, ty);
+                c.push(')');
+            }
+            // `L_slice(expr)`
+            Ty::List(_) => {
+                c.push_str(&format!("{}_slice(", self.ty(ty)));
+                self.expr(c, expr);
+                c.push(')');
+            }
+            _ => self.expr(c, expr),
+        }
+    }
+    /// Generate the address of the object, the values are in compound literals
+    /// (`(torch_list_i32[]){expr}`).
+    fn addr(&self, c: &mut String, expr: &Expr, ty: &Ty) {
+        if self.is_object(expr) {
+            c.push('&');
+            self.expr(c, expr);
+        } else {
+            c.push_str(&format!("({}[]){{", self.ty(ty)));
+            self.expr(c, expr);
+            c.push('}');
+        }
+    }
+
+    /// Generate C expression code.
+    pub(super) fn expr(&self, c: &mut String, expr: &Expr) {
+        match expr {
+            Expr::Lit(token) => match &token.lexeme {
+                Table::True => c.push_str("true"),
+                Table::False => c.push_str("false"),
+                // `'x'` -> `U'x'`
+                Table::CharLit(_) => {
+                    c.push('U');
+                    push_lit(c, token);
+                }
+                // `"..."` -> `TORCH_STR("...")`
+                Table::StringLit(_) => {
+                    c.push_str("TORCH_STR(");
+                    push_lit(c, token);
+                    c.push(')');
+                }
+                _ => push_lit(c, token),
+            },
+            Expr::Id(id) => {
+                let name: String = lit(id);
+                if let Some(builtin) = self.builtin(id) {
+                    self.untyped(builtin, &id.pos);
+                }
+                match self.lookup(&name) {
+                    // Constants of the headers are C strings.
+                    _ if self.c_strs.contains(&name) && self.is_global(&name) => {
+                        c.push_str(&format!("torch_str_from_c({})", name))
+                    }
+                    // `(*item)`
+                    Some(local) if local.binding == Binding::Item => {
+                        c.push_str("(*");
+                        self.name(c, id);
+                        c.push(')');
+                    }
+                    _ => self.name(c, id),
+                }
+            }
+            Expr::Array(_) => {
+                let ty: Ty = self.expect_type(expr);
+                self.value(c, expr, &ty);
+            }
+            Expr::Unary(op, expr) => {
+                c.push('(');
+                push_lit(c, op);
+                self.expr(c, expr);
+                c.push(')');
+            }
+            Expr::Binary(lhs, op, rhs)
+                if self.type_of(lhs).is_some_and(|ty| ty.is(lits::types::STR)) =>
+            {
+                let op: String = lit(op);
+                let call: &str = match op.as_str() {
+                    "+" => "torch_str_concat(",
+                    "==" => "torch_str_eq(",
+                    "!=" => "!torch_str_eq(",
+                    _ => "torch_str_cmp(",
+                };
+                c.push('(');
+                c.push_str(call);
+                self.expr(c, lhs);
+                c.push_str(", ");
+                self.expr(c, rhs);
+                c.push(')');
+                // `torch_str_cmp(lhs, rhs) < 0`
+                if !matches!(op.as_str(), "+" | "==" | "!=") {
+                    c.push_str(&format!(" {} 0", op));
+                }
+                c.push(')');
+            }
+            Expr::Binary(lhs, op, rhs) => {
+                c.push('(');
+                self.expr(c, lhs);
+                c.push(' ');
+                push_lit(c, op);
+                c.push(' ');
+                self.expr(c, rhs);
+                c.push(')');
+            }
+            Expr::Range(lo, _) => self.unsupported("ranges outside of 'for'", lo.pos()),
+            // `torch_format(n, piece, piece, ...)`
+            Expr::Format(pieces) => {
+                c.push_str(&format!("torch_format({}", pieces.len()));
+                for piece in pieces {
+                    c.push_str(", ");
+                    let call: &str = match self.type_of(piece) {
+                        Some(Ty::Id(id)) => match id.as_str() {
+                            lits::types::STR => {
+                                self.expr(c, piece);
+                                continue;
+                            }
+                            lits::types::CHAR => "torch_str_from_char(",
+                            lits::types::BOOL => "torch_str_from_bool(",
+                            _ => "torch_to_str(",
+                        },
+                        Some(ty) => {
+                            self.unsupported(&format!("interpolations of '{}'", ty), piece.pos())
+                        }
+                        None => "torch_to_str(",
+                    };
+                    c.push_str(call);
+                    self.expr(c, piece);
+                    c.push(')');
+                }
+                c.push(')');
+            }
+            Expr::Call(callee, args) => self.call(c, callee, args),
+            Expr::Member(object, name) => match self.ctx.import(expr) {
+                // `mathx.ops.add` -> `_T5mathx3ops3add`
+                Some(symbol) => c.push_str(&symbol),
+                None => {
+                    self.expr(c, object);
+                    c.push('.');
+                    push_lit(c, name);
+                }
+            },
+            Expr::Index(object, i, pos) => match self.expect_type(object) {
+                // `(*torch_str_at(expr, expr, pos))`
+                ty if ty.is(lits::types::STR) => {
+                    c.push_str("(*torch_str_at(");
+                    self.expr(c, object);
+                    c.push_str(", ");
+                    self.expr(c, i);
+                    c.push_str(", ");
+                    self.pos(c, pos);
+                    c.push_str("))");
+                }
+                // `(*S_at(slice, expr, pos))`
+                ty => {
+                    let Some(item) = ty.item() else {
+                        self.unsupported(&format!("indexing of '{}'", ty), Some(pos))
+                    };
+                    c.push_str(&format!(
+                        "(*{}_at(",
+                        self.ty(&Ty::Slice(Box::new(item.clone())))
+                    ));
+                    self.slice(c, object, &ty);
+                    c.push_str(", ");
+                    self.expr(c, i);
+                    c.push_str(", ");
+                    self.pos(c, pos);
+                    c.push_str("))");
+                }
+            },
+            // `S_sub(slice, lo, hi, pos)` or `S_tail(slice, lo, pos)`, the strings are
+            // `torch_str_sub` and `torch_str_tail`.
+            Expr::Slice(object, lo, hi, pos) => {
+                let ty: Ty = self.expect_type(object);
+                let prefix: String = match &ty {
+                    ty if ty.is(lits::types::STR) => String::from("torch_str"),
+                    ty => match ty.item() {
+                        Some(item) => self.ty(&Ty::Slice(Box::new(item.clone()))),
+                        None => self.unsupported(&format!("slicing of '{}'", ty), Some(pos)),
+                    },
+                };
+                c.push_str(&prefix);
+                c.push_str(match hi {
+                    Some(_) => "_sub(",
+                    None => "_tail(",
+                });
+                self.slice(c, object, &ty);
+                c.push_str(", ");
+                match lo {
+                    Some(lo) => self.expr(c, lo),
+                    None => c.push('0'),
+                }
+                if let Some(hi) = hi {
+                    c.push_str(", ");
+                    self.expr(c, hi);
+                }
+                c.push_str(", ");
+                self.pos(c, pos);
+                c.push(')');
+            }
+            // `torch_try_line_grapheme.value`
+            Expr::Try(_, pos) => {
+                c.push_str(&try_tmp(pos));
+                c.push('.');
+                c.push_str(lits::members::VALUE);
+            }
+        }
+    }
+    /// Generate the call, the arguments are converted to the types of the parameters.
+    fn call(&self, c: &mut String, callee: &Expr, args: &[Expr]) {
+        match callee {
+            Expr::Id(id) => match self.builtin(id) {
+                Some(lits::builtins::LEN) if args.len() == 1 => {
+                    return self.len(c, &args[0], &self.expect_type(&args[0]))
+                }
+                Some(builtin) => self.untyped(builtin, &id.pos),
+                None => {}
+            },
+            Expr::Member(object, name) if self.ctx.import(callee).is_none() => {
+                if let Some(ty) = self
+                    .type_of(object)
+                    .filter(|ty| ty.is_generic() || ty.is(lits::types::STR))
+                {
+                    return self.method(c, object, &ty, name, args);
+                }
+            }
+            _ => {}
+        }
+        let params: Option<&Vec<Ty>> = match callee {
+            Expr::Id(id) if self.lookup(&lit(id)).is_none() => {
+                self.fns.get(&lit(id)).map(|(params, _)| params)
+            }
+            _ => None,
+        };
+        self.expr(c, callee);
+        c.push('(');
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                c.push_str(", ");
+            }
+            match params.and_then(|params| params.get(i)) {
+                Some(param) => self.value(c, arg, param),
+                None => self.expr(c, arg),
+            }
+        }
+        c.push(')');
+    }
+    /// Generate the call of the method of the built-in type with its helper of C.
+    fn method(&self, c: &mut String, object: &Expr, ty: &Ty, name: &Token, args: &[Expr]) {
+        let method: String = lit(name);
+        let c_ty: String = self.ty(ty);
+        let arity: usize = match method.as_str() {
+            lits::members::PUSH | lits::members::UNWRAP_OR | lits::members::IS_CHAR_BOUNDARY => 1,
+            _ => 0,
+        };
+        // `push` and `clear` return nothing.
+        let known: bool = self.method_type(ty, &method).is_some()
+            || matches!(
+                (ty, method.as_str()),
+                (Ty::List(_), lits::members::PUSH | lits::members::CLEAR)
+            );
+        if !known || args.len() != arity {
+            self.unsupported(
+                &format!("the method '{}' of '{}'", method, ty),
+                Some(&name.pos),
+            );
+        }
+        match (ty, method.as_str()) {
+            (_, lits::members::LEN) => self.len(c, object, ty),
+            (_, lits::members::GRAPHEMES) => self.unsupported(
+                &format!("'{}' (its grapheme table is C++)", method),
+                Some(&name.pos),
+            ),
+            // `torch_str_is_char_boundary(expr, expr)`
+            (_, lits::members::IS_CHAR_BOUNDARY) => {
+                c.push_str("torch_str_is_char_boundary(");
+                self.expr(c, object);
+                c.push_str(", ");
+                self.expr(c, &args[0]);
+                c.push(')');
+            }
+            // `L_push(&expr, expr)`, `L_pop(&expr)`, `L_clear(&expr)`
+            (Ty::List(item), _) => {
+                c.push_str(&format!("{}_{}(", c_ty, method));
+                self.addr(c, object, ty);
+                if let Some(arg) = args.first() {
+                    c.push_str(", ");
+                    self.value(c, arg, item);
+                }
+                c.push(')');
+            }
+            // `(expr).ok`, `!(expr).some`, ...
+            (
+                _,
+                lits::members::IS_OK
+                | lits::members::IS_ERR
+                | lits::members::IS_SOME
+                | lits::members::IS_NONE,
+            ) => {
+                if matches!(
+                    method.as_str(),
+                    lits::members::IS_ERR | lits::members::IS_NONE
+                ) {
+                    c.push('!');
+                }
+                c.push('(');
+                self.expr(c, object);
+                c.push_str(match ty {
+                    Ty::Result(..) => ").ok",
+                    _ => ").some",
+                });
+            }
+            // `R_unwrap(expr)`, `R_unwrap_or(expr, expr)`
+            (Ty::Result(value, _) | Ty::Option(value), _) => {
+                c.push_str(&format!("{}_{}(", c_ty, method));
+                self.expr(c, object);
+                if let Some(arg) = args.first() {
+                    c.push_str(", ");
+                    self.value(c, arg, value);
+                }
+                c.push(')');
+            }
+            _ => self.unsupported(
+                &format!("the method '{}' of '{}'", method, ty),
+                Some(&name.pos),
+            ),
+        }
+    }
+    /// Generate the length of the string or collection (`len(expr)`, `expr.len()`).
+    fn len(&self, c: &mut String, expr: &Expr, ty: &Ty) {
+        match ty {
+            ty if ty.is(lits::types::STR) => {
+                c.push_str("torch_str_len(");
+                self.expr(c, expr);
+                c.push(')');
+            }
+            // `(expr).len`
+            Ty::Slice(_) | Ty::List(_) => {
+                c.push('(');
+                self.expr(c, expr);
+                c.push_str(").len");
+            }
+            // `(int64_t)n`, the values are still evaluated (`((void)expr, (int64_t)n)`).
+            Ty::Array(_, len) if self.is_object(expr) => c.push_str(&format!("(int64_t){}", len)),
+            Ty::Array(_, len) => {
+                c.push_str("((void)");
+                self.expr(c, expr);
+                c.push_str(&format!(", (int64_t){})", len));
+            }
+            ty => self.unsupported(
+                &format!("'{}' of '{}'", lits::builtins::LEN, ty),
+                expr.pos(),
+            ),
+        }
+    }
+    /// Stop at a built-in value whose type is not known (`ok(...)` out of a declaration,
+    /// an argument or a `return`).
+    fn untyped(&self, builtin: &str, pos: &Pos) -> ! {
+        self.diagnose(
+            &format!(
+                "the C backend does not know the type of '{}', declare it",
+                builtin
+            ),
+            Some(pos),
+        )
+    }
+
+    /// Generate the propagations (`expr?`) of the expression before its statement.
+    ///
+    /// ---
+    /// `R torch_try_line_grapheme = expr;`<br>
+    /// `if (!torch_try_line_grapheme.ok) { deferred code; return (R2){.ok = false, ...}; }`
+    pub(super) fn tries(&mut self, c: &mut String, expr: &Expr, depth: usize) {
+        match expr {
+            Expr::Lit(_) | Expr::Id(_) => {}
+            Expr::Array(items) | Expr::Format(items) => {
+                for item in items {
+                    self.tries(c, item, depth);
+                }
+            }
+            Expr::Unary(_, expr) | Expr::Member(expr, _) => self.tries(c, expr, depth),
+            Expr::Binary(lhs, _, rhs) | Expr::Range(lhs, rhs) | Expr::Index(lhs, rhs, _) => {
+                self.tries(c, lhs, depth);
+                self.tries(c, rhs, depth);
+            }
+            Expr::Call(callee, args) => {
+                self.tries(c, callee, depth);
+                for arg in args {
+                    self.tries(c, arg, depth);
+                }
+            }
+            Expr::Slice(expr, lo, hi, _) => {
+                self.tries(c, expr, depth);
+                for bound in [lo, hi].into_iter().flatten() {
+                    self.tries(c, bound, depth);
+                }
+            }
+            Expr::Try(expr, pos) => {
+                self.tries(c, expr, depth);
+
+                // The checker only lets results in the functions of results, options in
+                // the ones of options.
+                let ty: Ty = self.expect_type(expr);
+                let (flag, ret): (&str, String) = match (&ty, &self.ret) {
+                    (Ty::Result(..), Some(ret @ Ty::Result(..))) => {
+                        (lits::members::OK, self.ty(ret))
+                    }
+                    (Ty::Option(_), Some(ret @ Ty::Option(_))) => {
+                        (lits::members::SOME, self.ty(ret))
+                    }
+                    _ => self.unsupported(&format!("'?' of '{}' here", ty), Some(pos)),
+                };
+                let tmp: String = try_tmp(pos);
+                self.ctx.cgen_line(c, pos);
+                indent(c, depth);
+                c.push_str(&format!("{} {} = ", self.ty(&ty), tmp));
+                self.expr(c, expr);
+                c.push_str(";\n");
+                self.ctx.cgen_line(c, pos);
+                indent(c, depth);
+                c.push_str(&format!("if (!{}.{}) {{\n", tmp, flag));
+                self.unwind(c, self.fn_scope, depth + 1);
+                self.ctx.cgen_line(c, pos);
+                indent(c, depth + 1);
+                match flag {
+                    lits::members::OK => c.push_str(&format!(
+                        "return ({}){{.{} = false, .{} = {}.{}}};\n",
+                        ret,
+                        lits::members::OK,
+                        lits::members::ERROR,
+                        tmp,
+                        lits::members::ERROR
+                    )),
+                    _ => c.push_str(&format!(
+                        "return ({}){{.{} = false}};\n",
+                        ret,
+                        lits::members::SOME
+                    )),
+                }
+                indent(c, depth);
+                c.push_str("}\n");
+                self.ctx.cgen_line(c, pos);
+            }
+        }
+    }
+}
+
+/// Does the integer literal fit in `int`?
+fn is_int(lit: &str) -> bool {
+    let digits: String = lit.replace('_', "");
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x" | "0X") => (16, &digits[2..]),
+        Some("0o" | "0O") => (8, &digits[2..]),
+        Some("0b" | "0B") => (2, &digits[2..]),
+        _ => (10, digits.as_str()),
+    };
+    i32::from_str_radix(digits, radix).is_ok()
+}
@@ -0,0 +1,898 @@
+use super::{Emit, Runtime};
+use crate::cgen::{
+    escape, indent, push_id, push_lit, Abi, Const, Ctx, Defer, Expr, ExternDecl, Fn, For, Return,
+    Stmt, Type, Var,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+use torchc_lex::{Pos, Token};
+use torchc_lits::lits;
+use ty::{Ty, Types};
+
+mod expr;
+mod ty;
+
+/// C runtime of the language, written next to the generated code.
+const RUNTIME: &str = include_str!("../../runtime/torch_c.h");
+
+/// _**C backend**_
+///
+/// ---
+/// > _The strings are the `torch_str` of the runtime, their operators and the
+/// > interpolations are calls of the runtime chosen by the types of the operands._<br>
+/// > _The arrays, slices, lists, results and options are structs generated for each of
+/// > their types (see [`Ty`]) and `defer` is its code at every exit of its scope._
+pub struct C;
+impl Emit for C {
+    fn extension(&self) -> &'static str {
+        lits::extensions::C
+    }
+    fn runtime(&self) -> Vec<Runtime> {
+        vec![Runtime {
+            name: lits::cgen::C_RUNTIME,
+            contents: || String::from(RUNTIME),
+            once: false,
+        }]
+    }
+    fn emit(&self, script: &[Stmt], ctx: &Ctx, target: &Path) -> String {
+        Lower::new(script, ctx).script(script, target)
+    }
+    fn interface(&self, script: &[Stmt], ctx: &Ctx) -> String {
+        let lower: Lower = Lower::new(script, ctx);
+        // It is included after the runtime.
+        let mut c: String = String::from("#pragma once\n\n");
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                if !fn_stmt.is_main() {
+                    let mut item: String = String::new();
+                    lower.signature(&mut item, fn_stmt);
+                    item.push_str(";\n");
+                    lower.item(&mut c, &item);
+                }
+            }
+        }
+        c
+    }
+}
+
+/// Names of a body and its deferred code.
+#[derive(Debug, Default)]
+struct Scope {
+    names: HashMap<String, Local>,
+    /// `defer` statements, their code runs in reverse order at the exits of the scope.
+    defers: Vec<Defer>,
+    /// Is it the body of a loop (the scope that `break` and `continue` exit)?
+    is_loop: bool,
+}
+/// Constant, variable or parameter.
+#[derive(Debug, Clone)]
+struct Local {
+    ty: Ty,
+    binding: Binding,
+}
+/// How the name is bound to its value in C.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum Binding {
+    Var,
+    Const,
+    /// Item of a loop over a collection, a pointer to it (`for (T *x = ...)`) so the
+    /// assignments change the item as the references of C++.
+    Item,
+}
+
+/// Lowering of the **cgen data** of a script to C.
+struct Lower<'lower> {
+    ctx: &'lower Ctx<'lower>,
+    /// Types of the parameters and the return of the functions.
+    fns: HashMap<String, (Vec<Ty>, Option<Ty>)>,
+    /// Names of the functions and `extern` declarations in C (`_T4main4half`,
+    /// `torch_c_name`, ...), the other names are escaped.
+    names: HashMap<String, String>,
+    /// `extern "c" const` strings, they are `const char *` in the headers.
+    c_strs: HashSet<String>,
+    /// Constants, variables and parameters (the innermost scope is the last).
+    scopes: Vec<Scope>,
+    /// Scope of the parameters of the function being generated.
+    fn_scope: usize,
+    /// Return type of the function being generated.
+    ret: Option<Ty>,
+    /// Is the function being generated `main`? Its `return` has the exit status.
+    main: bool,
+    /// Definitions of the generic types used by the code generated so far.
+    types: RefCell<Types>,
+    /// Position of the statement being generated, the diagnostics of its expressions
+    /// without position (`[]`) are at it.
+    at: Cell<Pos>,
+}
+impl<'lower> Lower<'lower> {
+    fn new(script: &[Stmt], ctx: &'lower Ctx<'lower>) -> Self {
+        let mut lower: Self = Self {
+            ctx,
+            fns: HashMap::new(),
+            names: HashMap::new(),
+            c_strs: HashSet::new(),
+            scopes: vec![Scope::default()],
+            fn_scope: 0,
+            ret: None,
+            main: false,
+            types: RefCell::new(Types::default()),
+            at: Cell::new(Pos::default()),
+        };
+        for stmt in script {
+            match stmt {
+                Stmt::Fn(Some(fn_stmt)) => {
+                    let name: String = lit(&fn_stmt.name);
+                    lower.names.insert(name.clone(), fn_stmt.symbol(ctx.module));
+                    lower.fns.insert(name, signature_types(fn_stmt));
+                }
+                Stmt::Extern(Some(extern_stmt)) => match &extern_stmt.decl {
+                    ExternDecl::Fn(sig) => {
+                        let name: String = lit(&sig.name);
+                        let symbol: String = match has_str(sig) {
+                            true => String::from(lits::cgen::C_PREFIX) + &name,
+                            false => name.clone(),
+                        };
+                        lower.names.insert(name.clone(), symbol);
+                        lower.fns.insert(name, signature_types(sig));
+                    }
+                    ExternDecl::Const(param) => {
+                        let name: String = lit(&param.name);
+                        if param.ty.is_str() {
+                            lower.c_strs.insert(name.clone());
+                        }
+                        lower.names.insert(name.clone(), name);
+                        lower.declare(&param.name, Ty::of(&param.ty), Binding::Const);
+                    }
+                },
+                Stmt::Const(Some(const_stmt)) => {
+                    lower.at.set(const_stmt.name.pos);
+                    let ty: Ty = lower.const_type(const_stmt);
+                    lower.declare(&const_stmt.name, ty, Binding::Const);
+                }
+                _ => {}
+            }
+        }
+        lower
+    }
+
+    /// Generate the C file in the same order as the C++ one.
+    fn script(&mut self, script: &[Stmt], target: &Path) -> String {
+        let mut c: String = String::new();
+        if self.ctx.profile.bounds_checks {
+            c.push_str(&format!("#define {}\n", lits::cgen::BOUNDS_CHECKS));
+        }
+        c.push_str(&format!("#include \"{}\"\n\n", lits::cgen::C_RUNTIME));
+        let prelude: usize = c.len();
+
+        // Headers and functions of the C files of `src/` and the `cpp` blocks (C code).
+        let mut externs: bool = false;
+        for stmt in script {
+            let mut item: String = String::new();
+            match stmt {
+                Stmt::Include(Some(include)) => include.cgen_c(&mut item, target),
+                Stmt::Extern(Some(extern_stmt)) => match &extern_stmt.decl {
+                    ExternDecl::Fn(sig) if extern_stmt.abi == Abi::Cpp => self.unsupported(
+                        "'extern \"cpp\"' functions, they have C++ linkage",
+                        Some(&sig.name.pos),
+                    ),
+                    ExternDecl::Fn(sig) => self.extern_fn(&mut item, sig),
+                    ExternDecl::Const(_) => continue,
+                },
+                Stmt::Cpp(Some(cpp_stmt)) => {
+                    // The block is before the prototypes, it declares the functions it calls.
+                    for stmt in script {
+                        if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                            if cpp_stmt.calls(fn_stmt) {
+                                self.signature(&mut item, fn_stmt);
+                                item.push_str(";\n");
+                            }
+                        }
+                    }
+                    cpp_stmt.cgen(&mut item, self.ctx, 0)
+                }
+                _ => continue,
+            }
+            self.item(&mut c, &item);
+            externs = true;
+        }
+        if externs {
+            c.push('\n');
+        }
+
+        // Global constants, before everything that can use them.
+        let mut constants: bool = false;
+        for stmt in script {
+            if let Stmt::Const(Some(const_stmt)) = stmt {
+                let mut item: String = String::new();
+                self.constant(&mut item, const_stmt, 0);
+                self.item(&mut c, &item);
+                constants = true;
+            }
+        }
+        if constants {
+            c.push('\n');
+        }
+
+        // Prototypes, so the functions can be called regardless of their order.
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                if !fn_stmt.is_main() {
+                    let mut item: String = String::new();
+                    self.signature(&mut item, fn_stmt);
+                    item.push_str(";\n");
+                    self.item(&mut c, &item);
+                }
+            }
+        }
+
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                let mut item: String = String::from("\n");
+                self.function(&mut item, fn_stmt);
+                self.item(&mut c, &item);
+            }
+        }
+
+        // Wrappers of the exported functions, with their names in C.
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                if fn_stmt.export {
+                    c.push('\n');
+                    self.export(&mut c, fn_stmt);
+                }
+            }
+        }
+
+        // Interfaces of the modules of the dependencies named by the functions.
+        let mut imports: String = String::new();
+        self.ctx.cgen_imports(&mut imports);
+        c.insert_str(prelude, &imports);
+        c
+    }
+    /// Write the code of a declaration after the definitions of the types it uses.
+    fn item(&self, c: &mut String, item: &str) {
+        c.push_str(&self.types.borrow_mut().take());
+        c.push_str(item);
+    }
+
+    /// Stop at a construction that C cannot generate.
+    fn unsupported(&self, what: &str, pos: Option<&Pos>) -> ! {
+        self.diagnose(&format!("the C backend does not support {}", what), pos)
+    }
+    /// Launch an error diagnostic, at the statement being generated without position.
+    fn diagnose(&self, msg: &str, pos: Option<&Pos>) -> ! {
+        let pos: Pos = match pos {
+            Some(pos) => *pos,
+            None => self.at.get(),
+        };
+        self.ctx.diagnosis.diagnosis_file(msg, pos)
+    }
+
+    /// Constant, variable or parameter in the scopes.
+    fn lookup(&self, name: &str) -> Option<Local> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.names.get(name).cloned())
+    }
+    /// Is the name declared at global scope (not shadowed)?
+    fn is_global(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.names.contains_key(name))
+            == Some(0)
+    }
+    /// Add the name to the innermost scope.
+    fn declare(&mut self, name: &Token, ty: Ty, binding: Binding) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.names.insert(lit(name), Local { ty, binding });
+        }
+    }
+    /// Type of the constant, the untyped constants have the type of their literal.
+    fn const_type(&self, const_stmt: &Const) -> Ty {
+        match &const_stmt.ty {
+            Some(ty) => Ty::of(ty),
+            None => self.expect_type(&const_stmt.value),
+        }
+    }
+
+    /// C type of the language type, the generic types are defined by the next
+    /// [`Lower::item`].
+    fn ty(&self, ty: &Ty) -> String {
+        if let Ty::Array(_, 0) = ty {
+            self.unsupported("arrays without items", None);
+        }
+        self.types.borrow_mut().define(ty);
+        ty.c()
+    }
+    /// Generate the C type of the `extern "c"` functions (`str` is `const char *`).
+    fn c_ty(&self, c: &mut String, ty: Option<&Type>) {
+        match ty {
+            Some(ty) if ty.is_str() => c.push_str("const char *"),
+            Some(ty) => {
+                c.push_str(&self.ty(&Ty::of(ty)));
+                c.push(' ');
+            }
+            None => c.push_str("void "),
+        }
+    }
+
+    /// Generate the name in C (see [`Lower::names`]).
+    fn name(&self, c: &mut String, name: &Token) {
+        let lit: String = lit(name);
+        match self.names.get(&lit) {
+            Some(name) => c.push_str(name),
+            None => c.push_str(&escape(&lit)),
+        }
+    }
+    /// Generate the C function signature (`type name(type arg1, type arg2, ...)`).
+    fn signature(&self, c: &mut String, fn_stmt: &Fn) {
+        if fn_stmt.is_main() {
+            return c.push_str("int main(void)");
+        }
+        match &fn_stmt.ret {
+            Some(ret) => c.push_str(&self.ty(&Ty::of(ret))),
+            None => c.push_str("void"),
+        }
+        c.push(' ');
+        self.name(c, &fn_stmt.name);
+        c.push('(');
+        if fn_stmt.params.is_empty() {
+            c.push_str("void");
+        }
+        for (i, param) in fn_stmt.params.iter().enumerate() {
+            if i > 0 {
+                c.push_str(", ");
+            }
+            c.push_str(&self.ty(&Ty::of(&param.ty)));
+            c.push(' ');
+            push_id(c, &param.name);
+        }
+        c.push(')');
+    }
+    /// Generate the C function between `#line` directives.
+    fn function(&mut self, c: &mut String, fn_stmt: &Fn) {
+        self.ctx.cgen_line(c, &fn_stmt.name.pos);
+        self.at.set(fn_stmt.name.pos);
+        self.signature(c, fn_stmt);
+        c.push(' ');
+        self.fn_scope = self.scopes.len();
+        self.ret = fn_stmt.ret.as_ref().map(Ty::of);
+        self.main = fn_stmt.is_main();
+        let params: Scope = Scope {
+            names: fn_stmt
+                .params
+                .iter()
+                .map(|param| {
+                    let local: Local = Local {
+                        ty: Ty::of(&param.ty),
+                        binding: Binding::Var,
+                    };
+                    (lit(&param.name), local)
+                })
+                .collect(),
+            ..Scope::default()
+        };
+        self.body(c, &fn_stmt.body, 0, params, "");
+        c.push_str(lits::cgen::LINE_RESTORE);
+        c.push('\n');
+    }
+
+    /// Generate the declaration of the `extern "c"` function and, with strings, its
+    /// wrapper with the types of the language.
+    fn extern_fn(&self, c: &mut String, sig: &Fn) {
+        // `type name(type arg1, type arg2, ...);`
+        self.c_ty(c, sig.ret.as_ref());
+        push_lit(c, &sig.name);
+        c.push('(');
+        if sig.params.is_empty() {
+            c.push_str("void");
+        }
+        for (i, param) in sig.params.iter().enumerate() {
+            if i > 0 {
+                c.push_str(", ");
+            }
+            self.c_ty(c, Some(&param.ty));
+            push_id(c, &param.name);
+        }
+        c.push_str(");\n");
+        if !has_str(sig) {
+            return;
+        }
+
+        // `static inline type torch_c_name(torch_str arg1, ...) { return ...; }`
+        c.push_str("static inline ");
+        self.signature(c, sig);
+        c.push_str(" {\n    ");
+        let ret: bool = sig.ret.as_ref().is_some_and(Type::is_str);
+        if sig.ret.is_some() {
+            c.push_str("return ");
+        }
+        if ret {
+            c.push_str("torch_str_from_c(");
+        }
+        push_lit(c, &sig.name);
+        c.push('(');
+        for (i, param) in sig.params.iter().enumerate() {
+            if i > 0 {
+                c.push_str(", ");
+            }
+            if param.ty.is_str() {
+                c.push_str("torch_str_c(");
+                push_id(c, &param.name);
+                c.push(')');
+            } else {
+                push_id(c, &param.name);
+            }
+        }
+        c.push(')');
+        if ret {
+            c.push(')');
+        }
+        c.push_str(";\n}\n");
+    }
+    /// Generate the wrapper of the exported function with the C types of its header.
+    ///
+    /// ---
+    /// > _The returned strings are copies allocated with `malloc`._
+    fn export(&self, c: &mut String, fn_stmt: &Fn) {
+        // `type name(type arg1, type arg2, ...) {`
+        let ret: bool = fn_stmt.ret.as_ref().is_some_and(Type::is_str);
+        if ret {
+            c.push_str("char *");
+        } else {
+            self.c_ty(c, fn_stmt.ret.as_ref());
+        }
+        push_lit(c, &fn_stmt.name);
+        c.push('(');
+        if fn_stmt.params.is_empty() {
+            c.push_str("void");
+        }
+        for (i, param) in fn_stmt.params.iter().enumerate() {
+            if i > 0 {
+                c.push_str(", ");
+            }
+            self.c_ty(c, Some(&param.ty));
+            push_id(c, &param.name);
+        }
+
+        // `return torch_fn_name(arg1, arg2, ...);`
+        c.push_str(") {\n    ");
+        if fn_stmt.ret.is_some() {
+            c.push_str("return ");
+        }
+        if ret {
+            c.push_str("torch_str_to_c(");
+        }
+        self.name(c, &fn_stmt.name);
+        c.push('(');
+        for (i, param) in fn_stmt.params.iter().enumerate() {
+            if i > 0 {
+                c.push_str(", ");
+            }
+            if param.ty.is_str() {
+                c.push_str("torch_str_from_c(");
+                push_id(c, &param.name);
+                c.push(')');
+            } else {
+                push_id(c, &param.name);
+            }
+        }
+        c.push(')');
+        if ret {
+            c.push(')');
+        }
+        c.push_str(";\n}\n");
+    }
+
+    /// Generate the body of a statement (`{...}`) in its scope, the `prelude` are its
+    /// first lines.
+    ///
+    /// ---
+    /// > _The deferred code of the scope is generated at its end, unless the body leaves
+    /// > it before (`return`, `break` and `continue` generate it themselves)._
+    fn body(&mut self, c: &mut String, stmts: &[Stmt], depth: usize, scope: Scope, prelude: &str) {
+        c.push_str("{\n");
+        c.push_str(prelude);
+        self.scopes.push(scope);
+        for stmt in stmts {
+            self.stmt(c, stmt, depth + 1);
+        }
+        if !matches!(
+            stmts.last(),
+            Some(Stmt::Return(_) | Stmt::Break(_) | Stmt::Continue(_))
+        ) {
+            self.unwind(c, self.scopes.len() - 1, depth + 1);
+        }
+        self.scopes.pop();
+        indent(c, depth);
+        c.push_str("}\n");
+    }
+    /// Generate the deferred code of the scopes from the innermost one to `outer`, in
+    /// reverse order of their `defer` statements. Is there any?
+    ///
+    /// ---
+    /// > _The code sees the names of the scope of its `defer`, C cannot see them if a
+    /// > declaration of the inner scopes shadows them, it is diagnosed._
+    fn unwind(&mut self, c: &mut String, outer: usize, depth: usize) -> bool {
+        let mut any: bool = false;
+        for level in (outer..self.scopes.len()).rev() {
+            let defers: Vec<Defer> = self.scopes[level].defers.clone();
+            for defer in defers.iter().rev() {
+                let inner: Vec<Scope> = self.scopes.split_off(level + 1);
+                let mut names: HashSet<String> = HashSet::new();
+                stmts_names(&defer.body, &mut names);
+                if let Some(name) = inner
+                    .iter()
+                    .flat_map(|scope| scope.names.keys())
+                    .find(|name| names.contains(*name))
+                {
+                    self.unsupported(
+                        &format!("deferred code that names '{}' where it is shadowed", name),
+                        Some(&defer.pos),
+                    );
+                }
+                self.ctx.cgen_line(c, &defer.pos);
+                indent(c, depth);
+                self.body(c, &defer.body, depth, Scope::default(), "");
+                self.scopes.extend(inner);
+                any = true;
+            }
+        }
+        any
+    }
+    /// Generate the C code of the statements of a body.
+    fn stmt(&mut self, c: &mut String, stmt: &Stmt, depth: usize) {
+        if let Some(pos) = stmt.pos() {
+            self.ctx.cgen_line(c, pos);
+            self.at.set(*pos);
+        }
+        match stmt {
+            Stmt::Fn(Some(fn_stmt)) => {
+                self.unsupported("nested functions", Some(&fn_stmt.name.pos))
+            }
+            Stmt::Var(Some(var)) => self.var(c, var, depth),
+            Stmt::Const(Some(const_stmt)) => self.constant(c, const_stmt, depth),
+            Stmt::Assign(Some(assign)) => {
+                self.tries(c, &assign.target, depth);
+                self.tries(c, &assign.value, depth);
+                if let Expr::Index(object, _, pos) = &assign.target {
+                    if self
+                        .type_of(object)
+                        .is_some_and(|ty| ty.is(lits::types::STR))
+                    {
+                        self.unsupported("assignments to the bytes of the strings", Some(pos));
+                    }
+                }
+                let ty: Ty = self.expect_type(&assign.target);
+                indent(c, depth);
+                self.expr(c, &assign.target);
+                c.push_str(" = ");
+                self.value(c, &assign.value, &ty);
+                c.push_str(";\n");
+            }
+            Stmt::Expr(Some(expr)) => {
+                self.tries(c, expr, depth);
+                // `expr?` alone is only its propagation.
+                if let Expr::Try(..) = expr {
+                    return;
+                }
+                indent(c, depth);
+                self.expr(c, expr);
+                c.push_str(";\n");
+            }
+            Stmt::For(Some(for_stmt)) => self.for_stmt(c, for_stmt, depth),
+            Stmt::Return(Some(return_stmt)) => self.return_stmt(c, return_stmt, depth),
+            Stmt::Break(Some(token)) | Stmt::Continue(Some(token)) => {
+                let outer: usize = self
+                    .scopes
+                    .iter()
+                    .rposition(|scope| scope.is_loop)
+                    .unwrap_or(self.fn_scope);
+                if self.unwind(c, outer, depth) {
+                    self.ctx.cgen_line(c, &token.pos);
+                }
+                indent(c, depth);
+                c.push_str(match stmt {
+                    Stmt::Break(_) => "break;\n",
+                    _ => "continue;\n",
+                });
+            }
+            Stmt::Defer(Some(defer)) => {
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.defers.push(defer.clone());
+                }
+            }
+            Stmt::Cpp(Some(cpp_stmt)) => {
+                // The names in scope of the block, the `extern` constants are not escaped
+                // and the items of the loops are pointers.
+                let scope: Vec<(String, String)> = self
+                    .scopes
+                    .iter()
+                    .flat_map(|scope| scope.names.iter())
+                    .filter(|(name, _)| !self.names.contains_key(*name))
+                    .map(|(name, local)| match local.binding {
+                        Binding::Item => (name.clone(), format!("(*{})", escape(name))),
+                        _ => (name.clone(), escape(name)),
+                    })
+                    .collect();
+                self.ctx.scope.replace(scope);
+                cpp_stmt.cgen(c, self.ctx, depth)
+            }
+            _ => {}
+        }
+    }
+    /// Generate the C variable (`type name = expr;`).
+    fn var(&mut self, c: &mut String, var: &Var, depth: usize) {
+        if let Some(value) = &var.value {
+            self.tries(c, value, depth);
+        }
+        let ty: Ty = match (&var.ty, &var.value) {
+            (Some(ty), _) => Ty::of(ty),
+            (None, Some(value)) => self.expect_type(value),
+            (None, None) => self.diagnose(
+                "the C backend does not know the type of the variable, declare it",
+                Some(&var.name.pos),
+            ),
+        };
+        indent(c, depth);
+        c.push_str(&self.ty(&ty));
+        c.push(' ');
+        push_id(c, &var.name);
+        match &var.value {
+            Some(value) => {
+                c.push_str(" = ");
+                self.value(c, value, &ty);
+            }
+            None => c.push_str(" = {0}"),
+        }
+        c.push_str(";\n");
+        self.declare(&var.name, ty, Binding::Var);
+    }
+    /// Generate the C constant (`static const type NAME = lit;` at global scope).
+    fn constant(&mut self, c: &mut String, const_stmt: &Const, depth: usize) {
+        let ty: Ty = self.const_type(const_stmt);
+        indent(c, depth);
+        if depth == 0 {
+            c.push_str("static ");
+        }
+        c.push_str("const ");
+        c.push_str(&self.ty(&ty));
+        c.push(' ');
+        push_id(c, &const_stmt.name);
+        c.push_str(" = ");
+        match (&const_stmt.value, &ty) {
+            // The global initializers are constant expressions, not compound literals.
+            (Expr::Lit(token), _) if depth == 0 && ty.is(lits::types::STR) => {
+                c.push_str("TORCH_STR_INIT(");
+                push_lit(c, token);
+                c.push(')');
+            }
+            (Expr::Array(items), Ty::Array(item, _)) if depth == 0 => {
+                c.push('{');
+                self.items(c, items, item);
+                c.push('}');
+            }
+            (_, Ty::Id(_)) => self.value(c, &const_stmt.value, &ty),
+            (value, _) if depth == 0 => {
+                self.unsupported(&format!("global constants of '{}'", ty), value.pos())
+            }
+            (value, _) => self.value(c, value, &ty),
+        }
+        c.push_str(";\n");
+        self.declare(&const_stmt.name, ty, Binding::Const);
+    }
+    /// Generate the C loop over a range or the items of a collection.
+    ///
+    /// ---
+    /// `for (int64_t i = lo, torch_end_line_grapheme = hi; i < ...; ++i)`<br>
+    /// `{ S torch_iter_line_grapheme = items; for (int64_t torch_index_... = 0; ...) {
+    /// T *item = &torch_iter_...[torch_index_...]; ... } }`
+    fn for_stmt(&mut self, c: &mut String, for_stmt: &For, depth: usize) {
+        self.tries(c, &for_stmt.iter, depth);
+        let name: String = lit(&for_stmt.name);
+        let id: String = escape(&name);
+        let suffix: String = format!("{}_{}", for_stmt.name.pos.line, for_stmt.name.pos.grapheme);
+        if let Expr::Range(lo, hi) = &for_stmt.iter {
+            let end: String = format!("{}{}", lits::cgen::END_PREFIX, suffix);
+            indent(c, depth);
+            c.push_str(&format!("for (int64_t {} = ", id));
+            self.expr(c, lo);
+            c.push_str(&format!(", {} = ", end));
+            self.expr(c, hi);
+            c.push_str(&format!("; {} < {}; ++{}) ", id, end, id));
+            let scope: Scope = Scope {
+                names: HashMap::from([(
+                    name,
+                    Local {
+                        ty: Ty::id(lits::types::I64),
+                        binding: Binding::Var,
+                    },
+                )]),
+                is_loop: true,
+                ..Scope::default()
+            };
+            return self.body(c, &for_stmt.body, depth, scope, "");
+        }
+
+        let ty: Ty = self.expect_type(&for_stmt.iter);
+        let Some(item) = ty.item().cloned() else {
+            self.unsupported(&format!("loops over '{}'", ty), for_stmt.iter.pos())
+        };
+        let iter: String = format!("{}{}", lits::cgen::ITER_PREFIX, suffix);
+        let i: String = format!("{}{}", lits::cgen::INDEX_PREFIX, suffix);
+        indent(c, depth);
+        c.push_str("{\n");
+        indent(c, depth + 1);
+        c.push_str(&format!(
+            "{} {} = ",
+            self.ty(&Ty::Slice(Box::new(item.clone()))),
+            iter
+        ));
+        self.slice(c, &for_stmt.iter, &ty);
+        c.push_str(";\n");
+        indent(c, depth + 1);
+        c.push_str(&format!(
+            "for (int64_t {} = 0; {} < {}.len; ++{}) ",
+            i, i, iter, i
+        ));
+        let mut prelude: String = String::new();
+        indent(&mut prelude, depth + 2);
+        prelude.push_str(&format!(
+            "{} *{} = &{}.ptr[{}];\n",
+            self.ty(&item),
+            id,
+            iter,
+            i
+        ));
+        let scope: Scope = Scope {
+            names: HashMap::from([(
+                name,
+                Local {
+                    ty: item,
+                    binding: Binding::Item,
+                },
+            )]),
+            is_loop: true,
+            ..Scope::default()
+        };
+        self.body(c, &for_stmt.body, depth + 1, scope, &prelude);
+        indent(c, depth);
+        c.push_str("}\n");
+    }
+    /// Generate the C return, the value is computed before the deferred code runs
+    /// (`{ T torch_ret = expr; ...; return torch_ret; }`).
+    fn return_stmt(&mut self, c: &mut String, return_stmt: &Return, depth: usize) {
+        if let Some(value) = &return_stmt.value {
+            self.tries(c, value, depth);
+        }
+        let deferred: bool = self.scopes[self.fn_scope..]
+            .iter()
+            .any(|scope| !scope.defers.is_empty());
+        match (&return_stmt.value, self.ret.clone()) {
+            (Some(value), Some(ret)) if deferred => {
+                indent(c, depth);
+                c.push_str("{\n");
+                indent(c, depth + 1);
+                c.push_str(&format!("{} {} = ", self.ty(&ret), lits::cgen::RET));
+                self.value(c, value, &ret);
+                c.push_str(";\n");
+                self.unwind(c, self.fn_scope, depth + 1);
+                self.ctx.cgen_line(c, &return_stmt.pos);
+                indent(c, depth + 1);
+                c.push_str(&format!("return {};\n", lits::cgen::RET));
+                indent(c, depth);
+                c.push_str("}\n");
+            }
+            (value, ret) => {
+                if self.unwind(c, self.fn_scope, depth) {
+                    self.ctx.cgen_line(c, &return_stmt.pos);
+                }
+                indent(c, depth);
+                c.push_str("return");
+                match (value, ret) {
+                    (Some(value), Some(ret)) => {
+                        c.push(' ');
+                        self.value(c, value, &ret);
+                    }
+                    (Some(value), None) => {
+                        c.push(' ');
+                        self.expr(c, value);
+                    }
+                    // The exit status of `main`.
+                    (None, _) if self.main => c.push_str(" 0"),
+                    (None, _) => {}
+                }
+                c.push_str(";\n");
+            }
+        }
+    }
+}
+
+/// Literal of the token.
+fn lit(token: &Token) -> String {
+    match token.lit() {
+        Some(lit) => format!("{}", lit),
+        None => String::from(lits::cgen::DEFAULT_ID),
+    }
+}
+/// Does the signature have strings, which C receives and returns as `char *`?
+fn has_str(fn_stmt: &Fn) -> bool {
+    fn_stmt.ret.as_ref().is_some_and(Type::is_str)
+        || fn_stmt.params.iter().any(|param| param.ty.is_str())
+}
+/// Types of the parameters and the return of the function.
+fn signature_types(fn_stmt: &Fn) -> (Vec<Ty>, Option<Ty>) {
+    (
+        fn_stmt
+            .params
+            .iter()
+            .map(|param| Ty::of(&param.ty))
+            .collect(),
+        fn_stmt.ret.as_ref().map(Ty::of),
+    )
+}
+/// Names of the identifiers of the statements.
+fn stmts_names(stmts: &[Stmt], names: &mut HashSet<String>) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Var(Some(var)) => {
+                if let Some(value) = &var.value {
+                    expr_names(value, names);
+                }
+            }
+            Stmt::Const(Some(const_stmt)) => expr_names(&const_stmt.value, names),
+            Stmt::Assign(Some(assign)) => {
+                expr_names(&assign.target, names);
+                expr_names(&assign.value, names);
+            }
+            Stmt::Expr(Some(expr)) => expr_names(expr, names),
+            Stmt::For(Some(for_stmt)) => {
+                expr_names(&for_stmt.iter, names);
+                stmts_names(&for_stmt.body, names);
+            }
+            Stmt::Return(Some(Return {
+                value: Some(value), ..
+            })) => expr_names(value, names),
+            Stmt::Defer(Some(defer)) => stmts_names(&defer.body, names),
+            _ => {}
+        }
+    }
+}
+/// Names of the identifiers of the expression.
+fn expr_names(expr: &Expr, names: &mut HashSet<String>) {
+    match expr {
+        Expr::Lit(_) => {}
+        Expr::Id(id) => {
+            names.insert(lit(id));
+        }
+        Expr::Array(items) | Expr::Format(items) => {
+            for item in items {
+                expr_names(item, names);
+            }
+        }
+        Expr::Unary(_, expr) | Expr::Member(expr, _) | Expr::Try(expr, _) => {
+            expr_names(expr, names)
+        }
+        Expr::Binary(lhs, _, rhs) | Expr::Range(lhs, rhs) | Expr::Index(lhs, rhs, _) => {
+            expr_names(lhs, names);
+            expr_names(rhs, names);
+        }
+        Expr::Call(callee, args) => {
+            expr_names(callee, names);
+            for arg in args {
+                expr_names(arg, names);
+            }
+        }
+        Expr::Slice(expr, lo, hi, _) => {
+            expr_names(expr, names);
+            for bound in [lo, hi].into_iter().flatten() {
+                expr_names(bound, names);
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use super::lit;
+use crate::cgen::Type;
+use std::{collections::HashSet, fmt};
+use torchc_lits::lits;
+
+/// Types of the values in C.
+///
+/// ---
+/// > _The generic types are structs generated for each of their instantiations
+/// > (`list[i32]` -> `torch_list_i32`), with the helpers that the C++ runtime has as
+/// > methods (`torch_list_i32_push`, ...)._
+#[derive(Debug, Clone, PartialEq)]
+#[repr(u8)]
+pub enum Ty {
+    /// `i32`, `str`, ... or a type of the headers (`FILE`).
+    Id(String),
+    /// `[n]type`
+    Array(Box<Self>, usize),
+    /// `[]type`
+    Slice(Box<Self>),
+    /// `list[type]`
+    List(Box<Self>),
+    /// `result[type, type]`
+    Result(Box<Self>, Box<Self>),
+    /// `option[type]`
+    Option(Box<Self>),
+}
+impl Ty {
+    pub fn of(ty: &Type) -> Self {
+        match ty {
+            Type::Id(id) => Self::Id(lit(id)),
+            Type::Array(ty, len) => Self::Array(Box::new(Self::of(ty)), *len),
+            Type::Slice(ty) => Self::Slice(Box::new(Self::of(ty))),
+            Type::List(ty) => Self::List(Box::new(Self::of(ty))),
+            Type::Result(value, error) => {
+                Self::Result(Box::new(Self::of(value)), Box::new(Self::of(error)))
+            }
+            Type::Option(ty) => Self::Option(Box::new(Self::of(ty))),
+        }
+    }
+    pub fn id(id: &str) -> Self {
+        Self::Id(String::from(id))
+    }
+    /// Is it the built-in type (`str`, `bool`, ...)?
+    pub fn is(&self, id: &str) -> bool {
+        matches!(self, Self::Id(name) if name == id)
+    }
+    /// Type of the items of the collections.
+    pub fn item(&self) -> Option<&Self> {
+        match self {
+            Self::Array(item, _) | Self::Slice(item) | Self::List(item) => Some(item),
+            _ => None,
+        }
+    }
+    /// Is it a generic type (a struct of [`Types`])?
+    pub fn is_generic(&self) -> bool {
+        !matches!(self, Self::Id(_))
+    }
+
+    /// Type of the arithmetic of the numbers, with the conversions of C (`i8 + i8` is
+    /// `int`, `i32 + u32` is `unsigned`, ...).
+    pub fn arithmetic(lhs: Self, rhs: Self) -> Self {
+        let (Some((l_bits, l_float, l_signed)), Some((r_bits, r_float, r_signed))) =
+            (lhs.number(), rhs.number())
+        else {
+            return lhs;
+        };
+        if l_float || r_float {
+            return match (l_float && l_bits == 64) || (r_float && r_bits == 64) {
+                true => Self::id(lits::types::F64),
+                false => Self::id(lits::types::F32),
+            };
+        }
+        let bits: u8 = l_bits.max(r_bits).max(32);
+        let signed: bool = match l_bits.cmp(&r_bits) {
+            _ if l_bits.max(r_bits) < 32 => true,
+            std::cmp::Ordering::Less => r_signed,
+            std::cmp::Ordering::Greater => l_signed,
+            std::cmp::Ordering::Equal => l_signed && r_signed,
+        };
+        Self::id(match (bits, signed) {
+            (32, true) => lits::types::I32,
+            (32, false) => lits::types::U32,
+            (_, true) => lits::types::I64,
+            (_, false) => lits::types::U64,
+        })
+    }
+    /// Bits, is it a float and is it signed; `None` if it is not a number.
+    fn number(&self) -> Option<(u8, bool, bool)> {
+        let Self::Id(id) = self else {
+            return None;
+        };
+        Some(match id.as_str() {
+            lits::types::I8 => (8, false, true),
+            lits::types::I16 => (16, false, true),
+            lits::types::I32 => (32, false, true),
+            lits::types::I64 => (64, false, true),
+            lits::types::U8 => (8, false, false),
+            lits::types::U16 => (16, false, false),
+            lits::types::U32 => (32, false, false),
+            lits::types::U64 => (64, false, false),
+            lits::types::F32 => (32, true, true),
+            lits::types::F64 => (64, true, true),
+            _ => return None,
+        })
+    }
+
+    /// C type (`int32_t`, `torch_str`, `torch_list_i32`, ...).
+    pub fn c(&self) -> String {
+        let Self::Id(id) = self else {
+            return format!("{}_{}", lits::cgen::NAMESPACE, self.name());
+        };
+        String::from(match id.as_str() {
+            lits::types::I8 => "int8_t",
+            lits::types::I16 => "int16_t",
+            lits::types::I32 => "int32_t",
+            lits::types::I64 => "int64_t",
+            lits::types::U8 => "uint8_t",
+            lits::types::U16 => "uint16_t",
+            lits::types::U32 => "uint32_t",
+            lits::types::U64 => "uint64_t",
+            lits::types::F32 => "float",
+            lits::types::F64 => "double",
+            lits::types::BOOL => "bool",
+            // `char32_t` of `<uchar.h>`.
+            lits::types::CHAR => "uint_least32_t",
+            lits::types::STR => "torch_str",
+            id => id,
+        })
+    }
+    /// Name of the instantiation (`list_i32`, `result_array_3_i32_str`).
+    fn name(&self) -> String {
+        match self {
+            Self::Id(id) => id.clone(),
+            Self::Array(item, len) => format!("array_{}_{}", len, item.name()),
+            Self::Slice(item) => format!("slice_{}", item.name()),
+            Self::List(item) => format!("{}_{}", lits::types::LIST, item.name()),
+            Self::Result(value, error) => {
+                format!("{}_{}_{}", lits::types::RESULT, value.name(), error.name())
+            }
+            Self::Option(value) => format!("{}_{}", lits::types::OPTION, value.name()),
+        }
+    }
+    /// Are its items copied with it? The lists are values as the vectors of C++, their
+    /// copies (`var b = a`, arguments, ...) do not share the items.
+    pub fn is_deep(&self) -> bool {
+        match self {
+            Self::Id(_) | Self::Slice(_) => false,
+            Self::List(_) => true,
+            Self::Array(item, _) | Self::Option(item) => item.is_deep(),
+            Self::Result(value, error) => value.is_deep() || error.is_deep(),
+        }
+    }
+    /// C expression of the copy of the value (see [`Ty::is_deep`]).
+    fn copy(&self, value: &str) -> String {
+        match self.is_deep() {
+            true => format!("{}_copy({})", self.c(), value),
+            false => String::from(value),
+        }
+    }
+}
+/// The type as it is written in the scripts (`list[i32]`).
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "{}", id),
+            Self::Array(item, len) => write!(f, "[{}]{}", len, item),
+            Self::Slice(item) => write!(f, "[]{}", item),
+            Self::List(item) => write!(f, "{}[{}]", lits::types::LIST, item),
+            Self::Result(value, error) => {
+                write!(f, "{}[{}, {}]", lits::types::RESULT, value, error)
+            }
+            Self::Option(value) => write!(f, "{}[{}]", lits::types::OPTION, value),
+        }
+    }
+}
+
+/// Definitions of the instantiations of the generic types.
+///
+/// ---
+/// > _They are written before the code that uses them the first time, between guards
+/// > since the interfaces of the modules define them again._
+#[derive(Debug, Default)]
+pub struct Types {
+    defined: HashSet<String>,
+    /// Definitions not written yet.
+    pending: String,
+}
+impl Types {
+    /// Define the instantiation of the type, after the ones of its items.
+    pub fn define(&mut self, ty: &Ty) {
+        match ty {
+            Ty::Id(_) => return,
+            Ty::Array(item, _) | Ty::List(item) => {
+                self.define(item);
+                self.define(&Ty::Slice(item.clone()));
+            }
+            Ty::Slice(item) | Ty::Option(item) => self.define(item),
+            Ty::Result(value, error) => {
+                self.define(value);
+                self.define(error);
+            }
+        }
+        let name: String = ty.name();
+        if !self.defined.insert(name.clone()) {
+            return;
+        }
+        let c: String = ty.c();
+        let def: &mut String = &mut self.pending;
+        def.push_str(&format!(
+            "#ifndef TORCH_TYPE_{}\n#define TORCH_TYPE_{}\n",
+            name, name
+        ));
+        match ty {
+            Ty::Id(_) => {}
+            // `typedef struct { T *ptr; int64_t len; } S;` and `S_at`, `S_sub`, `S_tail`.
+            Ty::Slice(item) => {
+                let item: String = item.c();
+                def.push_str(&format!(
+                    "typedef struct {c} {{\n    {item} *ptr;\n    int64_t len;\n}} {c};\n\
+                     static inline {item} *{c}_at({c} self, int64_t i, torch_pos here) {{\n    \
+                     torch_bounds(i, self.len, here);\n    return &self.ptr[i];\n}}\n\
+                     static inline {c} {c}_sub({c} self, int64_t lo, int64_t hi, torch_pos here) {{\n    \
+                     torch_bounds_range(lo, hi, self.len, here);\n    \
+                     return ({c}){{lo ? self.ptr + lo : self.ptr, hi - lo}};\n}}\n\
+                     static inline {c} {c}_tail({c} self, int64_t lo, torch_pos here) {{\n    \
+                     return {c}_sub(self, lo, self.len, here);\n}}\n"
+                ));
+            }
+            // `typedef struct { T items[n]; } A;` and `A_slice`, `A_copy`.
+            Ty::Array(item, len) => {
+                let slice: String = Ty::Slice(item.clone()).c();
+                def.push_str(&format!(
+                    "typedef struct {c} {{\n    {} items[{len}];\n}} {c};\n\
+                     static inline {slice} {c}_slice({c} *self) {{\n    \
+                     return ({slice}){{self->items, {len}}};\n}}\n",
+                    item.c()
+                ));
+                if ty.is_deep() {
+                    def.push_str(&format!(
+                        "static inline {c} {c}_copy({c} self) {{\n    \
+                         for (int64_t i = 0; i < {len}; ++i) self.items[i] = {};\n    \
+                         return self;\n}}\n",
+                        item.copy("self.items[i]")
+                    ));
+                }
+            }
+            // `typedef struct { T *ptr; int64_t len; int64_t cap; } L;` and `L_slice`,
+            // `L_push`, `L_pop`, `L_clear`, `L_from`, `L_copy`.
+            Ty::List(item) => {
+                let slice: String = Ty::Slice(item.clone()).c();
+                let copy: String = item.copy("items.ptr[i]");
+                let item: String = item.c();
+                def.push_str(&format!(
+                    "typedef struct {c} {{\n    {item} *ptr;\n    int64_t len;\n    int64_t cap;\n}} {c};\n\
+                     static inline {slice} {c}_slice({c} self) {{\n    \
+                     return ({slice}){{self.ptr, self.len}};\n}}\n\
+                     static inline void {c}_push({c} *self, {item} item) {{\n    \
+                     if (self->len == self->cap) self->ptr = torch_grow(self->ptr, &self->cap, sizeof(*self->ptr));\n    \
+                     self->ptr[self->len++] = item;\n}}\n\
+                     static inline {item} {c}_pop({c} *self) {{\n    \
+                     if (!self->len) torch_panic(\"pop from an empty list\");\n    \
+                     return self->ptr[--self->len];\n}}\n\
+                     static inline void {c}_clear({c} *self) {{ self->len = 0; }}\n\
+                     static inline {c} {c}_from({slice} items) {{\n    \
+                     {c} self = {{0}};\n    \
+                     for (int64_t i = 0; i < items.len; ++i) {c}_push(&self, {copy});\n    \
+                     return self;\n}}\n\
+                     static inline {c} {c}_copy({c} self) {{ return {c}_from({c}_slice(self)); }}\n"
+                ));
+            }
+            // `typedef struct { bool ok; T value; E error; } R;` and `R_unwrap`,
+            // `R_unwrap_or`, `R_copy`.
+            Ty::Result(value, error) => {
+                def.push_str(&format!(
+                    "typedef struct {c} {{\n    bool {};\n    {} {};\n    {} {};\n}} {c};\n",
+                    lits::members::OK,
+                    value.c(),
+                    lits::members::VALUE,
+                    error.c(),
+                    lits::members::ERROR
+                ));
+                unwrap(def, &c, &value.c(), lits::members::OK, "an err result");
+                if ty.is_deep() {
+                    def.push_str(&format!(
+                        "static inline {c} {c}_copy({c} self) {{\n    \
+                         if (self.ok) self.value = {};\n    \
+                         else self.error = {};\n    return self;\n}}\n",
+                        value.copy("self.value"),
+                        error.copy("self.error")
+                    ));
+                }
+            }
+            // `typedef struct { bool some; T value; } O;` and `O_unwrap`, `O_unwrap_or`,
+            // `O_copy`.
+            Ty::Option(value) => {
+                def.push_str(&format!(
+                    "typedef struct {c} {{\n    bool {};\n    {} {};\n}} {c};\n",
+                    lits::members::SOME,
+                    value.c(),
+                    lits::members::VALUE
+                ));
+                unwrap(def, &c, &value.c(), lits::members::SOME, "a none option");
+                if ty.is_deep() {
+                    def.push_str(&format!(
+                        "static inline {c} {c}_copy({c} self) {{\n    \
+                         if (self.some) self.value = {};\n    return self;\n}}\n",
+                        value.copy("self.value")
+                    ));
+                }
+            }
+        }
+        def.push_str("#endif\n\n");
+    }
+    /// Take the definitions to write them.
+    pub fn take(&mut self) -> String {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Generate `unwrap` and `unwrap_or` of the results and options, `flag` is the field
+/// of the values.
+fn unwrap(def: &mut String, c: &str, value: &str, flag: &str, what: &str) {
+    def.push_str(&format!(
+        "static inline {value} {c}_unwrap({c} self) {{\n    \
+         if (!self.{flag}) torch_panic(\"unwrap of {what}\");\n    return self.value;\n}}\n\
+         static inline {value} {c}_unwrap_or({c} self, {value} other) {{\n    \
+         return self.{flag} ? self.value : other;\n}}\n"
+    ));
+}
@@ -0,0 +1,126 @@
+use super::{Emit, Runtime};
+use crate::{
+    cgen::{Ctx, Stmt},
+    unicode,
+};
+use std::path::Path;
+use torchc_lits::lits;
+
+/// C++ runtime of the language, written next to the generated code.
+const RUNTIME: &str = include_str!("../runtime/torch.hpp");
+
+/// _**C++ backend**_
+pub struct Cpp;
+impl Emit for Cpp {
+    fn extension(&self) -> &'static str {
+        lits::extensions::CPP
+    }
+    fn runtime(&self) -> Vec<Runtime> {
+        vec![
+            // The runtime is included by each generated file.
+            Runtime {
+                name: lits::cgen::RUNTIME,
+                contents: || String::from(RUNTIME),
+                once: false,
+            },
+            // The grapheme table only depends on the compiler, it is generated once.
+            Runtime {
+                name: lits::cgen::UNICODE,
+                contents: unicode::cgen,
+                once: true,
+            },
+        ]
+    }
+    fn emit(&self, script: &[Stmt], ctx: &Ctx, target: &Path) -> String {
+        let mut cpp: String = String::new();
+        if ctx.profile.bounds_checks {
+            cpp.push_str(&format!("#define {}\n", lits::cgen::BOUNDS_CHECKS));
+        }
+        cpp.push_str(&format!("#include \"{}\"\n\n", lits::cgen::RUNTIME));
+        let prelude: usize = cpp.len();
+
+        // Headers and functions of the C/C++ files of `src/` and the `cpp` blocks.
+        let mut externs: bool = false;
+        for stmt in script {
+            match stmt {
+                Stmt::Include(Some(include)) => include.cgen(&mut cpp, target),
+                Stmt::Extern(Some(extern_stmt)) => extern_stmt.cgen(&mut cpp),
+                Stmt::Cpp(Some(cpp_stmt)) => {
+                    // The block is before the prototypes, it declares the functions it calls.
+                    for stmt in script {
+                        if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                            if cpp_stmt.calls(fn_stmt) {
+                                fn_stmt.cgen_signature(&mut cpp, &fn_stmt.symbol(ctx.module));
+                                cpp.push_str(";\n");
+                            }
+                        }
+                    }
+                    cpp_stmt.cgen(&mut cpp, ctx, 0)
+                }
+                _ => continue,
+            }
+            externs = true;
+        }
+        if externs {
+            cpp.push('\n');
+        }
+
+        // Global constants, before everything that can use them.
+        let mut constants: bool = false;
+        for stmt in script {
+            if let Stmt::Const(Some(const_stmt)) = stmt {
+                const_stmt.cgen(&mut cpp, ctx, 0);
+                constants = true;
+            }
+        }
+        if constants {
+            cpp.push('\n');
+        }
+
+        // Prototypes, so the functions can be called regardless of their order.
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                if !fn_stmt.is_main() {
+                    fn_stmt.cgen_signature(&mut cpp, &fn_stmt.symbol(ctx.module));
+                    cpp.push_str(";\n");
+                }
+            }
+        }
+
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                cpp.push('\n');
+                fn_stmt.cgen(&mut cpp, ctx);
+            }
+        }
+
+        // C wrappers of the exported functions.
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                if fn_stmt.export {
+                    cpp.push('\n');
+                    fn_stmt.cgen_export(&mut cpp, ctx.module);
+                }
+            }
+        }
+
+        // Interfaces of the modules of the dependencies named by the functions.
+        let mut imports: String = String::new();
+        ctx.cgen_imports(&mut imports);
+        cpp.insert_str(prelude, &imports);
+        cpp
+    }
+    fn interface(&self, script: &[Stmt], ctx: &Ctx) -> String {
+        // It is included after the runtime.
+        let mut cpp: String = String::from("#pragma once\n\n");
+        for stmt in script {
+            if let Stmt::Fn(Some(fn_stmt)) = stmt {
+                if !fn_stmt.is_main() {
+                    fn_stmt.cgen_signature(&mut cpp, &fn_stmt.symbol(ctx.module));
+                    cpp.push_str(";\n");
+                }
+            }
+        }
+        cpp
+    }
+}
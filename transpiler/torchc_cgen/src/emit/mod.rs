@@ -0,0 +1,38 @@
+use crate::cgen::{Backend, Ctx, Stmt};
+use std::path::Path;
+
+mod c;
+mod cpp;
+
+/// File of the runtime written in the target folder next to the generated code.
+pub struct Runtime {
+    pub name: &'static str,
+    pub contents: fn() -> String,
+    /// It only depends on the compiler, it is written once.
+    pub once: bool,
+}
+
+/// Code emitter of a backend.
+///
+/// ---
+/// > _The front end (parser, checks and evaluations) is shared, the emitters only
+/// > lower the **cgen data** to their language._
+pub trait Emit {
+    /// Extension of the generated files.
+    fn extension(&self) -> &'static str;
+    /// Files of the runtime included by the generated files.
+    fn runtime(&self) -> Vec<Runtime>;
+    /// Generate the code of the script, with the C wrappers of its exported functions.
+    fn emit(&self, script: &[Stmt], ctx: &Ctx, target: &Path) -> String;
+    /// Generate the interface of the module, the prototypes of its functions for the
+    /// modules of its dependents.
+    fn interface(&self, script: &[Stmt], ctx: &Ctx) -> String;
+}
+
+/// Emitter of the backend.
+pub fn emitter(backend: Backend) -> &'static dyn Emit {
+    match backend {
+        Backend::C => &c::C,
+        Backend::Cpp => &cpp::Cpp,
+    }
+}
@@ -1,12 +1,21 @@
 use async_std::{
-    fs::File,
-    io::WriteExt,
+    fs,
     path::{Path, PathBuf},
 };
 use cgen::Stmt;
-use std::hash::{DefaultHasher, Hash, Hasher};
+use emit::Emit;
+use serde_json::json;
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashSet},
+};
+use torchc_diagnosis::panic;
 use torchc_lits::lits;
 
+pub mod cgen;
+mod emit;
+mod unicode;
+
 /// It performs the evaluations, optimizations and others; to later generate the
 /// C/C++ code of the script.
 ///
@@ -16,104 +25,373 @@ use torchc_lits::lits;
 pub struct CGen<'cgen> {
     script: Vec<Stmt>,
     target: &'cgen Path,
+    profile: &'cgen cgen::Profile,
+    backend: cgen::Backend,
+    /// Modules of the dependencies of the package.
+    imports: &'cgen cgen::Imports,
 }
 impl<'cgen> CGen<'cgen> {
-    pub fn new(script: Vec<Stmt>, target: &'cgen Path) -> Self {
-        Self { script, target }
+    pub fn new(
+        script: Vec<Stmt>,
+        target: &'cgen Path,
+        profile: &'cgen cgen::Profile,
+        backend: cgen::Backend,
+        imports: &'cgen cgen::Imports,
+    ) -> Self {
+        Self {
+            script,
+            target,
+            profile,
+            backend,
+            imports,
+        }
     }
-    /// Generate the C/C++ code of the script (_file-to-file_).
-    pub async fn cgen(&self, script: &Path) {
+    /// Generate the C/C++ code of the script of the module (_file-to-file_).
+    pub async fn cgen(&self, script: &Path, module: String, cwd: &Path) -> Generated {
+        let emit: &dyn Emit = emit::emitter(self.backend);
         let mut path: PathBuf = self.target.to_path_buf();
-        path.push({
-            let mut hasher: DefaultHasher = DefaultHasher::new();
-            script.hash(&mut hasher);
-            // `xxxxxxxxxxxxxxxxxxx.cpp`
-            &(hasher.finish().to_string() + lits::DOT + lits::extensions::CPP)
-        });
-        let mut cpp: File = async_std::fs::File::create(&path)
+        // `net.http.cpp` or `net.http.c`
+        path.push(module.clone() + lits::DOT + emit.extension());
+
+        // `src/file.t`
+        let rel: String = match script.strip_prefix(cwd) {
+            Ok(rel) => rel.to_string_lossy().into_owned(),
+            Err(_) => script.to_string_lossy().into_owned(),
+        };
+        let externs: HashSet<String> = self
+            .script
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::Extern(Some(cgen::Extern {
+                    abi: cgen::Abi::C,
+                    decl: cgen::ExternDecl::Fn(sig),
+                })) => sig.name.lit().map(|lit| format!("{}", lit)),
+                _ => None,
+            })
+            .collect();
+        let natives: HashSet<String> = self
+            .script
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::Extern(Some(extern_stmt)) => match &extern_stmt.decl {
+                    cgen::ExternDecl::Fn(sig) => sig.name.lit(),
+                    cgen::ExternDecl::Const(param) => param.name.lit(),
+                }
+                .map(|lit| format!("{}", lit)),
+                _ => None,
+            })
+            .collect();
+        let fns: HashSet<String> = self
+            .script
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::Fn(Some(fn_stmt)) => fn_stmt.name.lit().map(|lit| format!("{}", lit)),
+                _ => None,
+            })
+            .collect();
+        let ctx: cgen::Ctx = cgen::Ctx {
+            profile: self.profile,
+            script: &rel,
+            externs: &externs,
+            natives: &natives,
+            fns: &fns,
+            module: &module,
+            imports: self.imports,
+            imported: RefCell::new(BTreeSet::new()),
+            scope: RefCell::new(vec![]),
+            diagnosis: &panic::Diagnosis::new(script, cwd),
+        };
+
+        let code: String = emit.emit(&self.script, &ctx, self.target.as_ref());
+
+        // `net.http_interface.h`, the prototypes for the dependents.
+        let interface: PathBuf = interface_path(self.target, &module);
+        fs::write(&interface, emit.interface(&self.script, &ctx))
             .await
             .unwrap_or_else(|err| panic!("{}", err));
+        // The headers of `include "file.h"` and the interfaces of the named modules.
+        let includes: Vec<PathBuf> = self
+            .script
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::Include(Some(include)) => Some(PathBuf::from(include.header.clone())),
+                _ => None,
+            })
+            .chain(
+                ctx.imported
+                    .borrow()
+                    .iter()
+                    .map(|module| interface_path(self.target, module)),
+            )
+            .collect();
 
-        for stmt in &self.script {
-            match stmt {
-                Stmt::Fn(opt) => {
-                    if let Some(fn_stmt) = opt {
-                        let mut cpp_fn: String = String::new();
-                        fn_stmt.cgen(&mut cpp_fn);
-                        cpp.write_all(cpp_fn.as_bytes())
-                            .await
-                            .unwrap_or_else(|err| panic!("{}", err));
-                    }
-                }
-                Stmt::Global(_) => {}
+        // Header of the exported functions.
+        let exports: Vec<&cgen::Fn> = self
+            .script
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::Fn(Some(fn_stmt)) if fn_stmt.export => Some(fn_stmt),
+                _ => None,
+            })
+            .collect();
+
+        // `net.http.h`
+        let mut header: PathBuf = self.target.to_path_buf();
+        header.push(module.clone() + lits::DOT + lits::extensions::H);
+        if exports.is_empty() {
+            // Without exports the header of a previous build is stale.
+            if header.exists().await {
+                fs::remove_file(&header)
+                    .await
+                    .unwrap_or_else(|err| panic!("{}", err));
             }
+        } else {
+            fs::write(&header, cgen::header(&rel, &module, &exports))
+                .await
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
+
+        let filename: String = match path.file_name() {
+            Some(filename) => filename.to_string_lossy().into_owned(),
+            None => String::from(lits::cgen::DEFAULT_ID),
+        };
+        let code: String = cgen::restore_lines(&code, &filename);
+        fs::write(&path, &code)
+            .await
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        // `net.http.cpp.map`
+        let mut map: PathBuf = path.clone();
+        map.set_file_name(filename.clone() + lits::DOT + lits::extensions::MAP);
+        fs::write(
+            &map,
+            serde_json::to_string(&cgen::source_map(&code, &rel, &filename))
+                .unwrap_or_else(|err| panic!("{}", err))
+                + "\n",
+        )
+        .await
+        .unwrap_or_else(|err| panic!("{}", err));
+
+        let mut files: Vec<PathBuf> = vec![path, map, interface];
+        if !exports.is_empty() {
+            files.push(header);
+        }
+        Generated {
+            script: script.to_path_buf(),
+            module,
+            backend: self.backend,
+            files,
+            includes,
         }
     }
 }
 
-pub mod cgen {
-    use torchc_lex::Token;
-    use torchc_lits::lits;
-
-    /// Transpilation mode.
-    #[derive(Debug, PartialEq)]
-    #[repr(u8)]
-    pub enum Mode {
-        /// Ready for production.
-        Release,
-        /// Debugging and/or testing.
-        Dev,
+/// Write the runtime of the backend in the target folder, the generated files include
+/// it.
+///
+/// ---
+/// > _It is written before generating the scripts, which can be generated at the same
+/// > time._
+pub async fn runtime(target: &Path, backend: cgen::Backend) {
+    for runtime in emit::emitter(backend).runtime() {
+        let mut file: PathBuf = target.to_path_buf();
+        file.push(runtime.name);
+        if !runtime.once || !file.exists().await {
+            fs::write(&file, (runtime.contents)())
+                .await
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
     }
+}
 
-    /// Language statements.
-    #[derive(Debug)]
-    #[repr(u8)]
-    pub enum Stmt {
-        Global(Option<Vec<Self>>),
-        Fn(Option<Fn>),
+/// Files generated from a script.
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub script: PathBuf,
+    /// Name of the files (see [`module_name`]).
+    pub module: String,
+    pub backend: cgen::Backend,
+    /// Code, source map, interface and, with `export fn`, the header.
+    pub files: Vec<PathBuf>,
+    /// Headers included by the code, the ones of `include "file.h"` and the interfaces
+    /// of the modules of the dependencies.
+    pub includes: Vec<PathBuf>,
+}
+
+/// Name of the generated files of the script from its module path in `src/`
+/// (`src/net/http.t` -> `net.http`).
+///
+/// ---
+/// > _The components keep the ASCII letters and digits, `_` is `__` and the other bytes
+/// > are `_xx`, so two modules never have the same name and no module has the name of
+/// > a file of the runtime (`torch_*`)._
+pub fn module_name(script: &Path, src: &Path) -> String {
+    let mut module: PathBuf = match script.strip_prefix(src) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => PathBuf::from(script.file_name().unwrap_or_default()),
+    };
+    module.set_extension("");
+
+    let mut name: String = String::new();
+    for (i, component) in module.iter().enumerate() {
+        if i > 0 {
+            name.push_str(lits::DOT);
+        }
+        push_component(&mut name, &component.to_string_lossy());
     }
+    if name.is_empty() {
+        name.push_str(lits::cgen::DEFAULT_ID);
+    }
+    name
+}
+
+/// Name of the module of a dependency, its package is the first component
+/// (`net.http` of `web-kit` -> `web_2dkit.net.http`).
+pub fn dependency_module(package: &str, module: &str) -> String {
+    let mut name: String = String::new();
+    push_component(&mut name, package);
+    name.push_str(lits::DOT);
+    name.push_str(module);
+    name
+}
+
+/// Path of the module of a dependency in the language, the `-` of its package are `_`
+/// (`src/net/http.t` of `web-kit` -> `web_kit.net.http`).
+pub fn import_path(package: &str, script: &Path, src: &Path) -> String {
+    let mut module: PathBuf = match script.strip_prefix(src) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => PathBuf::from(script.file_name().unwrap_or_default()),
+    };
+    module.set_extension("");
+    [package.replace('-', "_")]
+        .into_iter()
+        .chain(
+            module
+                .iter()
+                .map(|component| component.to_string_lossy().into_owned()),
+        )
+        .collect::<Vec<String>>()
+        .join(lits::DOT)
+}
+/// Interface of the module in the target folder (`net.http_interface.h`).
+fn interface_path(target: &Path, module: &str) -> PathBuf {
+    target
+        .join(String::from(module) + lits::cgen::INTERFACE_SUFFIX + lits::DOT + lits::extensions::H)
+}
 
-    /// **Statement:**
-    ///
-    /// `fn name(var arg1 = type <lit>, arg2 = type <lit>, ...) var type <lit>, type <lit>, ...`<br>
-    /// &nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;&nbsp;`...`
-    #[derive(Debug)]
-    pub struct Fn {
-        pub name: Token,
-        pub body: Vec<Stmt>,
+/// Push the escaped component of a module name.
+fn push_component(name: &mut String, component: &str) {
+    for byte in component.bytes() {
+        match byte {
+            b'_' => name.push_str("__"),
+            byte if byte.is_ascii_alphanumeric() => name.push(byte as char),
+            byte => name.push_str(&format!("_{:02x}", byte)),
+        }
     }
-    impl Fn {
-        pub fn new() -> Self {
-            Self {
-                name: Token::new(),
-                body: vec![],
-            }
+}
+
+/// Write the manifest of the target folder, it maps each script to its generated
+/// files (paths relative to the project).
+///
+/// ---
+/// ```json
+/// {
+///   "modules": [{"backend": "cpp", "files": [...], "module": "main", "script": "src/main.t"}],
+///   "runtime": [".target/dev/torch.hpp", ...]
+/// }
+/// ```
+pub async fn manifest(target: &Path, cwd: &Path, generated: &[Generated]) {
+    let rel = |path: &Path| -> String {
+        match path.strip_prefix(cwd) {
+            Ok(rel) => rel.to_string_lossy().into_owned(),
+            Err(_) => path.to_string_lossy().into_owned(),
         }
-        /// Generate C/C++ function code.
-        pub fn cgen(&self, cpp_fn: &mut String) {
-            // Function name.
-            match self.name.lit() {
-                Some(lit) => cpp_fn.push_str(&format!(" {}", lit)),
-                None => {
-                    cpp_fn.push(' ');
-                    cpp_fn.push_str(lits::cgen::DEFAULT_ID);
-                }
-            }
+    };
+    // The runtimes of the backends of the modules.
+    let mut runtime: Vec<String> = vec![];
+    for backend in [cgen::Backend::Cpp, cgen::Backend::C] {
+        if generated
+            .iter()
+            .any(|generated| generated.backend == backend)
+        {
+            runtime.extend(
+                emit::emitter(backend)
+                    .runtime()
+                    .iter()
+                    .map(|runtime| rel(&target.join(runtime.name))),
+            );
+        }
+    }
+    let modules: Vec<serde_json::Value> = generated
+        .iter()
+        .map(|generated| {
+            json!({
+                "script": rel(&generated.script),
+                "module": generated.module,
+                "backend": generated.backend.name(),
+                "files": generated.files.iter().map(|file| rel(file)).collect::<Vec<String>>(),
+            })
+        })
+        .collect();
 
-            // Function body.
-            cpp_fn.push('{');
-            for stmt in &self.body {
-                match stmt {
-                    Stmt::Fn(opt) => match opt {
-                        Some(fn_stmt) => {
-                            fn_stmt.cgen(cpp_fn);
-                        }
-                        None => {}
-                    },
-                    Stmt::Global(_) => {}
-                }
-            }
-            cpp_fn.push('}');
+    let mut path: PathBuf = target.to_path_buf();
+    path.push(lits::cgen::MANIFEST);
+    let manifest: serde_json::Value = json!({
+        "runtime": runtime,
+        "modules": modules,
+    });
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&manifest).unwrap_or_else(|err| panic!("{}", err)) + "\n",
+    )
+    .await
+    .unwrap_or_else(|err| panic!("{}", err));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(script: &str) -> String {
+        module_name(Path::new(script), Path::new("src"))
+    }
+
+    #[test]
+    fn module_names() {
+        assert_eq!(name("src/main.t"), "main");
+        assert_eq!(name("src/net/http.t"), "net.http");
+        assert_eq!(name("src/a_b.t"), "a__b");
+        assert_eq!(name("src/a-b.t"), "a_2db");
+        assert_eq!(name("src/é.t"), "_c3_a9");
+        assert_eq!(
+            dependency_module("web-kit", "net.http"),
+            "web_2dkit.net.http"
+        );
+    }
+
+    #[test]
+    fn module_names_are_unique() {
+        let scripts: [&str; 10] = [
+            "src/a_b.t",
+            "src/a/b.t",
+            "src/a.b.t",
+            "src/a-b.t",
+            "src/a_2db.t",
+            "src/a__b.t",
+            "src/a_/b.t",
+            "src/a/_b.t",
+            "src/torch_unicode.t",
+            "src/torch.t",
+        ];
+        let mut names: HashSet<String> = scripts.iter().map(|script| name(script)).collect();
+        assert_eq!(names.len(), scripts.len());
+        // No module takes the name of a file of the runtime (`torch_*`).
+        for runtime in [lits::cgen::C_RUNTIME, lits::cgen::UNICODE] {
+            let stem: &str = runtime.split_once(lits::DOT).unwrap().0;
+            assert!(!names.contains(stem));
         }
+        // The modules of the dependencies do not take the names of the package modules.
+        names.insert(dependency_module("web-kit", "net.http"));
+        assert!(names.insert(name("src/web_2dkit/net/http.t")));
     }
 }
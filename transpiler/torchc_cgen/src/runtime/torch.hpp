@@ -0,0 +1,461 @@
+// Torch runtime.
+//
+// Written by `torchc` in the target folder next to the generated code, do not edit.
+#pragma once
+
+#include <array>
+#include <cstddef>
+#include <cstdint>
+#include <cstdio>
+#include <cstdlib>
+#include <cstring>
+#include <initializer_list>
+#include <string>
+#include <type_traits>
+#include <utility>
+#include <vector>
+
+#include "torch_unicode.hpp"
+
+namespace torch {
+
+/// Position in a Torch script (`src/file.t:line:grapheme`).
+struct pos {
+    const char *script;
+    std::int64_t line;
+    std::int64_t grapheme;
+};
+
+/// Abort the program.
+[[noreturn]] inline void panic(const char *msg) {
+    std::fflush(stdout);
+    std::fprintf(stderr, "error: %s\n", msg);
+    std::fflush(stderr);
+    std::abort();
+}
+/// Abort the program pointing at the Torch source position.
+[[noreturn]] inline void panic(const char *msg, const pos &here) {
+    std::fflush(stdout);
+    std::fprintf(stderr, "error: %s → %s:%lld:%lld\n", msg, here.script,
+                 static_cast<long long>(here.line), static_cast<long long>(here.grapheme));
+    std::fflush(stderr);
+    std::abort();
+}
+
+/// Bounds check of `i` in `[0, len)` (`TORCH_BOUNDS_CHECKS` enables it).
+inline void bounds(std::int64_t i, std::int64_t len, const pos &here) {
+#ifdef TORCH_BOUNDS_CHECKS
+    if (i < 0 || i >= len) {
+        char msg[96];
+        std::snprintf(msg, sizeof(msg), "index %lld is out of bounds for length %lld",
+                      static_cast<long long>(i), static_cast<long long>(len));
+        panic(msg, here);
+    }
+#else
+    (void)i;
+    (void)len;
+    (void)here;
+#endif
+}
+/// Bounds check of the range `[lo, hi)` in `[0, len]` (`TORCH_BOUNDS_CHECKS` enables it).
+inline void bounds(std::int64_t lo, std::int64_t hi, std::int64_t len, const pos &here) {
+#ifdef TORCH_BOUNDS_CHECKS
+    if (lo < 0 || lo > hi || hi > len) {
+        char msg[128];
+        std::snprintf(msg, sizeof(msg), "range %lld..%lld is out of bounds for length %lld",
+                      static_cast<long long>(lo), static_cast<long long>(hi),
+                      static_cast<long long>(len));
+        panic(msg, here);
+    }
+#else
+    (void)lo;
+    (void)hi;
+    (void)len;
+    (void)here;
+#endif
+}
+
+/// View of contiguous elements (`[]type`).
+template <class T> struct slice {
+    T *ptr = nullptr;
+    std::int64_t length = 0;
+
+    slice() = default;
+    slice(T *ptr, std::int64_t length) : ptr(ptr), length(length) {}
+    template <class C, class = decltype(std::declval<C &>().data())>
+    slice(C &items) : ptr(items.data()), length(items.len()) {}
+
+    T *data() const { return ptr; }
+    std::int64_t len() const { return length; }
+    T *begin() const { return ptr; }
+    T *end() const { return ptr + length; }
+};
+
+/// Fixed-size array (`[n]type`).
+template <class T, std::size_t N> struct array {
+    std::array<T, N> items;
+
+    T *data() { return items.data(); }
+    const T *data() const { return items.data(); }
+    std::int64_t len() const { return static_cast<std::int64_t>(N); }
+    T *begin() { return items.data(); }
+    T *end() { return items.data() + N; }
+    const T *begin() const { return items.data(); }
+    const T *end() const { return items.data() + N; }
+};
+template <class T, class... U> array(T, U...) -> array<T, 1 + sizeof...(U)>;
+
+/// Growable list (`list[type]`).
+template <class T> struct list {
+    std::vector<T> items;
+
+    list() = default;
+    list(std::initializer_list<T> items) : items(items) {}
+
+    T *data() { return items.data(); }
+    const T *data() const { return items.data(); }
+    std::int64_t len() const { return static_cast<std::int64_t>(items.size()); }
+    T *begin() { return items.data(); }
+    T *end() { return items.data() + items.size(); }
+    const T *begin() const { return items.data(); }
+    const T *end() const { return items.data() + items.size(); }
+
+    void push(T item) { items.push_back(std::move(item)); }
+    T pop() {
+        if (items.empty()) {
+            panic("pop from an empty list");
+        }
+        T item = std::move(items.back());
+        items.pop_back();
+        return item;
+    }
+    void clear() { items.clear(); }
+};
+
+namespace unicode {
+
+/// Decode the UTF-8 code point at `bytes[i]`, advancing `i` (an invalid byte is `U+FFFD`).
+inline char32_t decode(const char *bytes, std::int64_t len, std::int64_t &i) {
+    const auto byte = [&](std::int64_t j) { return static_cast<unsigned char>(bytes[j]); };
+    const unsigned char lead = byte(i);
+    const std::int64_t n = lead < 0x80         ? 0
+                           : lead >> 5 == 0x6  ? 1
+                           : lead >> 4 == 0xE  ? 2
+                           : lead >> 3 == 0x1E ? 3
+                                               : -1;
+    if (n < 0 || i + n >= len) {
+        i += 1;
+        return 0xFFFD;
+    }
+    char32_t c = n == 0 ? lead : lead & (0x3F >> n);
+    for (std::int64_t j = 1; j <= n; ++j) {
+        if (byte(i + j) >> 6 != 0x2) {
+            i += 1;
+            return 0xFFFD;
+        }
+        c = c << 6 | (byte(i + j) & 0x3F);
+    }
+    i += n + 1;
+    return c;
+}
+/// Encode the code point in UTF-8.
+inline void encode(char32_t c, std::string &bytes) {
+    if (c < 0x80) {
+        bytes += static_cast<char>(c);
+    } else if (c < 0x800) {
+        bytes += static_cast<char>(0xC0 | c >> 6);
+        bytes += static_cast<char>(0x80 | (c & 0x3F));
+    } else if (c < 0x10000) {
+        bytes += static_cast<char>(0xE0 | c >> 12);
+        bytes += static_cast<char>(0x80 | (c >> 6 & 0x3F));
+        bytes += static_cast<char>(0x80 | (c & 0x3F));
+    } else {
+        bytes += static_cast<char>(0xF0 | c >> 18);
+        bytes += static_cast<char>(0x80 | (c >> 12 & 0x3F));
+        bytes += static_cast<char>(0x80 | (c >> 6 & 0x3F));
+        bytes += static_cast<char>(0x80 | (c & 0x3F));
+    }
+}
+
+/// Grapheme cluster break property of the code point.
+enum class gcb_full : std::uint8_t { other, cr, lf, control, extend, zwj, prepend, regional, pictographic, l, v, t, lv, lvt };
+inline gcb_full property(char32_t c) {
+    if (c == '\r') return gcb_full::cr;
+    if (c == '\n') return gcb_full::lf;
+    if (c == 0x200D) return gcb_full::zwj;
+    if (c >= 0x1F1E6 && c <= 0x1F1FF) return gcb_full::regional;
+    if ((c >= 0x1100 && c <= 0x115F) || (c >= 0xA960 && c <= 0xA97C)) return gcb_full::l;
+    if ((c >= 0x1160 && c <= 0x11A7) || (c >= 0xD7B0 && c <= 0xD7C6)) return gcb_full::v;
+    if ((c >= 0x11A8 && c <= 0x11FF) || (c >= 0xD7CB && c <= 0xD7FB)) return gcb_full::t;
+    if (c >= 0xAC00 && c <= 0xD7A3) return (c - 0xAC00) % 28 == 0 ? gcb_full::lv : gcb_full::lvt;
+
+    std::size_t lo = 0, hi = sizeof(GCB) / sizeof(GCB[0]);
+    while (lo < hi) {
+        const std::size_t mid = lo + (hi - lo) / 2;
+        if (c < GCB[mid].lo) {
+            hi = mid;
+        } else if (c > GCB[mid].hi) {
+            lo = mid + 1;
+        } else {
+            switch (GCB[mid].property) {
+            case gcb::control: return gcb_full::control;
+            case gcb::extend: return gcb_full::extend;
+            case gcb::prepend: return gcb_full::prepend;
+            case gcb::pictographic: return gcb_full::pictographic;
+            case gcb::other: break;
+            }
+            break;
+        }
+    }
+    return gcb_full::other;
+}
+
+/// Count the extended grapheme clusters of the UTF-8 bytes.
+inline std::int64_t graphemes(const char *bytes, std::int64_t len) {
+    std::int64_t count = 0;
+    gcb_full prev = gcb_full::other;
+    bool pictographic = false; // `Extended_Pictographic Extend*` before the current one.
+    std::int64_t regionals = 0; // Consecutive regional indicators before the current one.
+    for (std::int64_t i = 0; i < len;) {
+        const gcb_full cur = property(decode(bytes, len, i));
+        bool join;
+        if (count == 0) {
+            join = false;
+        } else if (prev == gcb_full::cr && cur == gcb_full::lf) {
+            join = true;
+        } else if (prev == gcb_full::cr || prev == gcb_full::lf || prev == gcb_full::control ||
+                   cur == gcb_full::cr || cur == gcb_full::lf || cur == gcb_full::control) {
+            join = false;
+        } else if (prev == gcb_full::l && (cur == gcb_full::l || cur == gcb_full::v ||
+                                           cur == gcb_full::lv || cur == gcb_full::lvt)) {
+            join = true;
+        } else if ((prev == gcb_full::lv || prev == gcb_full::v) &&
+                   (cur == gcb_full::v || cur == gcb_full::t)) {
+            join = true;
+        } else if ((prev == gcb_full::lvt || prev == gcb_full::t) && cur == gcb_full::t) {
+            join = true;
+        } else if (cur == gcb_full::extend || cur == gcb_full::zwj || prev == gcb_full::prepend) {
+            join = true;
+        } else if (prev == gcb_full::zwj && cur == gcb_full::pictographic && pictographic) {
+            join = true;
+        } else if (prev == gcb_full::regional && cur == gcb_full::regional) {
+            join = regionals % 2 == 1;
+        } else {
+            join = false;
+        }
+        if (!join) {
+            count += 1;
+        }
+
+        if (cur == gcb_full::pictographic) {
+            pictographic = true;
+        } else if (cur != gcb_full::extend && !(cur == gcb_full::zwj && pictographic)) {
+            pictographic = false;
+        }
+        regionals = cur == gcb_full::regional ? regionals + 1 : 0;
+        prev = cur;
+    }
+    return count;
+}
+
+} // namespace unicode
+
+/// Owned UTF-8 string (`str`).
+struct str {
+    std::string bytes;
+
+    str() = default;
+    template <std::size_t N> str(const char (&lit)[N]) : bytes(lit, N - 1) {}
+    str(const char *bytes, std::int64_t len) : bytes(bytes, static_cast<std::size_t>(len)) {}
+    explicit str(std::string bytes) : bytes(std::move(bytes)) {}
+    /// String of the C functions (`nullptr` is the empty string).
+    static str from_c(const char *bytes) { return bytes ? str(std::string(bytes)) : str(); }
+    /// Copy for C allocated with `malloc`, it is released with `free`.
+    char *to_c() const {
+        char *copy = static_cast<char *>(std::malloc(bytes.size() + 1));
+        if (!copy) panic("out of memory");
+        std::memcpy(copy, bytes.c_str(), bytes.size() + 1);
+        return copy;
+    }
+
+    char *data() { return bytes.data(); }
+    const char *data() const { return bytes.data(); }
+    /// Length in bytes.
+    std::int64_t len() const { return static_cast<std::int64_t>(bytes.size()); }
+    /// Length in extended grapheme clusters.
+    std::int64_t graphemes() const { return unicode::graphemes(bytes.data(), len()); }
+    /// Is the byte index the start of a UTF-8 sequence (or the end of the string)?
+    bool is_char_boundary(std::int64_t i) const {
+        return i == len() || (i >= 0 && i < len() && (static_cast<unsigned char>(bytes[i]) >> 6) != 0x2);
+    }
+    const char *c_str() const { return bytes.c_str(); }
+};
+inline str operator+(const str &lhs, const str &rhs) { return str(lhs.bytes + rhs.bytes); }
+inline bool operator==(const str &lhs, const str &rhs) { return lhs.bytes == rhs.bytes; }
+inline bool operator!=(const str &lhs, const str &rhs) { return lhs.bytes != rhs.bytes; }
+inline bool operator<(const str &lhs, const str &rhs) { return lhs.bytes < rhs.bytes; }
+inline bool operator<=(const str &lhs, const str &rhs) { return lhs.bytes <= rhs.bytes; }
+inline bool operator>(const str &lhs, const str &rhs) { return lhs.bytes > rhs.bytes; }
+inline bool operator>=(const str &lhs, const str &rhs) { return lhs.bytes >= rhs.bytes; }
+
+/// Char boundary check of the byte range `[lo, hi)` (`TORCH_BOUNDS_CHECKS` enables it).
+inline void boundaries(const str &s, std::int64_t lo, std::int64_t hi, const pos &here) {
+#ifdef TORCH_BOUNDS_CHECKS
+    for (const std::int64_t i : {lo, hi}) {
+        if (!s.is_char_boundary(i)) {
+            char msg[96];
+            std::snprintf(msg, sizeof(msg), "byte index %lld is not a char boundary",
+                          static_cast<long long>(i));
+            panic(msg, here);
+        }
+    }
+#else
+    (void)s;
+    (void)lo;
+    (void)hi;
+    (void)here;
+#endif
+}
+
+/// Text representation of the values of the interpolations (`"...{expr}..."`).
+inline str to_str(const str &value) { return value; }
+inline str to_str(bool value) { return value ? str("true") : str("false"); }
+inline str to_str(char32_t value) {
+    std::string bytes;
+    unicode::encode(value, bytes);
+    return str(std::move(bytes));
+}
+template <class T, std::enable_if_t<std::is_integral_v<T>, int> = 0> str to_str(T value) {
+    return str(std::to_string(value));
+}
+template <class T, std::enable_if_t<std::is_floating_point_v<T>, int> = 0> str to_str(T value) {
+    char bytes[32];
+    const int len = std::snprintf(bytes, sizeof(bytes), "%g", static_cast<double>(value));
+    return str(bytes, len);
+}
+
+/// `"...{expr}..."`
+template <class... A> str format(const A &...values) {
+    str out;
+    ((out.bytes += to_str(values).bytes), ...);
+    return out;
+}
+
+/// `len(array|slice|list|str)`
+template <class C> std::int64_t len(const C &items) { return items.len(); }
+
+/// `items[i]`
+template <class C> auto at(C &&items, std::int64_t i, const pos &here) -> decltype(*items.data()) {
+    bounds(i, items.len(), here);
+    return items.data()[i];
+}
+/// `items[lo..hi]`
+template <class C>
+auto subslice(C &&items, std::int64_t lo, std::int64_t hi, const pos &here)
+    -> std::conditional_t<std::is_same_v<std::decay_t<C>, str>, str,
+                          slice<std::remove_reference_t<decltype(*items.data())>>> {
+    bounds(lo, hi, items.len(), here);
+    if constexpr (std::is_same_v<std::decay_t<C>, str>) {
+        boundaries(items, lo, hi, here);
+        return str(items.data() + lo, hi - lo);
+    } else {
+        return {items.data() + lo, hi - lo};
+    }
+}
+
+/// `ok(value)`, it converts to any `result[type, type]` with a compatible value type.
+template <class T> struct ok_t {
+    T value;
+};
+/// `err(error)`, it converts to any `result[type, type]` with a compatible error type.
+template <class E> struct err_t {
+    E error;
+};
+/// `some(value)`, it converts to any `option[type]` with a compatible value type.
+template <class T> struct some_t {
+    T value;
+};
+/// `none`, it converts to any `option[type]`.
+struct none_t {};
+
+template <class T> ok_t<std::decay_t<T>> ok(T &&value) { return {std::forward<T>(value)}; }
+template <class E> err_t<std::decay_t<E>> err(E &&error) { return {std::forward<E>(error)}; }
+template <class T> some_t<std::decay_t<T>> some(T &&value) { return {std::forward<T>(value)}; }
+inline constexpr none_t none{};
+
+/// Value or error (`result[type, type]`).
+template <class T, class E> struct result {
+    bool ok;
+    T value;
+    E error;
+
+    template <class U> result(ok_t<U> other) : ok(true), value(std::move(other.value)), error() {}
+    template <class F> result(err_t<F> other) : ok(false), value(), error(std::move(other.error)) {}
+
+    explicit operator bool() const { return ok; }
+    bool is_ok() const { return ok; }
+    bool is_err() const { return !ok; }
+    T unwrap() const {
+        if (!ok) {
+            panic("unwrap of an err result");
+        }
+        return value;
+    }
+    T unwrap_or(T other) const { return ok ? value : other; }
+};
+/// Value or nothing (`option[type]`).
+template <class T> struct option {
+    bool some;
+    T value;
+
+    template <class U> option(some_t<U> other) : some(true), value(std::move(other.value)) {}
+    option(none_t) : some(false), value() {}
+
+    explicit operator bool() const { return some; }
+    bool is_some() const { return some; }
+    bool is_none() const { return !some; }
+    T unwrap() const {
+        if (!some) {
+            panic("unwrap of a none option");
+        }
+        return value;
+    }
+    T unwrap_or(T other) const { return some ? value : other; }
+};
+
+/// `expr?` of an err result, the error is returned to the caller.
+template <class T, class E> err_t<E> propagate(result<T, E> &&failed) {
+    return {std::move(failed.error)};
+}
+/// `expr?` of a none option, `none` is returned to the caller.
+template <class T> none_t propagate(option<T> &&) { return none; }
+
+/// `defer`, the body runs when the guard goes out of scope.
+template <class F> struct defer {
+    F body;
+
+    explicit defer(F body) : body(std::move(body)) {}
+    defer(const defer &) = delete;
+    defer &operator=(const defer &) = delete;
+    ~defer() { body(); }
+};
+
+/// `lo..hi`
+struct range {
+    std::int64_t lo;
+    std::int64_t hi;
+
+    struct iterator {
+        std::int64_t i;
+        std::int64_t operator*() const { return i; }
+        iterator &operator++() {
+            ++i;
+            return *this;
+        }
+        bool operator!=(const iterator &other) const { return i != other.i; }
+    };
+    iterator begin() const { return {lo}; }
+    iterator end() const { return {hi < lo ? lo : hi}; }
+};
+
+} // namespace torch
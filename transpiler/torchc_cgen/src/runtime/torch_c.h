@@ -0,0 +1,253 @@
+// Torch runtime of the C backend.
+//
+// Written by `torchc` in the target folder next to the generated code, do not edit.
+#pragma once
+
+#include <inttypes.h>
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+/// Abort the program.
+static inline _Noreturn void torch_panic(const char *msg) {
+    fflush(stdout);
+    fprintf(stderr, "error: %s\n", msg);
+    fflush(stderr);
+    abort();
+}
+
+/// Position in a Torch script (`src/file.t:line:grapheme`).
+typedef struct {
+    const char *script;
+    int64_t line;
+    int64_t grapheme;
+} torch_pos;
+
+/// Abort the program pointing at the Torch source position.
+static inline _Noreturn void torch_panic_at(const char *msg, torch_pos here) {
+    fflush(stdout);
+    fprintf(stderr, "error: %s → %s:%" PRId64 ":%" PRId64 "\n", msg, here.script, here.line,
+            here.grapheme);
+    fflush(stderr);
+    abort();
+}
+
+/// Bounds check of `i` in `[0, len)` (`TORCH_BOUNDS_CHECKS` enables it).
+static inline void torch_bounds(int64_t i, int64_t len, torch_pos here) {
+#ifdef TORCH_BOUNDS_CHECKS
+    if (i < 0 || i >= len) {
+        char msg[96];
+        snprintf(msg, sizeof(msg), "index %" PRId64 " is out of bounds for length %" PRId64, i,
+                 len);
+        torch_panic_at(msg, here);
+    }
+#else
+    (void)i;
+    (void)len;
+    (void)here;
+#endif
+}
+/// Bounds check of the range `[lo, hi)` in `[0, len]` (`TORCH_BOUNDS_CHECKS` enables it).
+static inline void torch_bounds_range(int64_t lo, int64_t hi, int64_t len, torch_pos here) {
+#ifdef TORCH_BOUNDS_CHECKS
+    if (lo < 0 || lo > hi || hi > len) {
+        char msg[128];
+        snprintf(msg, sizeof(msg),
+                 "range %" PRId64 "..%" PRId64 " is out of bounds for length %" PRId64, lo, hi,
+                 len);
+        torch_panic_at(msg, here);
+    }
+#else
+    (void)lo;
+    (void)hi;
+    (void)len;
+    (void)here;
+#endif
+}
+
+/// Items of a list with room for more (`list[type]`), `cap` is updated.
+static inline void *torch_grow(void *items, int64_t *cap, size_t size) {
+    *cap = *cap ? *cap * 2 : 4;
+    items = realloc(items, (size_t)*cap * size);
+    if (!items) torch_panic("out of memory");
+    return items;
+}
+
+/// UTF-8 string (`str`).
+///
+/// The bytes are followed by `'\0'`, they are literals or allocated with `malloc`.
+/// Without destructors the allocated strings are not released.
+typedef struct {
+    char *ptr;
+    int64_t len;
+} torch_str;
+
+/// `"..."` of the initializers (`torch_str s = TORCH_STR_INIT("...");`).
+#define TORCH_STR_INIT(lit) {(char *)(lit), (int64_t)(sizeof(lit) - 1)}
+/// `"..."`
+#define TORCH_STR(lit) ((torch_str)TORCH_STR_INIT(lit))
+
+/// String of `len` bytes, only its `'\0'` is written.
+static inline torch_str torch_str_alloc(int64_t len) {
+    char *ptr = (char *)malloc((size_t)len + 1);
+    if (!ptr) torch_panic("out of memory");
+    ptr[len] = '\0';
+    return (torch_str){ptr, len};
+}
+/// String of the C functions (`NULL` is the empty string).
+static inline torch_str torch_str_from_c(const char *bytes) {
+    if (!bytes) return TORCH_STR("");
+    torch_str s = torch_str_alloc((int64_t)strlen(bytes));
+    memcpy(s.ptr, bytes, (size_t)s.len);
+    return s;
+}
+/// Bytes for the C functions (the zero-initialized string is `""`).
+static inline const char *torch_str_c(torch_str s) { return s.ptr ? s.ptr : ""; }
+/// Copy for C allocated with `malloc`, it is released with `free`.
+static inline char *torch_str_to_c(torch_str s) {
+    torch_str copy = torch_str_alloc(s.len);
+    if (s.len) memcpy(copy.ptr, s.ptr, (size_t)s.len);
+    return copy.ptr;
+}
+
+/// `len(str)`, length in bytes.
+static inline int64_t torch_str_len(torch_str s) { return s.len; }
+/// `str + str`
+static inline torch_str torch_str_concat(torch_str lhs, torch_str rhs) {
+    torch_str out = torch_str_alloc(lhs.len + rhs.len);
+    if (lhs.len) memcpy(out.ptr, lhs.ptr, (size_t)lhs.len);
+    if (rhs.len) memcpy(out.ptr + lhs.len, rhs.ptr, (size_t)rhs.len);
+    return out;
+}
+/// `str == str`
+static inline bool torch_str_eq(torch_str lhs, torch_str rhs) {
+    return lhs.len == rhs.len && (!lhs.len || memcmp(lhs.ptr, rhs.ptr, (size_t)lhs.len) == 0);
+}
+/// `str < str`, ... (bytewise, negative, zero or positive).
+static inline int torch_str_cmp(torch_str lhs, torch_str rhs) {
+    const int64_t len = lhs.len < rhs.len ? lhs.len : rhs.len;
+    const int cmp = len ? memcmp(lhs.ptr, rhs.ptr, (size_t)len) : 0;
+    if (cmp) return cmp;
+    return (lhs.len > rhs.len) - (lhs.len < rhs.len);
+}
+
+/// Is the byte index the start of a UTF-8 sequence (or the end of the string)?
+static inline bool torch_str_is_char_boundary(torch_str s, int64_t i) {
+    return i == s.len || (i >= 0 && i < s.len && ((unsigned char)s.ptr[i] >> 6) != 0x2);
+}
+/// `s[i]`, the byte.
+static inline char *torch_str_at(torch_str s, int64_t i, torch_pos here) {
+    torch_bounds(i, s.len, here);
+    return &s.ptr[i];
+}
+/// `s[lo..hi]`, a copy of the bytes (`TORCH_BOUNDS_CHECKS` checks the char boundaries).
+static inline torch_str torch_str_sub(torch_str s, int64_t lo, int64_t hi, torch_pos here) {
+    torch_bounds_range(lo, hi, s.len, here);
+#ifdef TORCH_BOUNDS_CHECKS
+    const int64_t bounds[2] = {lo, hi};
+    for (int i = 0; i < 2; ++i) {
+        if (!torch_str_is_char_boundary(s, bounds[i])) {
+            char msg[96];
+            snprintf(msg, sizeof(msg), "byte index %" PRId64 " is not a char boundary",
+                     bounds[i]);
+            torch_panic_at(msg, here);
+        }
+    }
+#endif
+    torch_str out = torch_str_alloc(hi - lo);
+    if (hi > lo) memcpy(out.ptr, s.ptr + lo, (size_t)(hi - lo));
+    return out;
+}
+/// `s[lo..]`
+static inline torch_str torch_str_tail(torch_str s, int64_t lo, torch_pos here) {
+    return torch_str_sub(s, lo, s.len, here);
+}
+
+/// Text representation of the values of the interpolations (`"...{expr}..."`).
+static inline torch_str torch_str_id(torch_str value) { return value; }
+static inline torch_str torch_str_from_bool(bool value) {
+    return value ? TORCH_STR("true") : TORCH_STR("false");
+}
+static inline torch_str torch_str_from_char(uint_least32_t value) {
+    char bytes[4];
+    int64_t len;
+    if (value < 0x80) {
+        bytes[0] = (char)value;
+        len = 1;
+    } else if (value < 0x800) {
+        bytes[0] = (char)(0xC0 | (value >> 6));
+        bytes[1] = (char)(0x80 | (value & 0x3F));
+        len = 2;
+    } else if (value < 0x10000) {
+        bytes[0] = (char)(0xE0 | (value >> 12));
+        bytes[1] = (char)(0x80 | ((value >> 6) & 0x3F));
+        bytes[2] = (char)(0x80 | (value & 0x3F));
+        len = 3;
+    } else {
+        bytes[0] = (char)(0xF0 | (value >> 18));
+        bytes[1] = (char)(0x80 | ((value >> 12) & 0x3F));
+        bytes[2] = (char)(0x80 | ((value >> 6) & 0x3F));
+        bytes[3] = (char)(0x80 | (value & 0x3F));
+        len = 4;
+    }
+    torch_str s = torch_str_alloc(len);
+    memcpy(s.ptr, bytes, (size_t)len);
+    return s;
+}
+static inline torch_str torch_str_from_i64(int64_t value) {
+    char bytes[32];
+    const int len = snprintf(bytes, sizeof(bytes), "%" PRId64, value);
+    torch_str s = torch_str_alloc(len);
+    memcpy(s.ptr, bytes, (size_t)len);
+    return s;
+}
+static inline torch_str torch_str_from_u64(uint64_t value) {
+    char bytes[32];
+    const int len = snprintf(bytes, sizeof(bytes), "%" PRIu64, value);
+    torch_str s = torch_str_alloc(len);
+    memcpy(s.ptr, bytes, (size_t)len);
+    return s;
+}
+static inline torch_str torch_str_from_f64(double value) {
+    char bytes[32];
+    const int len = snprintf(bytes, sizeof(bytes), "%g", value);
+    torch_str s = torch_str_alloc(len);
+    memcpy(s.ptr, bytes, (size_t)len);
+    return s;
+}
+/// Numbers and strings, the generated code converts the chars and bools itself
+/// (C has no `char32_t` nor `bool` distinct from the integers of the comparisons).
+#define torch_to_str(value)                                                          \
+    _Generic((value),                                                                \
+        torch_str: torch_str_id,                                                     \
+        float: torch_str_from_f64,                                                   \
+        double: torch_str_from_f64,                                                  \
+        unsigned char: torch_str_from_u64,                                           \
+        unsigned short: torch_str_from_u64,                                          \
+        unsigned int: torch_str_from_u64,                                            \
+        unsigned long: torch_str_from_u64,                                           \
+        unsigned long long: torch_str_from_u64,                                      \
+        default: torch_str_from_i64)(value)
+
+/// `"...{expr}..."` of `count` pieces already converted to strings.
+static inline torch_str torch_format(int count, ...) {
+    va_list pieces;
+    int64_t len = 0;
+    va_start(pieces, count);
+    for (int i = 0; i < count; ++i) len += va_arg(pieces, torch_str).len;
+    va_end(pieces);
+
+    torch_str out = torch_str_alloc(len);
+    int64_t at = 0;
+    va_start(pieces, count);
+    for (int i = 0; i < count; ++i) {
+        const torch_str piece = va_arg(pieces, torch_str);
+        if (piece.len) memcpy(out.ptr + at, piece.ptr, (size_t)piece.len);
+        at += piece.len;
+    }
+    va_end(pieces);
+    return out;
+}
@@ -0,0 +1,109 @@
+use torchc_lits::lits;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Planes with grapheme cluster break properties other than `Other` (_BMP, SMP, SIP,
+/// TIP and SSP_).
+const PLANES: [(u32, u32); 2] = [(0x0, 0x3FFFF), (0xE0000, 0xEFFFF)];
+
+/// Grapheme cluster break properties that the runtime cannot compute by itself.
+///
+/// ---
+/// > _`CR`, `LF`, `ZWJ`, `Regional_Indicator` and the Hangul syllables are resolved by
+/// > the runtime, the others are probed with the same segmentation as the lexer so that
+/// > both agree on what a grapheme is._
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum Property {
+    Other,
+    Control,
+    Extend,
+    Prepend,
+    Pictographic,
+}
+impl Property {
+    fn of(c: char, buf: &mut String) -> Self {
+        let mut one = |chars: &[char]| -> bool {
+            buf.clear();
+            buf.extend(chars);
+            buf.graphemes(true).count() == 1
+        };
+
+        // Nothing is attached to the controls, not even an extension.
+        if !one(&[c, '\u{308}']) {
+            Property::Control
+        } else if one(&['a', c]) {
+            Property::Extend
+        } else if one(&[c, 'a']) {
+            Property::Prepend
+        } else if one(&['\u{1F600}', '\u{200D}', c]) {
+            Property::Pictographic
+        } else {
+            Property::Other
+        }
+    }
+    fn cgen(&self) -> &str {
+        match self {
+            Property::Other => "other",
+            Property::Control => "control",
+            Property::Extend => "extend",
+            Property::Prepend => "prepend",
+            Property::Pictographic => "pictographic",
+        }
+    }
+}
+
+/// Generate the C++ table of grapheme cluster break properties of the runtime.
+pub fn cgen() -> String {
+    let mut cpp: String = format!(
+        "// Grapheme cluster break properties of the Torch runtime.\n\
+         //\n\
+         // Generated by `torchc` from `unicode-segmentation`, do not edit.\n\
+         #pragma once\n\n\
+         #include <cstdint>\n\n\
+         namespace {}::unicode {{\n\n\
+         enum class gcb : std::uint8_t {{ other, control, extend, prepend, pictographic }};\n\n\
+         struct gcb_range {{\n    char32_t lo;\n    char32_t hi;\n    gcb property;\n}};\n\n\
+         inline constexpr gcb_range GCB[] = {{\n",
+        lits::cgen::NAMESPACE
+    );
+
+    let mut buf: String = String::new();
+    let mut range: Option<(u32, u32, Property)> = None;
+    let push = |cpp: &mut String, range: (u32, u32, Property)| {
+        cpp.push_str(&format!(
+            "    {{0x{:X}, 0x{:X}, gcb::{}}},\n",
+            range.0,
+            range.1,
+            range.2.cgen()
+        ));
+    };
+    for (lo, hi) in PLANES {
+        for c in (lo..=hi).filter_map(char::from_u32) {
+            let property: Property = Property::of(c, &mut buf);
+            range = match range {
+                // Consecutive code points with the same property.
+                Some((lo, hi, last)) if last == property && hi + 1 == c as u32 => {
+                    Some((lo, c as u32, last))
+                }
+                last => {
+                    if let Some(last) = last {
+                        if last.2 != Property::Other {
+                            push(&mut cpp, last);
+                        }
+                    }
+                    Some((c as u32, c as u32, property))
+                }
+            };
+        }
+    }
+    if let Some(last) = range {
+        if last.2 != Property::Other {
+            push(&mut cpp, last);
+        }
+    }
+
+    cpp.push_str("};\n\n} // namespace ");
+    cpp.push_str(lits::cgen::NAMESPACE);
+    cpp.push_str("::unicode\n");
+    cpp
+}
@@ -1,4 +1,5 @@
-use std::env::{self, Args};
+use colored::Colorize;
+use std::{env, vec::IntoIter};
 use torchc_lits::lits;
 
 /// _**Command-line Interface**_
@@ -6,52 +7,323 @@ use torchc_lits::lits;
 pub struct Cli {
     pub cmd: Option<Box<[u8]>>,
     pub subcmd: Option<Subcmd>,
+    /// `--backend c|cpp`, it overrides the one of the manifest.
+    pub backend: Option<String>,
+    /// `-p name` or `--package name`, the package of the workspace.
+    pub package: Option<String>,
+    /// `-j N` or `--jobs N`, the scripts and the compilations in parallel.
+    pub jobs: Option<String>,
+    /// `--release`, the optimized build of `torch run`.
+    pub release: bool,
+    /// `--target-dir dir`, the folder of `.target/` and `target/` instead of the root
+    /// of the project.
+    pub target_dir: Option<String>,
+    /// `--color auto|always|never`
+    pub color: Color,
+    /// `--quiet` or `--verbose`
+    pub verbosity: Verbosity,
+    /// `torch run -- args`, the arguments of the executable.
+    pub args: Vec<String>,
 }
 impl Cli {
     pub fn parse() -> Self {
-        let mut args: Args = env::args();
-        Self {
-            // `torch ...`
-            //  ^^^^^
-            cmd: match args.next() {
-                Some(cmd) if !cmd.is_empty() => Some(cmd.into_bytes().into_boxed_slice()),
-                _ => None,
-            },
-            subcmd: {
-                Some(match args.next() {
-                    Some(subcmd) => match subcmd.to_ascii_lowercase().as_str() {
-                        // `torch build`
-                        //        ^^^^^
-                        lits::cli::BUILD => Subcmd::Build(match args.next() {
-                            Some(nested_subcmd) => {
-                                match nested_subcmd.to_ascii_lowercase().as_str() {
-                                    // `torch build run`
-                                    //              ^^^
-                                    lits::cli::RUN => Some(NestedSubcmdForBuild::Run),
+        Self::from_args(env::args().collect())
+    }
 
-                                    _ => panic!("illegal subcommand for build"),
-                                }
-                            }
-                            None => None, // `torch build`
-                        }),
+    /// Parse the command line, the flags can be before or after the subcommand.
+    ///
+    /// ---
+    /// > _The errors are launched with `panic!(...)`, with the closest subcommand or
+    /// > flag of a misspelled one._
+    pub fn from_args(args: Vec<String>) -> Self {
+        let mut args: IntoIter<String> = args.into_iter();
+        // `torch ...`
+        //  ^^^^^
+        let cmd: Option<Box<[u8]>> = match args.next() {
+            Some(cmd) if !cmd.is_empty() => Some(cmd.into_bytes().into_boxed_slice()),
+            _ => None,
+        };
 
-                        // `torch run`
-                        //        ^^^
-                        lits::cli::RUN => Subcmd::Run,
+        let mut positionals: Vec<String> = vec![];
+        let mut flags: Vec<(&Flag, Option<String>)> = vec![];
+        let mut program: Option<Vec<String>> = None;
+        while let Some(arg) = args.next() {
+            // `torch run -- args`
+            //            ^^
+            if arg == lits::cli::ARGS {
+                program = Some(args.by_ref().collect());
+                break;
+            }
+            if !arg.starts_with('-') || arg == "-" {
+                positionals.push(arg);
+                continue;
+            }
 
-                        // `torch manual`
-                        //        ^^^^^^
-                        lits::cli::MANUAL => Subcmd::Manual,
+            // `--jobs=4` or `-j4`
+            let (name, attached): (&str, Option<&str>) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None if !arg.starts_with("--") && arg.len() > 2 => (&arg[..2], Some(&arg[2..])),
+                None => (&arg, None),
+            };
+            let flag: &Flag = FLAGS
+                .iter()
+                .find(|flag| flag.long == name || flag.short == Some(name))
+                .unwrap_or_else(|| {
+                    panic!(
+                        "unexpected flag {}{}",
+                        name.red().bold(),
+                        suggestion(name, FLAGS.iter().map(|flag| flag.long))
+                    )
+                });
+            let value: Option<String> = match (flag.value, attached) {
+                (Some(_), Some(value)) => Some(String::from(value)),
+                (Some(value), None) => Some(args.next().unwrap_or_else(|| {
+                    panic!("missing {} for {}", value.bold(), flag.long.bold())
+                })),
+                (None, Some(_)) => panic!("the flag {} has no value", flag.long.bold()),
+                (None, None) => None,
+            };
+            flags.push((flag, value));
+        }
+        let has = |name: &str| flags.iter().any(|(flag, _)| flag.long == name);
+        let value = |name: &str| {
+            flags
+                .iter()
+                .rev()
+                .find(|(flag, _)| flag.long == name)
+                .and_then(|(_, value)| value.clone())
+        };
 
-                        _ => panic!("illegal subcommand"),
-                    },
-                    None => Subcmd::Version, // `torch`
-                })
+        let mut positionals: IntoIter<String> = positionals.into_iter();
+        let name: Option<String> = positionals.next().map(|name| name.to_ascii_lowercase());
+        if let Some(name) = &name {
+            if !SUBCMDS.iter().any(|subcmd| subcmd.name == name) && name != lits::cli::HELP {
+                panic!(
+                    "illegal subcommand {}{}",
+                    name.red().bold(),
+                    suggestion(name, SUBCMDS.iter().map(|subcmd| subcmd.name))
+                );
+            }
+        }
+        // `torch build --help`
+        if has(lits::cli::HELP_FLAG) {
+            return Self::manual(cmd, name);
+        }
+
+        let subcmd: Subcmd = match name.as_deref() {
+            // `torch build` or `torch build run`
+            //        ^^^^^              ^^^
+            Some(lits::cli::BUILD) => Subcmd::Build(match positionals.next() {
+                Some(nested_subcmd) if nested_subcmd.eq_ignore_ascii_case(lits::cli::RUN) => {
+                    Some(NestedSubcmdForBuild::Run)
+                }
+                Some(nested_subcmd) => panic!(
+                    "illegal subcommand {} for {}{}",
+                    nested_subcmd.red().bold(),
+                    lits::cli::BUILD.bold(),
+                    suggestion(&nested_subcmd, [lits::cli::RUN].into_iter())
+                ),
+                None => None,
+            }),
+            Some(lits::cli::RUN) => Subcmd::Run,
+            // `torch manual build`
+            //               ^^^^^
+            Some(lits::cli::MANUAL | lits::cli::HELP) => {
+                return Self::manual(cmd, positionals.next());
+            }
+            Some(lits::cli::BINDGEN) => Subcmd::Bindgen,
+            // `torch demangle _T4main4half ...`
+            //                 ^^^^^^^^^^^^
+            Some(lits::cli::DEMANGLE) => Subcmd::Demangle(positionals.by_ref().collect()),
+            Some(lits::cli::CLEAN) => Subcmd::Clean {
+                dev: has(lits::cli::DEV),
+                release: has(lits::cli::RELEASE),
+                dry_run: has(lits::cli::DRY_RUN),
+            },
+            // `torch new name`
+            //            ^^^^
+            Some(lits::cli::NEW) => Subcmd::New(
+                positionals
+                    .next()
+                    .unwrap_or_else(|| panic!("missing name for {}", lits::cli::NEW.bold())),
+            ),
+            Some(lits::cli::INIT) => Subcmd::Init,
+            // `torch` or `torch version --json`
+            _ => Subcmd::Version {
+                json: has(lits::cli::JSON),
             },
+        };
+        let name: &str = name.as_deref().unwrap_or(lits::cli::VERSION);
+        if let Some(arg) = positionals.next() {
+            panic!(
+                "unexpected argument {} for {}",
+                arg.red().bold(),
+                format!("torch {}", name).bold()
+            );
+        }
+        for (flag, _) in &flags {
+            if !flag.subcmds.is_empty() && !flag.subcmds.contains(&name) {
+                panic!(
+                    "the flag {} is not a flag of {}",
+                    flag.long.red().bold(),
+                    format!("torch {}", name).bold()
+                );
+            }
+        }
+        let runs: bool = matches!(
+            subcmd,
+            Subcmd::Run | Subcmd::Build(Some(NestedSubcmdForBuild::Run))
+        );
+        if program.is_some() && !runs {
+            panic!(
+                "the arguments after {} are for the executable of {}",
+                lits::cli::ARGS.bold(),
+                format!("torch {}", lits::cli::RUN).bold()
+            );
+        }
+
+        Self {
+            cmd,
+            subcmd: Some(subcmd),
+            backend: value(lits::cli::BACKEND),
+            package: value(lits::cli::PACKAGE),
+            jobs: value(lits::cli::JOBS),
+            release: has(lits::cli::RELEASE),
+            target_dir: value(lits::cli::TARGET_DIR),
+            color: match value(lits::cli::COLOR).as_deref() {
+                None | Some(lits::cli::AUTO) => Color::Auto,
+                Some(lits::cli::ALWAYS) => Color::Always,
+                Some(lits::cli::NEVER) => Color::Never,
+                Some(color) => panic!(
+                    "illegal color {}, expected {}, {} or {}",
+                    color.red().bold(),
+                    lits::cli::AUTO.bold(),
+                    lits::cli::ALWAYS.bold(),
+                    lits::cli::NEVER.bold()
+                ),
+            },
+            verbosity: match (has(lits::cli::QUIET), has(lits::cli::VERBOSE)) {
+                (true, true) => panic!(
+                    "the flags {} and {} are exclusive",
+                    lits::cli::QUIET.bold(),
+                    lits::cli::VERBOSE.bold()
+                ),
+                (true, false) => Verbosity::Quiet,
+                (false, true) => Verbosity::Verbose,
+                (false, false) => Verbosity::Normal,
+            },
+            args: program.unwrap_or_default(),
+        }
+    }
+
+    /// `torch manual`, with the manual of the subcommand if there is one.
+    fn manual(cmd: Option<Box<[u8]>>, subcmd: Option<String>) -> Self {
+        if let Some(name) = &subcmd {
+            if !SUBCMDS.iter().any(|subcmd| subcmd.name == name) {
+                panic!(
+                    "illegal subcommand {}{}",
+                    name.red().bold(),
+                    suggestion(name, SUBCMDS.iter().map(|subcmd| subcmd.name))
+                );
+            }
+        }
+        Self {
+            cmd,
+            subcmd: Some(Subcmd::Manual(subcmd)),
+            backend: None,
+            package: None,
+            jobs: None,
+            release: false,
+            target_dir: None,
+            color: Color::Auto,
+            verbosity: Verbosity::Normal,
+            args: vec![],
         }
     }
 }
 
+/// Manual of the command-line interface or of a subcommand, generated from the
+/// subcommands and flags.
+pub fn manual(subcmd: Option<&str>) -> String {
+    let mut manual: String = String::new();
+    let flags: Vec<&Flag> = match subcmd.and_then(|name| {
+        SUBCMDS
+            .iter()
+            .find(|subcmd| subcmd.name.eq_ignore_ascii_case(name))
+    }) {
+        Some(subcmd) => {
+            manual.push_str(&format!(
+                "{}\n\n{} torch {}{}{} [flags]\n",
+                subcmd.help,
+                "Usage:".bold(),
+                subcmd.name,
+                if subcmd.args.is_empty() { "" } else { " " },
+                subcmd.args
+            ));
+            FLAGS
+                .iter()
+                .filter(|flag| flag.subcmds.is_empty() || flag.subcmds.contains(&subcmd.name))
+                .collect()
+        }
+        None => {
+            manual.push_str(&format!(
+                "Compiler of the Torch language\n\n{} torch [subcommand] [flags]\n\n{}\n",
+                "Usage:".bold(),
+                "Subcommands:".bold()
+            ));
+            let usages: Vec<String> = SUBCMDS
+                .iter()
+                .map(|subcmd| format!("{} {}", subcmd.name, subcmd.args))
+                .collect();
+            let width: usize = usages.iter().map(String::len).max().unwrap_or_default();
+            for (subcmd, usage) in SUBCMDS.iter().zip(&usages) {
+                manual.push_str(&format!(
+                    "    {:width$}  {}\n",
+                    usage.trim_end(),
+                    subcmd.help,
+                    width = width
+                ));
+            }
+            FLAGS.iter().collect()
+        }
+    };
+
+    manual.push_str(&format!("\n{}\n", "Flags:".bold()));
+    let usages: Vec<String> = flags
+        .iter()
+        .map(|flag| {
+            format!(
+                "{}{}{}",
+                match flag.short {
+                    Some(short) => format!("{}, ", short),
+                    None => String::from("    "),
+                },
+                flag.long,
+                match flag.value {
+                    Some(value) => format!(" {}", value),
+                    None => String::new(),
+                }
+            )
+        })
+        .collect();
+    let width: usize = usages.iter().map(String::len).max().unwrap_or_default();
+    for (flag, usage) in flags.iter().zip(&usages) {
+        manual.push_str(&format!(
+            "    {:width$}  {}\n",
+            usage,
+            flag.help,
+            width = width
+        ));
+    }
+    if subcmd.is_none() {
+        manual.push_str(&format!(
+            "\nSee {} for the manual of a subcommand.\n",
+            "torch manual <subcommand>".bold()
+        ));
+    }
+    manual
+}
+
 /// CLI subcommands.
 #[derive(Debug)]
 #[repr(u8)]
@@ -60,10 +332,30 @@ pub enum Subcmd {
     Build(Option<NestedSubcmdForBuild>),
     /// Build and run the hidden development executable.
     Run,
-    /// Advise on the use of the command-line interface.
-    Manual,
-    /// Displays language version information.
-    Version,
+    /// Generate the Torch bindings of the C headers of `src/`.
+    Bindgen,
+    /// Demangle the symbols of the arguments or, without them, of the standard input.
+    Demangle(Vec<String>),
+    /// Create a package in a new folder with the name.
+    New(String),
+    /// Create a package in the current folder.
+    Init,
+    /// Remove the generated files of the development (`--dev`) and release
+    /// (`--release`) builds, by default both.
+    Clean {
+        dev: bool,
+        release: bool,
+        /// `--dry-run`, list them without removing them.
+        dry_run: bool,
+    },
+    /// Advise on the use of the command-line interface, or of the subcommand.
+    Manual(Option<String>),
+    /// Displays the versions of the compiler and of the language, the commit it was
+    /// built from and the C/C++ compilers.
+    Version {
+        /// `--json`, for the tools.
+        json: bool,
+    },
 }
 /// Subcommands of the `Build` subcommand.
 #[derive(Debug)]
@@ -72,3 +364,217 @@ pub enum NestedSubcmdForBuild {
     /// Run the release executable from the `target/` folder.
     Run,
 }
+
+/// `--color`, by default the colors are used in the terminals.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Color {
+    Auto,
+    Always,
+    Never,
+}
+/// Messages of the compiler besides the diagnostics.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Verbosity {
+    /// `--quiet`, without the status messages (`Compiled ...`).
+    Quiet,
+    Normal,
+    /// `--verbose`, with the commands of the C/C++ compilers.
+    Verbose,
+}
+
+/// Subcommand of the manual.
+struct Command {
+    name: &'static str,
+    /// Arguments of its usage (`[run]`).
+    args: &'static str,
+    help: &'static str,
+}
+const SUBCMDS: [Command; 9] = [
+    Command {
+        name: lits::cli::BUILD,
+        args: "[run] [-- args]",
+        help: "Build the release executable in the target/ folder, and run it",
+    },
+    Command {
+        name: lits::cli::RUN,
+        args: "[-- args]",
+        help: "Build and run the development executable",
+    },
+    Command {
+        name: lits::cli::NEW,
+        args: "<name>",
+        help: "Create a package in a new folder",
+    },
+    Command {
+        name: lits::cli::INIT,
+        args: "",
+        help: "Create a package in the current folder",
+    },
+    Command {
+        name: lits::cli::CLEAN,
+        args: "",
+        help: "Remove the generated files of the project",
+    },
+    Command {
+        name: lits::cli::BINDGEN,
+        args: "",
+        help: "Generate the Torch bindings of the C headers of src/",
+    },
+    Command {
+        name: lits::cli::DEMANGLE,
+        args: "[symbols...]",
+        help: "Demangle the symbols, or the ones of the standard input",
+    },
+    Command {
+        name: lits::cli::MANUAL,
+        args: "[subcommand]",
+        help: "Print this manual, or the one of a subcommand (help)",
+    },
+    Command {
+        name: lits::cli::VERSION,
+        args: "",
+        help: "Print the version, the commit and the C/C++ compilers (the default)",
+    },
+];
+
+/// Flag of the command line, with the subcommands that accept it (all of them
+/// without subcommands).
+struct Flag {
+    long: &'static str,
+    short: Option<&'static str>,
+    /// Name of its value (`--jobs N`), `None` for the switches.
+    value: Option<&'static str>,
+    subcmds: &'static [&'static str],
+    help: &'static str,
+}
+const FLAGS: [Flag; 13] = [
+    Flag {
+        long: lits::cli::RELEASE,
+        short: None,
+        value: None,
+        subcmds: &[lits::cli::BUILD, lits::cli::RUN, lits::cli::CLEAN],
+        help: "Optimized build of run; only the release files of clean",
+    },
+    Flag {
+        long: lits::cli::DEV,
+        short: None,
+        value: None,
+        subcmds: &[lits::cli::CLEAN],
+        help: "Only the development files",
+    },
+    Flag {
+        long: lits::cli::DRY_RUN,
+        short: None,
+        value: None,
+        subcmds: &[lits::cli::CLEAN],
+        help: "List the files without removing them",
+    },
+    Flag {
+        long: lits::cli::PACKAGE,
+        short: Some(lits::cli::PACKAGE_SHORT),
+        value: Some("<name>"),
+        subcmds: &[lits::cli::BUILD, lits::cli::RUN, lits::cli::BINDGEN],
+        help: "Package of the workspace",
+    },
+    Flag {
+        long: lits::cli::BACKEND,
+        short: None,
+        value: Some("c|cpp"),
+        subcmds: &[lits::cli::BUILD, lits::cli::RUN],
+        help: "Backend of the generated code, it overrides the one of torch.toml",
+    },
+    Flag {
+        long: lits::cli::JOBS,
+        short: Some(lits::cli::JOBS_SHORT),
+        value: Some("<N>"),
+        subcmds: &[lits::cli::BUILD, lits::cli::RUN],
+        help: "Scripts generated and compiled at the same time, by default the CPUs",
+    },
+    Flag {
+        long: lits::cli::TARGET_DIR,
+        short: None,
+        value: Some("<dir>"),
+        subcmds: &[lits::cli::BUILD, lits::cli::RUN, lits::cli::CLEAN],
+        help: "Folder of .target/ and target/, by default the root of the project",
+    },
+    Flag {
+        long: lits::cli::COLOR,
+        short: None,
+        value: Some("auto|always|never"),
+        subcmds: &[],
+        help: "Colors of the messages, by default in the terminals",
+    },
+    Flag {
+        long: lits::cli::QUIET,
+        short: Some(lits::cli::QUIET_SHORT),
+        value: None,
+        subcmds: &[],
+        help: "Without the status messages",
+    },
+    Flag {
+        long: lits::cli::VERBOSE,
+        short: Some(lits::cli::VERBOSE_SHORT),
+        value: None,
+        subcmds: &[],
+        help: "With the commands of the C/C++ compilers",
+    },
+    Flag {
+        long: lits::cli::HELP_FLAG,
+        short: Some(lits::cli::HELP_SHORT),
+        value: None,
+        subcmds: &[],
+        help: "Print the manual",
+    },
+    Flag {
+        long: lits::cli::ARGS,
+        short: None,
+        value: Some("<args>..."),
+        subcmds: &[lits::cli::BUILD, lits::cli::RUN],
+        help: "Arguments of the executable of run",
+    },
+    Flag {
+        long: lits::cli::JSON,
+        short: None,
+        value: None,
+        subcmds: &[lits::cli::VERSION],
+        help: "Version information in JSON",
+    },
+];
+
+/// ` , did you mean build?` if a candidate is close to the word.
+fn suggestion<'s>(word: &str, candidates: impl Iterator<Item = &'s str>) -> String {
+    let word: String = word.trim_start_matches('-').to_ascii_lowercase();
+    candidates
+        .map(|candidate| {
+            (
+                distance(&word, candidate.trim_start_matches('-')),
+                candidate,
+            )
+        })
+        .filter(|(distance, candidate)| {
+            // One edit in 3 characters, and a transposition (`buidl`).
+            *distance <= candidate.trim_start_matches('-').len() / 3 + 1
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!(", did you mean {}?", candidate.bold()))
+        .unwrap_or_default()
+}
+/// Edit distance (Levenshtein) of the words.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current: Vec<usize> = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            current.push(
+                (previous[j] + usize::from(a != *b))
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
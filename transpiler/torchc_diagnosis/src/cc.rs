@@ -0,0 +1,168 @@
+/// Severity of the diagnostics of the C/C++ compilers.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Severity {
+    /// `error`, `fatal error`
+    Error,
+    Warning,
+    Note,
+}
+
+/// Markers of the diagnostics after their location (`file:line:column: error: ...`).
+const MARKERS: [(&str, Severity); 4] = [
+    (": fatal error: ", Severity::Error),
+    (": error: ", Severity::Error),
+    (": warning: ", Severity::Warning),
+    (": note: ", Severity::Note),
+];
+
+/// Diagnostic of GCC/Clang.
+///
+/// ---
+/// `file:line:column: severity: message`
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// File as the compiler shows it, a script (`src/file.t`) with the `#line` of the
+    /// generated code.
+    pub file: String,
+    pub line: usize,
+    /// `0` when the compiler does not show it.
+    pub column: usize,
+    pub severity: Severity,
+    pub msg: String,
+}
+
+/// Diagnostics of the output of the compiler.
+///
+/// ---
+/// > _The context lines (`In function ...`, `In file included from ...`) and the excerpts
+/// > of the code are omitted, so are the lines of the linker._
+pub fn parse(stderr: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+    for line in stderr.lines() {
+        for (marker, severity) in MARKERS {
+            let Some(at) = line.find(marker) else {
+                continue;
+            };
+            if let Some((file, line_n, column)) = location(&line[..at]) {
+                diagnostics.push(Diagnostic {
+                    file,
+                    line: line_n,
+                    column,
+                    severity,
+                    msg: String::from(&line[at + marker.len()..]),
+                });
+            }
+            break;
+        }
+    }
+    diagnostics
+}
+
+/// Lines of the output that are not diagnostics, contexts nor excerpts of the code
+/// (the linker errors, ...).
+pub fn others(stderr: &str) -> Vec<&str> {
+    stderr
+        .lines()
+        .filter(|line| {
+            let excerpt: bool = line.starts_with(' ') && line.contains('|');
+            // `In file included from a.h:1,` and its `                 from m.c:1:`
+            let context: bool = line.starts_with("In file included from ")
+                || line.trim_start().starts_with("from ")
+                || line.ends_with(':')
+                    && [": In ", ": At ", "required from "]
+                        .iter()
+                        .any(|context| line.contains(context));
+            let diagnostic: bool = MARKERS.iter().any(|(marker, _)| {
+                line.find(marker)
+                    .is_some_and(|at| location(&line[..at]).is_some())
+            });
+            !excerpt && !context && !diagnostic && !line.trim().is_empty()
+        })
+        .collect()
+}
+
+/// `file:line:column` or `file:line`
+fn location(location: &str) -> Option<(String, usize, usize)> {
+    let parts: Vec<&str> = location.rsplitn(3, ':').collect();
+    match parts.as_slice() {
+        [column, line, file]
+            if column.parse::<usize>().is_ok() && line.parse::<usize>().is_ok() =>
+        {
+            Some((
+                String::from(*file),
+                line.parse().ok()?,
+                column.parse().ok()?,
+            ))
+        }
+        _ => {
+            let (file, line) = location.rsplit_once(':')?;
+            Some((String::from(file), line.parse().ok()?, 0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GCC: &str = "\
+In file included from a.h:1,
+                 from src/main.t:1:
+b.h: In function 'f':
+b.h:1:18: error: 'x' undeclared (first use in this function)
+    1 | int f() { return x; }
+      |                  ^
+src/main.t:2:10: fatal error: nope.h: No such file or directory
+    2 | #include \"nope.h\"
+      |          ^~~~~~~~
+compilation terminated.
+";
+    const CLANG: &str = "\
+In file included from src/main.t:1:
+src/main.t:4:5: warning: unused variable 'y' [-Wunused-variable]
+    4 |     int y;
+      |         ^
+src/main.t:7: note: declared here
+/usr/bin/ld: main.o: in function `main':
+main.cpp:(.text+0xa): undefined reference to `h'
+clang: error: linker command failed with exit code 1 (use -v to see invocation)
+1 warning generated.
+";
+
+    #[test]
+    fn gcc() {
+        let diagnostics: Vec<Diagnostic> = parse(GCC);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "b.h");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 18));
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].file, "src/main.t");
+        assert_eq!((diagnostics[1].line, diagnostics[1].column), (2, 10));
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(diagnostics[1].msg, "nope.h: No such file or directory");
+        assert_eq!(others(GCC), ["compilation terminated."]);
+    }
+
+    #[test]
+    fn clang() {
+        let diagnostics: Vec<Diagnostic> = parse(CLANG);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].msg,
+            "unused variable 'y' [-Wunused-variable]"
+        );
+        assert_eq!((diagnostics[1].line, diagnostics[1].column), (7, 0));
+        assert_eq!(diagnostics[1].severity, Severity::Note);
+        assert_eq!(
+            others(CLANG),
+            [
+                "/usr/bin/ld: main.o: in function `main':",
+                "main.cpp:(.text+0xa): undefined reference to `h'",
+                "clang: error: linker command failed with exit code 1 (use -v to see invocation)",
+                "1 warning generated.",
+            ]
+        );
+    }
+}
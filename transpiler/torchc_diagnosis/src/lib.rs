@@ -1,8 +1,10 @@
+pub mod cc;
+
 pub mod panic {
     use async_std::path::Path;
     use colored::Colorize;
     use pathdiff::diff_paths;
-    use std::sync::Once;
+    use std::{any::Any, cell::Cell, panic::AssertUnwindSafe, sync::Once};
     use torchc_lex::{Pos, Table};
     use torchc_lits::lits;
     use torchc_script::{
@@ -10,42 +12,63 @@ pub mod panic {
         Script,
     };
 
+    thread_local! {
+        /// The panics of the thread are caught by [`catch`], they are not printed.
+        static CATCHING: Cell<bool> = const { Cell::new(false) };
+    }
+
     /// Default configuration of the general error diagnoser using `panic!(...)`.
     #[inline(always)]
     pub fn default() {
         static ONCE: Once = Once::new();
         ONCE.call_once(|| {
             std::panic::set_hook(Box::new(|panic_info| {
-                if let Some(err) = panic_info.payload().downcast_ref::<&str>() {
-                    return eprintln!(
+                if CATCHING.with(Cell::get) {
+                    return;
+                }
+                match message(panic_info.payload()) {
+                    Some(err) => eprintln!(
                         "{}{} {}",
                         lits::EPREFIX.red().bold(),
                         lits::COLON.bold(),
                         err
-                    );
-                }
-                if let Some(err) = panic_info.payload().downcast_ref::<String>() {
-                    return eprintln!(
-                        "{}{} {}",
+                    ),
+                    #[cfg(debug_assertions)]
+                    None => eprintln!(
+                        "{}{} the type in {}{}{} has no support",
                         lits::EPREFIX.red().bold(),
                         lits::COLON.bold(),
-                        err
-                    );
+                        "panic_info.payload().downcast_ref::<".bold(),
+                        "T".red().bold(),
+                        ">()".bold()
+                    ),
+                    #[cfg(not(debug_assertions))]
+                    None => {}
                 }
-
-                #[cfg(debug_assertions)]
-                eprintln!(
-                    "{}{} the type in {}{}{} has no support",
-                    lits::EPREFIX.red().bold(),
-                    lits::COLON.bold(),
-                    "panic_info.payload().downcast_ref::<".bold(),
-                    "T".red().bold(),
-                    ">()".bold()
-                );
             }))
         });
     }
 
+    /// Run the function of a parallel job, its error diagnostic is the message of the
+    /// panic instead of being printed.
+    ///
+    /// ---
+    /// > _The diagnostics of the jobs are launched again in the order of the jobs, so
+    /// > they do not depend on the scheduling._
+    pub fn catch<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+        CATCHING.with(|catching| catching.set(true));
+        let result: std::thread::Result<R> = std::panic::catch_unwind(AssertUnwindSafe(f));
+        CATCHING.with(|catching| catching.set(false));
+        result.map_err(|payload| message(&*payload).unwrap_or_default())
+    }
+    /// Message of the payload of a `panic!(...)`.
+    fn message(payload: &(dyn Any + Send)) -> Option<String> {
+        if let Some(err) = payload.downcast_ref::<&str>() {
+            return Some(String::from(*err));
+        }
+        payload.downcast_ref::<String>().cloned()
+    }
+
     /// Handles the diagnosis of language errors.
     ///
     /// ---
@@ -61,17 +84,93 @@ pub mod panic {
         /// Script path.
         script: &'diagnosis Path,
         cwd: &'diagnosis Path,
+        /// Folder of the scripts of the package (`src/`), the headers are in it.
+        src: Option<&'diagnosis Path>,
+        /// The script is nested in the one of the path (`"...{expr}..."`), the lines are
+        /// the ones of the file.
+        nested: bool,
     }
     impl<'diagnosis> Diagnosis<'diagnosis> {
         pub fn new(path: &'diagnosis Path, cwd: &'diagnosis Path) -> Self {
-            Self { script: path, cwd }
+            Self {
+                script: path,
+                cwd,
+                src: None,
+                nested: false,
+            }
+        }
+        /// Diagnosis of a script of the `src/` folder of a package.
+        pub fn with_src(self, src: &'diagnosis Path) -> Self {
+            Self {
+                src: Some(src),
+                ..self
+            }
+        }
+        /// Diagnosis of a script nested in the diagnosed one, its positions are the ones
+        /// of the file (see [`Script::offset`]).
+        pub fn nested(&self) -> Self {
+            Self {
+                script: self.script,
+                cwd: self.cwd,
+                src: self.src,
+                nested: true,
+            }
+        }
+        /// Path of the diagnosed script.
+        pub fn path(&self) -> &Path {
+            self.script
+        }
+        /// Folder of the scripts of its package.
+        pub fn src(&self) -> Option<&Path> {
+            self.src
         }
 
         /// Launch an error diagnostic and stop the execution.
-        pub fn diagnosis(&self, msg: &str, pos: Pos, script: &mut Script) {
+        pub fn diagnosis(&self, msg: &str, pos: Pos, script: &mut Script) -> ! {
+            // The excerpt of a nested script is the line of the file.
+            if self.nested {
+                if let Ok(contents) = std::fs::read_to_string(self.script) {
+                    let mut file: Script = Script::from_contents(&contents);
+                    panic!("{}", self.render(lits::EPREFIX, msg, pos, &mut file));
+                }
+            }
+            // Everything is printed until the `panic` is launched.
+            panic!("{}", self.render(lits::EPREFIX, msg, pos, script));
+        }
+        /// Launch an error diagnostic of the script file and stop the execution, for the
+        /// stages after the parser that no longer have its [`Script`] (the emitters).
+        pub fn diagnosis_file(&self, msg: &str, pos: Pos) -> ! {
+            let contents: String = std::fs::read_to_string(self.script).unwrap_or_default();
+            self.diagnosis(msg, pos, &mut Script::from_contents(&contents))
+        }
+        /// Diagnostic of the first token of the line, `None` if the line has no code.
+        ///
+        /// ---
+        /// > _For the C/C++ compilers, their columns are the ones of the generated code._
+        pub fn render_line(
+            &self,
+            prefix: &str,
+            msg: &str,
+            line: usize,
+            script: &mut Script,
+        ) -> Option<String> {
+            script.reset();
+            while let Some(token) = script.token(Next(Feature::Code)) {
+                if token.pos.line == line {
+                    return Some(self.render(prefix, msg, token.pos, script));
+                }
+                if token.pos.line > line {
+                    break;
+                }
+            }
+            None
+        }
+        /// Diagnostic of the position, without the prefix (`error`, `warning`, ...) that
+        /// aligns it.
+        pub fn render(&self, prefix: &str, msg: &str, pos: Pos, script: &mut Script) -> String {
             // `error: message → src/file`
             //       ^^ +2
-            let mut indent: String = " ".repeat(lits::EPREFIX.len() + 2);
+            let mut indent: String = " ".repeat(prefix.len() + 2);
 
             let chunk_1: String = format!(
                 "{} {} {}\n{}{} {} ",
@@ -143,12 +242,9 @@ pub mod panic {
 
             // `1 | error line`
             //   ^^^ +3
-            indent.push_str(
-                &" ".repeat(3 + pos.line.to_string().len() + if i > 1 { i - 1 } else { 0 }),
-            );
+            indent.push_str(&" ".repeat(3 + pos.line.to_string().len() + i.saturating_sub(1)));
 
-            // Everything is printed until the `panic` is launched.
-            panic!(
+            format!(
                 "{}{}{}{}\n{}{} {}",
                 chunk_1,
                 chunk_2,
@@ -158,7 +254,7 @@ pub mod panic {
                 indent,
                 "↑".red().bold(),
                 pos.grapheme,
-            );
+            )
         }
     }
 }
@@ -11,7 +11,7 @@ use torchc_lits::lits;
 /// `.t`, `.c` and/or `.cpp` files.
 pub async fn hike(src: &Path) -> Vec<PathBuf> {
     let mut scripts: Vec<PathBuf> = vec![];
-    let mut entries: WalkDir = WalkDir::new(&src);
+    let mut entries: WalkDir = WalkDir::new(src);
     loop {
         match entries.next().await {
             Some(Ok(entry)) => {
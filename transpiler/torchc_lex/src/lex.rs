@@ -1,6 +1,6 @@
 use torchc_lits::lits;
 
-use super::{Script, Table, Token};
+use super::{Pos, Script, Table, Token};
 
 /// Get the following token from the script.
 ///
@@ -11,6 +11,11 @@ pub fn lexer<'lexer>(script: &mut Script<'lexer>) -> Option<Token> {
     let mut lit: String = String::new();
     let mut prer: bool = false; // The previous character is `\r`?
 
+    // Block of the previous `cpp` keyword.
+    if let Some(indent) = script.raw.take() {
+        return Some(raw(script, indent));
+    }
+
     while let Some(c) = script.peek_char() {
         match c {
             // Skip token(s).
@@ -71,19 +76,82 @@ pub fn lexer<'lexer>(script: &mut Script<'lexer>) -> Option<Token> {
                 // Keyword or identifier.
                 token.lexeme = match lit.as_str() {
                     lits::token_table::FN => Table::Fn,
+                    lits::token_table::VAR => Table::Var,
+                    lits::token_table::CONST => Table::Const,
+                    lits::token_table::EXTERN => Table::Extern,
+                    lits::token_table::EXPORT => Table::Export,
+                    lits::token_table::INCLUDE => Table::Include,
+                    lits::token_table::FOR => Table::For,
+                    lits::token_table::IN => Table::In,
+                    lits::token_table::RETURN => Table::Return,
+                    lits::token_table::BREAK => Table::Break,
+                    lits::token_table::CONTINUE => Table::Continue,
+                    lits::token_table::DEFER => Table::Defer,
+                    // `cpp` alone on its line opens the raw block.
+                    lits::token_table::CPP => {
+                        let rest: String = script.peek_line();
+                        let rest: &str = rest.trim();
+                        if rest.is_empty() || rest.starts_with(lits::token_table::CMT) {
+                            script.raw = Some(token.pos.grapheme);
+                        }
+                        Table::Cpp
+                    }
+                    lits::token_table::TRUE => Table::True,
+                    lits::token_table::FALSE => Table::False,
                     _ => Table::Id(Some(lit.into_bytes().into_boxed_slice())),
                 };
             }
 
+            // Integer or float literal.
+            '0'..='9' => {
+                lit.push(script.next_char().unwrap());
+                token.pos = script.pos;
+                let mut float: bool = false;
+
+                while let Some(c) = script.peek_char().copied() {
+                    match c {
+                        '0'..='9' | '_' => lit.push(script.next_char().unwrap()),
+                        // `1.5` but not `1..5` (range).
+                        '.' if !float && matches!(script.peek_second_char(), Some('0'..='9')) => {
+                            float = true;
+                            lit.push(script.next_char().unwrap());
+                        }
+                        _ => break,
+                    }
+                }
+                let lit: Box<[u8]> = lit.replace('_', "").into_bytes().into_boxed_slice();
+                token.lexeme = if float {
+                    Table::FloatLit(Some(lit))
+                } else {
+                    Table::IntLit(Some(lit))
+                };
+            }
+
             // String literal.
             '"' => {
                 lit.push(script.next_char().unwrap());
                 token.pos = script.pos;
+                // Interpolations (`{expr}`) may contain string literals.
+                let mut interpolation: bool = false;
+                let mut nested: bool = false;
 
                 while let Some(c) = script.next_char() {
                     lit.push(c);
                     match c {
+                        '"' if interpolation => nested = !nested,
                         '"' => break,
+                        // Escape sequence (`\"`, `\\`, ...).
+                        '\\' => {
+                            if let Some(c) = script.next_char() {
+                                lit.push(c);
+                            }
+                        }
+                        // `{{` is the literal brace.
+                        '{' if !interpolation && script.peek_char() == Some(&'{') => {
+                            lit.push(script.next_char().unwrap());
+                        }
+                        '{' if !nested => interpolation = true,
+                        '}' if !nested => interpolation = false,
                         _ => {}
                     }
                 }
@@ -98,6 +166,12 @@ pub fn lexer<'lexer>(script: &mut Script<'lexer>) -> Option<Token> {
                     lit.push(c);
                     match c {
                         '\'' => break,
+                        // Escape sequence (`\'`, `\\`, ...).
+                        '\\' => {
+                            if let Some(c) = script.next_char() {
+                                lit.push(c);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -121,6 +195,50 @@ pub fn lexer<'lexer>(script: &mut Script<'lexer>) -> Option<Token> {
                 token.lexeme = Table::DivisionSym;
             }
 
+            // Symbols.
+            '+' | '-' | '*' | '%' | '(' | ')' | '[' | ']' | ',' | ':' | '?' => {
+                let c: char = script.next_char().unwrap();
+                token.pos = script.pos;
+                token.lexeme = match c {
+                    '+' => Table::AdditionSym,
+                    '-' => Table::SubtractionSym,
+                    '*' => Table::MultiplicationSym,
+                    '%' => Table::RemainderSym,
+                    '(' => Table::OpenParenSym,
+                    ')' => Table::CloseParenSym,
+                    '[' => Table::OpenBracketSym,
+                    ']' => Table::CloseBracketSym,
+                    ',' => Table::CommaSym,
+                    ':' => Table::ColonSym,
+                    _ => Table::QuestionSym,
+                };
+            }
+            // Symbols that can be followed by a second character.
+            '=' | '!' | '<' | '>' | '.' | '&' | '|' => {
+                let c: char = script.next_char().unwrap();
+                token.pos = script.pos;
+                let next: Option<char> = script.peek_char().copied();
+                token.lexeme = match (c, next) {
+                    ('=', Some('=')) => Table::EqualSym,
+                    ('!', Some('=')) => Table::NotEqualSym,
+                    ('<', Some('=')) => Table::LessEqualSym,
+                    ('>', Some('=')) => Table::GreaterEqualSym,
+                    ('.', Some('.')) => Table::RangeSym,
+                    ('&', Some('&')) => Table::AndSym,
+                    ('|', Some('|')) => Table::OrSym,
+                    ('=', _) => Table::AssignSym,
+                    ('!', _) => Table::NotSym,
+                    ('<', _) => Table::LessSym,
+                    ('>', _) => Table::GreaterSym,
+                    ('.', _) => Table::DotSym,
+                    // `&` and `|` alone.
+                    _ => Table::Illegal(Some(c.to_string().into_bytes().into_boxed_slice())),
+                };
+                if token.len() == 2 {
+                    script.next_char().unwrap();
+                }
+            }
+
             // Illegal token.
             _ => {
                 // Advance the position counter (without advancing the iteration of
@@ -138,3 +256,76 @@ pub fn lexer<'lexer>(script: &mut Script<'lexer>) -> Option<Token> {
     }
     None
 }
+
+/// Get the lines indented more than the `cpp` keyword (`indent`) as a single token,
+/// without the common indentation.
+///
+/// ---
+/// > _The blank lines are only kept inside the block, the newline of its last line is
+/// > left for the end of statement._
+fn raw(script: &mut Script<'_>, indent: usize) -> Token {
+    let mut token: Token = Token::new();
+
+    // Rest of the `cpp` line (whitespace or comment).
+    while let Some(c) = script.peek_char() {
+        if *c == '\n' {
+            break;
+        }
+        script.next_char().unwrap();
+    }
+    token.pos = script.pos;
+
+    let mut lines: Vec<String> = vec![];
+    let mut blanks: usize = 0;
+    while script.peek_char() == Some(&'\n') {
+        let line: String = {
+            let mut buf = script.buf.clone();
+            buf.next();
+            buf.take_while(|c| *c != '\n').collect()
+        };
+        let ws: usize = line.chars().take_while(|c| *c == ' ' || *c == '\t').count();
+        let blank: bool = line.trim().is_empty();
+        if !blank && ws < indent {
+            break;
+        }
+
+        script.next_char().unwrap();
+        for _ in line.chars() {
+            script.next_char().unwrap();
+        }
+        if blank {
+            blanks += 1;
+            continue;
+        }
+        if lines.is_empty() {
+            blanks = 0;
+            token.pos = Pos {
+                line: script.pos.line,
+                grapheme: ws + 1,
+            };
+        }
+        for _ in 0..blanks {
+            lines.push(String::new());
+        }
+        blanks = 0;
+        lines.push(String::from(line.trim_end()));
+    }
+
+    let common: usize = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().take_while(|c| *c == ' ' || *c == '\t').count())
+        .min()
+        .unwrap_or(0);
+    // Without lines it is empty, the diagnosis shows it as part of the `cpp` line.
+    token.lexeme = Table::Raw(Some(
+        lines
+            .iter()
+            .map(|line| line.chars().skip(common).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+            .into_bytes()
+            .into_boxed_slice(),
+    ));
+    token
+}
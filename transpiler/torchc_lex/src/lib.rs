@@ -11,6 +11,11 @@ pub struct Token {
     pub lexeme: Table,
     pub pos: Pos,
 }
+impl Default for Token {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl Token {
     pub fn new() -> Self {
         Self {
@@ -27,8 +32,12 @@ impl Token {
     pub fn len(&self) -> usize {
         self.lexeme.len()
     }
+    /// The token has no literal.
+    pub fn is_empty(&self) -> bool {
+        self.lexeme.is_empty()
+    }
     /// Obtain the token literal.
-    pub fn lit(&self) -> Option<Lit> {
+    pub fn lit(&self) -> Option<Lit<'_>> {
         self.lexeme.lit()
     }
 }
@@ -39,14 +48,15 @@ pub struct Pos {
     pub line: usize,
     pub grapheme: usize,
 }
-impl Pos {
-    pub fn default() -> Self {
+impl Default for Pos {
+    fn default() -> Self {
         Self {
             line: 1,
             grapheme: 0,
         }
     }
-
+}
+impl Pos {
     /// It advances according to the unicode character and returns it.
     pub fn advance(&mut self, c: char) -> char {
         if c == '\n' {
@@ -63,12 +73,24 @@ impl Pos {
 pub struct Script<'script> {
     buf: Peekable<Chars<'script>>,
     pub pos: Pos,
+    /// Indentation of the `cpp` keyword whose block is the following token.
+    raw: Option<usize>,
 }
 impl<'script> Script<'script> {
+    /// Gets the rest of the current line from the script but does not advance.
+    pub fn peek_line(&self) -> String {
+        self.buf.clone().take_while(|c| *c != '\n').collect()
+    }
     /// Gets the next character from the script but does not advance.
     pub fn peek_char(&mut self) -> Option<&char> {
         self.buf.peek()
     }
+    /// Gets the character after the next one from the script but does not advance.
+    pub fn peek_second_char(&self) -> Option<char> {
+        let mut buf: Peekable<Chars<'script>> = self.buf.clone();
+        buf.next();
+        buf.next()
+    }
     /// Gets the next character from the script.
     pub fn next_char(&mut self) -> Option<char> {
         match self.buf.next() {
@@ -92,13 +114,14 @@ impl<'script> Script<'script> {
     }
 }
 pub trait ToScript {
-    fn to_script(&self) -> Script;
+    fn to_script(&self) -> Script<'_>;
 }
 impl ToScript for String {
-    fn to_script(&self) -> Script {
+    fn to_script(&self) -> Script<'_> {
         Script {
             buf: self.chars().peekable(),
             pos: Pos::default(),
+            raw: None,
         }
     }
 }
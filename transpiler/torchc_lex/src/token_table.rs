@@ -1,4 +1,5 @@
 use super::{Table::*, Token};
+use core::mem;
 use torchc_lits::{lits, Lit, NonReserved};
 
 #[derive(Debug, Clone)]
@@ -8,78 +9,161 @@ pub enum Table {
     Id(Option<Box<[u8]>>),
     /// `fn`
     Fn,
+    /// `var`
+    Var,
+    /// `const`
+    Const,
+    /// `extern`
+    Extern,
+    /// `export`
+    Export,
+    /// `include`
+    Include,
+    /// `for`
+    For,
+    /// `in`
+    In,
+    /// `return`
+    Return,
+    /// `break`
+    Break,
+    /// `continue`
+    Continue,
+    /// `defer`
+    Defer,
+    /// `cpp`
+    Cpp,
+    /// `true`
+    True,
+    /// `false`
+    False,
     /// `"..."`
     StringLit(Option<Box<[u8]>>),
     /// `'...'`
     CharLit(Option<Box<[u8]>>),
+    /// `123`
+    IntLit(Option<Box<[u8]>>),
+    /// `1.23`
+    FloatLit(Option<Box<[u8]>>),
     /// `\n`
     EndOfStmt,
     /// `' '`<br>`\t`
     Whitespace,
     /// `/`
     DivisionSym,
+    /// `+`
+    AdditionSym,
+    /// `-`
+    SubtractionSym,
+    /// `*`
+    MultiplicationSym,
+    /// `%`
+    RemainderSym,
+    /// `=`
+    AssignSym,
+    /// `==`
+    EqualSym,
+    /// `!=`
+    NotEqualSym,
+    /// `<`
+    LessSym,
+    /// `<=`
+    LessEqualSym,
+    /// `>`
+    GreaterSym,
+    /// `>=`
+    GreaterEqualSym,
+    /// `!`
+    NotSym,
+    /// `&&`
+    AndSym,
+    /// `||`
+    OrSym,
+    /// `(`
+    OpenParenSym,
+    /// `)`
+    CloseParenSym,
+    /// `[`
+    OpenBracketSym,
+    /// `]`
+    CloseBracketSym,
+    /// `,`
+    CommaSym,
+    /// `:`
+    ColonSym,
+    /// `.`
+    DotSym,
+    /// `..`
+    RangeSym,
+    /// `?`
+    QuestionSym,
+    /// Indented lines of the `cpp` block, without tokenizing them.
+    Raw(Option<Box<[u8]>>),
     /// `//...`
     Cmt(Option<Vec<Token>>),
     Illegal(Option<Box<[u8]>>),
 }
-impl Table {
-    pub fn default() -> Self {
+impl Default for Table {
+    fn default() -> Self {
         Illegal(None)
     }
-
+}
+impl Table {
     /// Check what the token identifier is.
     pub fn is(&self, cmp: &Self) -> bool {
-        match self {
-            Id(_) => match cmp {
-                Id(_) => true,
-                _ => false,
-            },
-            Fn => match cmp {
-                Fn => true,
-                _ => false,
-            },
-            EndOfStmt => match cmp {
-                EndOfStmt => true,
-                _ => false,
-            },
-            DivisionSym => match cmp {
-                DivisionSym => true,
-                _ => false,
-            },
-            CharLit(_) => match cmp {
-                CharLit(_) => true,
-                _ => false,
-            },
-            StringLit(_) => match cmp {
-                StringLit(_) => true,
-                _ => false,
-            },
-            Whitespace => match cmp {
-                Whitespace => true,
-                _ => false,
-            },
-            Cmt(_) => match cmp {
-                Cmt(_) => true,
-                _ => false,
-            },
-            Illegal(_) => match cmp {
-                Illegal(_) => true,
-                _ => false,
-            },
-        }
+        // The content of the token is not compared, only its identifier.
+        mem::discriminant(self) == mem::discriminant(cmp)
     }
 
     /// Obtain the token literal.
-    pub fn lit(&self) -> Option<Lit> {
+    pub fn lit(&self) -> Option<Lit<'_>> {
         Some(match self {
-            Id(opt) | Illegal(opt) | CharLit(opt) | StringLit(opt) => match opt {
+            Id(opt) | Illegal(opt) | CharLit(opt) | StringLit(opt) | IntLit(opt)
+            | FloatLit(opt) | Raw(opt) => match opt {
                 Some(lit) => Lit::NonReserved(NonReserved::Primitive(lit)),
                 None => return None,
             },
             Fn => Lit::Reserved(lits::token_table::FN),
+            Var => Lit::Reserved(lits::token_table::VAR),
+            Const => Lit::Reserved(lits::token_table::CONST),
+            Extern => Lit::Reserved(lits::token_table::EXTERN),
+            Export => Lit::Reserved(lits::token_table::EXPORT),
+            Include => Lit::Reserved(lits::token_table::INCLUDE),
+            For => Lit::Reserved(lits::token_table::FOR),
+            In => Lit::Reserved(lits::token_table::IN),
+            Return => Lit::Reserved(lits::token_table::RETURN),
+            Break => Lit::Reserved(lits::token_table::BREAK),
+            Continue => Lit::Reserved(lits::token_table::CONTINUE),
+            Defer => Lit::Reserved(lits::token_table::DEFER),
+            Cpp => Lit::Reserved(lits::token_table::CPP),
+            True => Lit::Reserved(lits::token_table::TRUE),
+            False => Lit::Reserved(lits::token_table::FALSE),
             Whitespace => Lit::Reserved(lits::token_table::SPACE),
             EndOfStmt => Lit::Reserved(lits::token_table::SEMICOLON_SYMBOL),
             DivisionSym => Lit::Reserved(lits::token_table::DIVISION_SYMBOL),
+            AdditionSym => Lit::Reserved(lits::token_table::ADDITION_SYMBOL),
+            SubtractionSym => Lit::Reserved(lits::token_table::SUBTRACTION_SYMBOL),
+            MultiplicationSym => Lit::Reserved(lits::token_table::MULTIPLICATION_SYMBOL),
+            RemainderSym => Lit::Reserved(lits::token_table::REMAINDER_SYMBOL),
+            AssignSym => Lit::Reserved(lits::token_table::ASSIGN_SYMBOL),
+            EqualSym => Lit::Reserved(lits::token_table::EQUAL_SYMBOL),
+            NotEqualSym => Lit::Reserved(lits::token_table::NOT_EQUAL_SYMBOL),
+            LessSym => Lit::Reserved(lits::token_table::LESS_SYMBOL),
+            LessEqualSym => Lit::Reserved(lits::token_table::LESS_EQUAL_SYMBOL),
+            GreaterSym => Lit::Reserved(lits::token_table::GREATER_SYMBOL),
+            GreaterEqualSym => Lit::Reserved(lits::token_table::GREATER_EQUAL_SYMBOL),
+            NotSym => Lit::Reserved(lits::token_table::NOT_SYMBOL),
+            AndSym => Lit::Reserved(lits::token_table::AND_SYMBOL),
+            OrSym => Lit::Reserved(lits::token_table::OR_SYMBOL),
+            OpenParenSym => Lit::Reserved(lits::token_table::OPEN_PAREN_SYMBOL),
+            CloseParenSym => Lit::Reserved(lits::token_table::CLOSE_PAREN_SYMBOL),
+            OpenBracketSym => Lit::Reserved(lits::token_table::OPEN_BRACKET_SYMBOL),
+            CloseBracketSym => Lit::Reserved(lits::token_table::CLOSE_BRACKET_SYMBOL),
+            CommaSym => Lit::Reserved(lits::token_table::COMMA_SYMBOL),
+            ColonSym => Lit::Reserved(lits::token_table::COLON_SYMBOL),
+            DotSym => Lit::Reserved(lits::token_table::DOT_SYMBOL),
+            RangeSym => Lit::Reserved(lits::token_table::RANGE_SYMBOL),
+            QuestionSym => Lit::Reserved(lits::token_table::QUESTION_SYMBOL),
             Cmt(opt) => match opt {
                 Some(tokens) => {
                     if !tokens.is_empty() {
@@ -108,4 +192,8 @@ impl Table {
             None => 0,
         }
     }
+    /// The token has no literal.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
@@ -3,6 +3,10 @@ use core::fmt;
 /// Global literals.
 pub mod lits {
     pub const EPREFIX: &str = "error";
+    pub const WPREFIX: &str = "warning";
+    pub const NPREFIX: &str = "note";
+    /// Errors of the C/C++ compilers in the generated code, not in a script.
+    pub const ICE: &str = "internal compiler error";
     pub const COLON: &str = ":";
     pub const DOT: &str = ".";
     pub const CURRENT: &str = "current";
@@ -13,8 +17,119 @@ pub mod lits {
         pub const RUN: &str = "run";
         /// `help` aliases.
         pub const MANUAL: &str = "manual";
+        pub const HELP: &str = "help";
+        pub const BINDGEN: &str = "bindgen";
+        pub const DEMANGLE: &str = "demangle";
+        pub const CLEAN: &str = "clean";
+        /// `torch new name`, a package in a new folder.
+        pub const NEW: &str = "new";
+        /// `torch init`, a package in the current folder.
+        pub const INIT: &str = "init";
+        /// `torch clean --dev`, only the development files.
+        pub const DEV: &str = "--dev";
+        /// `torch run --release`, the optimized build; `torch clean --release`, only the
+        /// release files.
+        pub const RELEASE: &str = "--release";
+        /// `torch clean --dry-run`, list the files without removing them.
+        pub const DRY_RUN: &str = "--dry-run";
+        /// `--backend c|cpp`
+        pub const BACKEND: &str = "--backend";
+        /// `--package name`, `-p name`
+        pub const PACKAGE: &str = "--package";
+        pub const PACKAGE_SHORT: &str = "-p";
+        /// `--jobs N`, `-j N`
+        pub const JOBS: &str = "--jobs";
+        pub const JOBS_SHORT: &str = "-j";
+        /// `--target-dir dir`, the folder of `.target/` and `target/`.
+        pub const TARGET_DIR: &str = "--target-dir";
+        /// `--color auto|always|never`
+        pub const COLOR: &str = "--color";
+        pub const AUTO: &str = "auto";
+        pub const ALWAYS: &str = "always";
+        pub const NEVER: &str = "never";
+        /// `--quiet`, `-q`, without the status messages.
+        pub const QUIET: &str = "--quiet";
+        pub const QUIET_SHORT: &str = "-q";
+        /// `--verbose`, `-v`, with the commands of the compilers.
+        pub const VERBOSE: &str = "--verbose";
+        pub const VERBOSE_SHORT: &str = "-v";
+        /// `--help`, `-h`, the manual of the subcommand.
+        pub const HELP_FLAG: &str = "--help";
+        pub const HELP_SHORT: &str = "-h";
+        /// `torch run -- args`, the arguments of the executable.
+        pub const ARGS: &str = "--";
+        /// `torch version`, also without subcommand.
+        pub const VERSION: &str = "version";
+        /// `torch version --json`, the information for the tools.
+        pub const JSON: &str = "--json";
     }
 
+    /// C/C++ compilers of the generated code.
+    pub mod compilers {
+        /// Environment variable of the C compiler.
+        pub const CC: &str = "CC";
+        /// Environment variable of the C++ compiler.
+        pub const CXX: &str = "CXX";
+        /// Default C compiler.
+        pub const DEFAULT_CC: &str = "cc";
+        /// Default C++ compiler.
+        pub const DEFAULT_CXX: &str = "c++";
+        pub const C_STD: &str = "-std=c11";
+        pub const CPP_STD: &str = "-std=c++20";
+        /// Optimization level (`-O3`).
+        pub const OPT_LEVEL: &str = "-O";
+        pub const DEBUG: &str = "-g";
+        /// Trap on the overflow of the signed integers.
+        pub const TRAPV: &str = "-ftrapv";
+        /// Disable the `assert` of the C/C++ code.
+        pub const NDEBUG: &str = "-DNDEBUG";
+        /// Sanitizers (`-fsanitize=address,undefined`).
+        pub const SANITIZE: &str = "-fsanitize=";
+        /// Folder of the headers.
+        pub const INCLUDE: &str = "-I";
+        /// Folder of the libraries.
+        pub const LIB_DIR: &str = "-L";
+        /// Library (`-lm`).
+        pub const LIB: &str = "-l";
+        /// Prefix of the libraries (`libname.a`).
+        pub const LIB_PREFIX: &str = "lib";
+        /// Position-independent code of the libraries.
+        pub const PIC: &str = "-fPIC";
+        /// Compile without linking.
+        pub const OBJECT: &str = "-c";
+        pub const SHARED: &str = "-shared";
+        /// Environment variable of the archiver of the static libraries.
+        pub const AR: &str = "AR";
+        pub const DEFAULT_AR: &str = "ar";
+        /// Replace the members, create the archive and index it.
+        pub const AR_FLAGS: &str = "rcs";
+        /// Version of the compiler (`cc --version`).
+        pub const VERSION: &str = "--version";
+    }
+
+    /// Compilation profiles of the manifest (`[profile.dev]`, `[profile.release]`).
+    pub mod profiles {
+        /// `opt-level = 3` or `opt-level = "s"`
+        pub const OPT_LEVEL: &str = "opt-level";
+        /// Optimization levels of the compilers.
+        pub const OPT_LEVELS: [&str; 6] = ["0", "1", "2", "3", "s", "z"];
+        /// `sanitizers = ["address", "undefined"]`
+        pub const SANITIZERS_KEY: &str = "sanitizers";
+        /// Sanitizers of GCC and Clang (`-fsanitize=address,undefined`).
+        pub const SANITIZERS: [&str; 5] = ["address", "undefined", "thread", "leak", "memory"];
+    }
+
+    /// Languages of the generated code (`--backend`, `backend` of `torch.toml`).
+    pub mod backends {
+        pub const C: &str = "c";
+        pub const CPP: &str = "cpp";
+    }
+
+    /// Literals of the bindings generated from the C headers (`torch bindgen`).
+    pub mod bindgen {
+        /// Suffix of the name of the generated module (`file.h` -> `file_bindings.t`).
+        pub const SUFFIX: &str = "_bindings";
+    }
     pub mod extensions {
         /// `file.t`
         pub const T: &str = "t";
@@ -26,17 +141,48 @@ pub mod lits {
         pub const CPP: &str = "cpp";
         /// `file.c++`
         pub const CPP2: &str = "c++";
+        /// `file.cpp.map`, source map of the generated file.
+        pub const MAP: &str = "map";
+        /// `file.o`, object of a library.
+        pub const O: &str = "o";
+        /// `libname.a`, static library.
+        pub const A: &str = "a";
+        /// `libname.so`, shared library.
+        pub const SO: &str = "so";
     }
     pub mod std_resources {
         /// Folder name for the code in the language.
         pub const SRC: &str = "src";
 
+        /// Script of the `main` function, relative to `src`.
+        pub const ENTRY: &str = "main.t";
+        /// Version of the package without manifest.
+        pub const VERSION: &str = "0.0.0";
+
+        /// Settings of the project.
+        pub const MANIFEST: &str = "torch.toml";
+        /// Files ignored by git of a new package.
+        pub const GITIGNORE: &str = ".gitignore";
+
+        /// Folder name for the release executable.
+        pub const TARGET: &str = "target";
+
         /// Hidden compilation folder.
         pub mod dot_target {
             /// Compilation hidden folder name.
             pub const NAME: &str = ".target";
             /// Folder name for the development compilation.
             pub const DEV: &str = "dev";
+            /// Name of the executable without manifest.
+            pub const EXE: &str = "main";
+            /// Cache of the incremental builds, the hashes of the scripts and of the
+            /// objects.
+            pub const CACHE: &str = "torch_cache.json";
+            /// Marker of the folders created by the compiler (`.target/`, `target/`),
+            /// `torch clean` only removes them.
+            pub const MARKER: &str = "torch_target.tag";
+            pub const MARKER_CONTENTS: &str =
+                "# Folder created by torch, `torch clean` removes its files.\n";
         }
     }
 
@@ -45,14 +191,196 @@ pub mod lits {
         pub const SEMICOLON_SYMBOL: &str = ";";
         pub const SPACE: &str = " ";
         pub const DIVISION_SYMBOL: &str = "/";
+        pub const ADDITION_SYMBOL: &str = "+";
+        pub const SUBTRACTION_SYMBOL: &str = "-";
+        pub const MULTIPLICATION_SYMBOL: &str = "*";
+        pub const REMAINDER_SYMBOL: &str = "%";
+        pub const ASSIGN_SYMBOL: &str = "=";
+        pub const EQUAL_SYMBOL: &str = "==";
+        pub const NOT_EQUAL_SYMBOL: &str = "!=";
+        pub const LESS_SYMBOL: &str = "<";
+        pub const LESS_EQUAL_SYMBOL: &str = "<=";
+        pub const GREATER_SYMBOL: &str = ">";
+        pub const GREATER_EQUAL_SYMBOL: &str = ">=";
+        pub const NOT_SYMBOL: &str = "!";
+        pub const AND_SYMBOL: &str = "&&";
+        pub const OR_SYMBOL: &str = "||";
+        pub const OPEN_PAREN_SYMBOL: &str = "(";
+        pub const CLOSE_PAREN_SYMBOL: &str = ")";
+        pub const OPEN_BRACKET_SYMBOL: &str = "[";
+        pub const CLOSE_BRACKET_SYMBOL: &str = "]";
+        pub const COMMA_SYMBOL: &str = ",";
+        pub const COLON_SYMBOL: &str = ":";
+        pub const DOT_SYMBOL: &str = ".";
+        /// `start..end`
+        pub const RANGE_SYMBOL: &str = "..";
+        /// `expr?`
+        pub const QUESTION_SYMBOL: &str = "?";
         /// Commentator's literal.
         pub const CMT: &str = "//";
         pub const FN: &str = "fn";
+        pub const VAR: &str = "var";
+        pub const CONST: &str = "const";
+        pub const EXTERN: &str = "extern";
+        pub const EXPORT: &str = "export";
+        pub const INCLUDE: &str = "include";
+        pub const FOR: &str = "for";
+        pub const IN: &str = "in";
+        pub const RETURN: &str = "return";
+        pub const BREAK: &str = "break";
+        pub const CONTINUE: &str = "continue";
+        pub const DEFER: &str = "defer";
+        pub const CPP: &str = "cpp";
+        pub const TRUE: &str = "true";
+        pub const FALSE: &str = "false";
+        /// All the keywords, no declaration can take their names.
+        pub const KEYWORDS: [&str; 15] = [
+            FN, VAR, CONST, EXTERN, EXPORT, INCLUDE, FOR, IN, RETURN, BREAK, CONTINUE, DEFER, CPP,
+            TRUE, FALSE,
+        ];
+    }
+
+    /// Built-in type names.
+    pub mod types {
+        pub const I8: &str = "i8";
+        pub const I16: &str = "i16";
+        pub const I32: &str = "i32";
+        pub const I64: &str = "i64";
+        pub const U8: &str = "u8";
+        pub const U16: &str = "u16";
+        pub const U32: &str = "u32";
+        pub const U64: &str = "u64";
+        pub const F32: &str = "f32";
+        pub const F64: &str = "f64";
+        pub const BOOL: &str = "bool";
+        pub const CHAR: &str = "char";
+        /// Owned UTF-8 string.
+        pub const STR: &str = "str";
+        /// Growable list (`list[type]`).
+        pub const LIST: &str = "list";
+        /// Value or error (`result[type, type]`).
+        pub const RESULT: &str = "result";
+        /// Value or nothing (`option[type]`).
+        pub const OPTION: &str = "option";
+        /// All the built-in types, no declaration can take their names.
+        pub const ALL: [&str; 16] = [
+            I8, I16, I32, I64, U8, U16, U32, U64, F32, F64, BOOL, CHAR, STR, LIST, RESULT, OPTION,
+        ];
+    }
+
+    /// Built-in functions and values.
+    pub mod builtins {
+        /// `len(array|slice|list|str)`
+        pub const LEN: &str = "len";
+        /// `ok(expr)`
+        pub const OK: &str = "ok";
+        /// `err(expr)`
+        pub const ERR: &str = "err";
+        /// `some(expr)`
+        pub const SOME: &str = "some";
+        /// `none`
+        pub const NONE: &str = "none";
+        /// All the built-ins, they live in the namespace of the runtime.
+        pub const ALL: [&str; 5] = [LEN, OK, ERR, SOME, NONE];
+    }
+
+    /// Members of the built-in types (`items.push(item)`, `res.value`).
+    pub mod members {
+        /// `len()` of the strings and collections, field of the slices and lists.
+        pub const LEN: &str = "len";
+        /// `push(item)` of the lists.
+        pub const PUSH: &str = "push";
+        /// `pop()` of the lists.
+        pub const POP: &str = "pop";
+        /// `clear()` of the lists.
+        pub const CLEAR: &str = "clear";
+        /// `graphemes()` of the strings.
+        pub const GRAPHEMES: &str = "graphemes";
+        /// `is_char_boundary(i)` of the strings.
+        pub const IS_CHAR_BOUNDARY: &str = "is_char_boundary";
+        /// `c_str()` of the strings.
+        pub const C_STR: &str = "c_str";
+        /// `is_ok()` of the results.
+        pub const IS_OK: &str = "is_ok";
+        /// `is_err()` of the results.
+        pub const IS_ERR: &str = "is_err";
+        /// `is_some()` of the options.
+        pub const IS_SOME: &str = "is_some";
+        /// `is_none()` of the options.
+        pub const IS_NONE: &str = "is_none";
+        /// `unwrap()` of the results and options.
+        pub const UNWRAP: &str = "unwrap";
+        /// `unwrap_or(value)` of the results and options.
+        pub const UNWRAP_OR: &str = "unwrap_or";
+        /// Field of the results, is it a value?
+        pub const OK: &str = "ok";
+        /// Field of the options, is it a value?
+        pub const SOME: &str = "some";
+        /// Field of the results and options.
+        pub const VALUE: &str = "value";
+        /// Field of the results.
+        pub const ERROR: &str = "error";
+    }
+
+    /// Linkages of the `extern` functions (`extern "c" fn ...`).
+    pub mod abi {
+        /// Declared with C linkage (`extern "C"`).
+        pub const C: &str = "c";
+        /// Declared as C++, it can use the runtime types.
+        pub const CPP: &str = "cpp";
     }
+
     /// Literals for C/C++ code generation.
     pub mod cgen {
         /// Default name of C/C++ identifiers (_it will practically never be used_).
         pub const DEFAULT_ID: &str = "name";
+        /// Name of the entry point function.
+        pub const MAIN: &str = "main";
+        /// Name of the runtime header written in the target folder.
+        pub const RUNTIME: &str = "torch.hpp";
+        /// Name of the runtime header of the C backend (`torch.h` is the header of the
+        /// `export fn` of a `src/torch.t`).
+        pub const C_RUNTIME: &str = "torch_c.h";
+        /// Namespace of the runtime.
+        pub const NAMESPACE: &str = "torch";
+        /// Namespace of the runtime with the `extern "c"` functions (`torch::c`).
+        pub const C_NAMESPACE: &str = "c";
+        /// Namespace of the runtime with the C wrappers of the `export fn`
+        /// (`torch::exports`).
+        pub const EXPORTS_NAMESPACE: &str = "exports";
+        /// Name of the manifest of the target folder, it maps the scripts to their
+        /// generated files.
+        pub const MANIFEST: &str = "torch_manifest.json";
+        /// Name of the grapheme table of the runtime written in the target folder.
+        pub const UNICODE: &str = "torch_unicode.hpp";
+        /// Prefix of the C backend wrappers of the `extern "c"` functions with strings
+        /// (`torch_c_name`).
+        pub const C_PREFIX: &str = "torch_c_";
+        /// Prefix of the mangled names (`_T4main4half`).
+        pub const MANGLE_PREFIX: &str = "_T";
+        /// Prefix of the temporaries of the propagations (`expr?`).
+        pub const TRY_PREFIX: &str = "torch_try_";
+        /// Prefix of the ends of the ranges of the C backend loops (`for`).
+        pub const END_PREFIX: &str = "torch_end_";
+        /// Prefix of the slices of the items of the C backend loops (`for`).
+        pub const ITER_PREFIX: &str = "torch_iter_";
+        /// Prefix of the indexes of the items of the C backend loops (`for`).
+        pub const INDEX_PREFIX: &str = "torch_index_";
+        /// Returned value of the C backend while the deferred code runs (`return`).
+        pub const RET: &str = "torch_ret";
+        /// Prefix of the scope-exit guards (`defer`).
+        pub const DEFER_PREFIX: &str = "torch_defer_";
+        /// Placeholder of the `#line` that returns to the generated file after a `cpp`
+        /// block, it is replaced when the whole file is generated.
+        pub const LINE_RESTORE: &str = "#line torch_restore";
+        /// Macro that enables the bounds checks of the runtime.
+        pub const BOUNDS_CHECKS: &str = "TORCH_BOUNDS_CHECKS";
+        /// Suffix of the headers with the prototypes of the functions of a module, the
+        /// modules of its dependents include it (`net.http_interface.h`).
+        ///
+        /// > _A module name never has a single `_` before a letter that is not hexadecimal,
+        /// > so it is not the header of another module._
+        pub const INTERFACE_SUFFIX: &str = "_interface";
     }
 }
 
@@ -60,7 +388,7 @@ pub mod lits {
 ///
 /// ---
 /// > _It is always verified that the token literals are valid unicodes or graphemes
-/// at the time of their creation._
+/// > at the time of their creation._
 #[derive(Debug)]
 #[repr(u8)]
 pub enum Lit<'lit> {
@@ -69,7 +397,7 @@ pub enum Lit<'lit> {
     /// Predefined literals.
     Reserved(&'lit str),
 }
-impl<'lit> fmt::Display for Lit<'lit> {
+impl fmt::Display for Lit<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Lit::Reserved(lit) => f.write_str(lit),
@@ -84,7 +412,7 @@ impl<'lit> fmt::Display for Lit<'lit> {
 #[repr(u8)]
 pub enum NonReserved<'non_reserved> {
     /// Non-modifiable primitives.
-    Primitive(&'non_reserved Box<[u8]>),
+    Primitive(&'non_reserved [u8]),
     /// Temporary constructions according to the context.
     Pseudo(Box<[u8]>),
 }
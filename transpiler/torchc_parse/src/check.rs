@@ -0,0 +1,569 @@
+use crate::expr::start;
+use std::collections::HashMap;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Table, Token};
+use torchc_lits::lits;
+use torchc_script::Script;
+
+/// Integer types.
+const INTS: [&str; 8] = [
+    lits::types::I8,
+    lits::types::I16,
+    lits::types::I32,
+    lits::types::I64,
+    lits::types::U8,
+    lits::types::U16,
+    lits::types::U32,
+    lits::types::U64,
+];
+/// Types whose values are checked in the calls.
+const PRIMITIVES: [&str; 13] = [
+    lits::types::I8,
+    lits::types::I16,
+    lits::types::I32,
+    lits::types::I64,
+    lits::types::U8,
+    lits::types::U16,
+    lits::types::U32,
+    lits::types::U64,
+    lits::types::F32,
+    lits::types::F64,
+    lits::types::BOOL,
+    lits::types::CHAR,
+    lits::types::STR,
+];
+
+/// Kind of failure of a fallible value.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum Fallible {
+    /// `result[type, type]`
+    Result,
+    /// `option[type]`
+    Option,
+}
+impl Fallible {
+    fn of(ty: &cgen::Type) -> Option<Self> {
+        match ty {
+            cgen::Type::Result(..) => Some(Fallible::Result),
+            cgen::Type::Option(_) => Some(Fallible::Option),
+            _ => None,
+        }
+    }
+    /// Kind of the type name of the scope (`result`, `option`).
+    fn named(name: &str) -> Option<Self> {
+        match name {
+            lits::types::RESULT => Some(Fallible::Result),
+            lits::types::OPTION => Some(Fallible::Option),
+            _ => None,
+        }
+    }
+    fn lit(&self) -> &str {
+        match self {
+            Fallible::Result => lits::types::RESULT,
+            Fallible::Option => lits::types::OPTION,
+        }
+    }
+}
+
+/// It checks the calls and the error handling of the functions of the script.
+///
+/// ---
+/// - The calls match the signatures of the functions and the `extern` declarations
+///   (_the arguments whose type is known_).
+/// - A fallible result (`result[type, type]`, `option[type]`) cannot be ignored.
+/// - `expr?` is only allowed in functions that return the same kind of fallible value,
+///   the calls and the variables whose type is known.
+/// - `expr?` is not allowed on the right of `&&` and `||`, the propagations are
+///   evaluated before the statement and would break the short circuit.
+/// - `break` and `continue` only go inside a loop.
+/// - The body of `defer` cannot leave its scope (`return`, `expr?`, `break` and
+///   `continue` of an outer loop).
+pub fn check(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>, globals: &[cgen::Stmt]) {
+    let mut sigs: HashMap<String, &cgen::Fn> = HashMap::new();
+    for global in globals {
+        match global {
+            cgen::Stmt::Fn(Some(fn_stmt)) => {
+                sigs.insert(name(&fn_stmt.name), fn_stmt);
+            }
+            cgen::Stmt::Extern(Some(cgen::Extern {
+                decl: cgen::ExternDecl::Fn(sig),
+                ..
+            })) => {
+                sigs.insert(name(&sig.name), sig);
+            }
+            _ => {}
+        }
+    }
+    let mut fns: HashMap<String, Fallible> = HashMap::new();
+    for (name, sig) in &sigs {
+        if let Some(fallible) = sig.ret.as_ref().and_then(Fallible::of) {
+            fns.insert(name.clone(), fallible);
+        }
+    }
+    fns.insert(String::from(lits::builtins::OK), Fallible::Result);
+    fns.insert(String::from(lits::builtins::ERR), Fallible::Result);
+    fns.insert(String::from(lits::builtins::SOME), Fallible::Option);
+
+    let mut checker: Checker = Checker {
+        script,
+        diagnosis,
+        fns: &fns,
+        sigs: &sigs,
+        scope: vec![],
+        ret: None,
+        loops: 0,
+        deferred: false,
+    };
+    for global in globals {
+        if let cgen::Stmt::Fn(Some(fn_stmt)) = global {
+            checker.ret = fn_stmt.ret.as_ref().and_then(Fallible::of);
+            checker.scope = fn_stmt
+                .params
+                .iter()
+                .map(|param| (name(&param.name), kind(Some(&param.ty))))
+                .collect();
+            checker.body(&fn_stmt.body);
+        }
+    }
+}
+
+struct Checker<'checker, 'diagnosis> {
+    script: &'checker mut Script,
+    diagnosis: &'checker mut panic::Diagnosis<'diagnosis>,
+    /// Fallible functions of the script and the built-ins.
+    fns: &'checker HashMap<String, Fallible>,
+    /// Signatures of the functions and the `extern` declarations.
+    sigs: &'checker HashMap<String, &'checker cgen::Fn>,
+    /// Variables in scope and their type, if it is known (`name`, `type`), the
+    /// fallible ones are only named by their kind (`result`).
+    scope: Vec<(String, Option<String>)>,
+    /// Fallible kind returned by the current function.
+    ret: Option<Fallible>,
+    /// Loops around the current statement (`for`).
+    loops: usize,
+    /// Inside the body of a `defer`?
+    deferred: bool,
+}
+impl Checker<'_, '_> {
+    fn body(&mut self, body: &[cgen::Stmt]) {
+        let len: usize = self.scope.len();
+        for stmt in body {
+            match stmt {
+                cgen::Stmt::Var(Some(var)) => {
+                    if let Some(value) = &var.value {
+                        self.expr(value, false);
+                    }
+                    let ty: Option<String> = match (&var.ty, &var.value) {
+                        (Some(ty), _) => kind(Some(ty)),
+                        (None, Some(value)) => self.ty(value),
+                        (None, None) => None,
+                    };
+                    self.scope.push((name(&var.name), ty));
+                }
+                cgen::Stmt::Const(Some(const_stmt)) => {
+                    let ty: Option<String> = match &const_stmt.ty {
+                        Some(ty) => kind(Some(ty)),
+                        None => self.ty(&const_stmt.value),
+                    };
+                    self.scope.push((name(&const_stmt.name), ty));
+                }
+                cgen::Stmt::Assign(Some(assign)) => {
+                    self.expr(&assign.target, false);
+                    self.expr(&assign.value, false);
+                }
+                cgen::Stmt::Expr(Some(expr)) => {
+                    // `fallible(...)`
+                    //  ^^^^^^^^^^^^^
+                    if let Some(fallible) = self.call(expr) {
+                        self.diagnosis.diagnosis(
+                            &format!(
+                                "unused {} that must be used, handle it or propagate it with '?'",
+                                fallible.lit()
+                            ),
+                            start(expr),
+                            self.script,
+                        );
+                    }
+                    self.expr(expr, false);
+                }
+                cgen::Stmt::For(Some(for_stmt)) => {
+                    self.expr(&for_stmt.iter, false);
+                    // The ranges iterate `i64`.
+                    let ty: Option<String> = match for_stmt.iter {
+                        cgen::Expr::Range(..) => Some(String::from(lits::types::I64)),
+                        _ => None,
+                    };
+                    self.scope.push((name(&for_stmt.name), ty));
+                    self.loops += 1;
+                    self.body(&for_stmt.body);
+                    self.loops -= 1;
+                    self.scope.pop();
+                }
+                cgen::Stmt::Break(Some(token)) | cgen::Stmt::Continue(Some(token))
+                    if self.loops == 0 =>
+                {
+                    self.diagnosis.diagnosis(
+                        &format!(
+                            "illegal '{}' {}",
+                            name(token),
+                            if self.deferred {
+                                "in 'defer', the body cannot leave its scope"
+                            } else {
+                                "outside of a loop"
+                            }
+                        ),
+                        token.pos,
+                        self.script,
+                    );
+                }
+                cgen::Stmt::Defer(Some(defer)) => {
+                    let (loops, deferred): (usize, bool) = (self.loops, self.deferred);
+                    self.loops = 0;
+                    self.deferred = true;
+                    self.body(&defer.body);
+                    self.loops = loops;
+                    self.deferred = deferred;
+                }
+                cgen::Stmt::Return(Some(ret)) => {
+                    if self.deferred {
+                        self.diagnosis.diagnosis(
+                            "illegal 'return' in 'defer', the body cannot leave its scope",
+                            ret.pos,
+                            self.script,
+                        );
+                    }
+                    if let Some(value) = &ret.value {
+                        self.expr(value, false);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.scope.truncate(len);
+    }
+
+    /// `short_circuit` indicates that the expression is on the right of `&&` or `||`.
+    fn expr(&mut self, expr: &cgen::Expr, short_circuit: bool) {
+        match expr {
+            cgen::Expr::Lit(_) | cgen::Expr::Id(_) => {}
+            cgen::Expr::Array(items) | cgen::Expr::Format(items) => {
+                for item in items {
+                    self.expr(item, short_circuit);
+                }
+            }
+            cgen::Expr::Unary(_, expr) | cgen::Expr::Member(expr, _) => {
+                self.expr(expr, short_circuit)
+            }
+            cgen::Expr::Binary(lhs, op, rhs) => {
+                self.expr(lhs, short_circuit);
+                self.expr(
+                    rhs,
+                    short_circuit || op.is(&Table::AndSym) || op.is(&Table::OrSym),
+                );
+            }
+            cgen::Expr::Range(lhs, rhs) | cgen::Expr::Index(lhs, rhs, _) => {
+                self.expr(lhs, short_circuit);
+                self.expr(rhs, short_circuit);
+            }
+            cgen::Expr::Call(callee, args) => {
+                self.expr(callee, short_circuit);
+                for arg in args {
+                    self.expr(arg, short_circuit);
+                }
+                self.args(callee, args);
+            }
+            cgen::Expr::Slice(expr, lo, hi, _) => {
+                self.expr(expr, short_circuit);
+                for bound in [lo, hi].into_iter().flatten() {
+                    self.expr(bound, short_circuit);
+                }
+            }
+            // `expr?`
+            //      ^
+            cgen::Expr::Try(expr, pos) => {
+                if self.deferred {
+                    self.diagnosis.diagnosis(
+                        "illegal '?' in 'defer', the body cannot leave its scope",
+                        *pos,
+                        self.script,
+                    );
+                }
+                if short_circuit {
+                    self.diagnosis.diagnosis(
+                        "illegal '?' on the right of '&&' or '||'",
+                        *pos,
+                        self.script,
+                    );
+                }
+                // `call()?` or a fallible variable (`x?`).
+                let fallible: Option<Fallible> = match (self.call(expr), self.ty(expr)) {
+                    (Some(fallible), _) => Some(fallible),
+                    (None, Some(ty)) => match Fallible::named(&ty) {
+                        Some(fallible) => Some(fallible),
+                        None => self.diagnosis.diagnosis(
+                            &format!(
+                                "illegal '?' of '{}', it is not a '{}' or an '{}'",
+                                ty,
+                                lits::types::RESULT,
+                                lits::types::OPTION
+                            ),
+                            *pos,
+                            self.script,
+                        ),
+                    },
+                    (None, None) => None,
+                };
+                match (self.ret, fallible) {
+                    (None, _) => self.diagnosis.diagnosis(
+                        "illegal '?' in a function that does not return 'result' or 'option'",
+                        *pos,
+                        self.script,
+                    ),
+                    (Some(ret), Some(fallible)) if ret != fallible => self.diagnosis.diagnosis(
+                        &format!(
+                            "illegal '?' of '{}' in a function that returns '{}'",
+                            fallible.lit(),
+                            ret.lit()
+                        ),
+                        *pos,
+                        self.script,
+                    ),
+                    _ => {}
+                }
+                self.expr(expr, short_circuit);
+            }
+        }
+    }
+
+    /// Check the arguments of the call against the signature of the function.
+    fn args(&mut self, callee: &cgen::Expr, args: &[cgen::Expr]) {
+        let id: &Token = match callee {
+            cgen::Expr::Id(id) => id,
+            _ => return,
+        };
+        let sigs: &HashMap<String, &cgen::Fn> = self.sigs;
+        let sig: &cgen::Fn = match sigs.get(&name(id)) {
+            Some(sig) => sig,
+            None => return,
+        };
+
+        if args.len() != sig.params.len() {
+            self.diagnosis.diagnosis(
+                &format!(
+                    "expecting {} arguments and found {}",
+                    sig.params.len(),
+                    args.len()
+                ),
+                id.pos,
+                self.script,
+            );
+        }
+        for (arg, param) in args.iter().zip(&sig.params) {
+            if let (Some(expected), Some(found)) = (ty_name(Some(&param.ty)), self.ty(arg)) {
+                if !compatible(&expected, &found) {
+                    self.diagnosis.diagnosis(
+                        &format!(
+                            "mismatched types, expecting '{}' and found '{}'",
+                            expected, found
+                        ),
+                        start(arg),
+                        self.script,
+                    );
+                }
+            }
+        }
+    }
+    /// Type of the expression, if it is known.
+    ///
+    /// ---
+    /// > _The untyped literals are `integer` and `float`._
+    fn ty(&self, expr: &cgen::Expr) -> Option<String> {
+        match expr {
+            cgen::Expr::Lit(token) => Some(String::from(match token.lexeme {
+                Table::IntLit(_) => "integer",
+                Table::FloatLit(_) => "float",
+                Table::StringLit(_) => lits::types::STR,
+                Table::CharLit(_) => lits::types::CHAR,
+                _ => lits::types::BOOL,
+            })),
+            cgen::Expr::Format(_) => Some(String::from(lits::types::STR)),
+            cgen::Expr::Id(id) => {
+                let name: String = name(id);
+                self.scope
+                    .iter()
+                    .rev()
+                    .find(|var| var.0 == name)
+                    .and_then(|var| var.1.clone())
+            }
+            cgen::Expr::Call(callee, _) => match callee.as_ref() {
+                cgen::Expr::Id(id) => match self.call(expr) {
+                    Some(fallible) => Some(String::from(fallible.lit())),
+                    None => self
+                        .sigs
+                        .get(&name(id))
+                        .and_then(|sig| ty_name(sig.ret.as_ref())),
+                },
+                _ => None,
+            },
+            cgen::Expr::Unary(op, _) if op.is(&Table::NotSym) => {
+                Some(String::from(lits::types::BOOL))
+            }
+            cgen::Expr::Binary(_, op, _)
+                if [
+                    Table::EqualSym,
+                    Table::NotEqualSym,
+                    Table::LessSym,
+                    Table::LessEqualSym,
+                    Table::GreaterSym,
+                    Table::GreaterEqualSym,
+                    Table::AndSym,
+                    Table::OrSym,
+                ]
+                .iter()
+                .any(|cmp| op.is(cmp)) =>
+            {
+                Some(String::from(lits::types::BOOL))
+            }
+            _ => None,
+        }
+    }
+
+    /// Fallible kind of the call to a known function.
+    fn call(&self, expr: &cgen::Expr) -> Option<Fallible> {
+        match expr {
+            cgen::Expr::Call(callee, _) => match callee.as_ref() {
+                cgen::Expr::Id(id) => self.fns.get(&name(id)).copied(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn name(token: &Token) -> String {
+    match token.lit() {
+        Some(lit) => format!("{}", lit),
+        None => String::new(),
+    }
+}
+
+/// Name of the primitive type (`i32`, `str`, ...), the others are not checked.
+fn ty_name(ty: Option<&cgen::Type>) -> Option<String> {
+    match ty {
+        Some(cgen::Type::Id(id)) => {
+            let name: String = name(id);
+            if PRIMITIVES.contains(&name.as_str()) {
+                Some(name)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+/// Type of the scope, the fallible ones are named by their kind (`result`).
+fn kind(ty: Option<&cgen::Type>) -> Option<String> {
+    match ty.and_then(Fallible::of) {
+        Some(fallible) => Some(String::from(fallible.lit())),
+        None => ty_name(ty),
+    }
+}
+/// Can the argument be passed to the parameter?
+fn compatible(param: &str, arg: &str) -> bool {
+    let int = |ty: &str| INTS.contains(&ty) || ty == "integer";
+    let float = |ty: &str| ty == lits::types::F32 || ty == lits::types::F64 || ty == "float";
+    if int(param) {
+        int(arg)
+    } else if float(param) {
+        int(arg) || float(arg)
+    } else {
+        param == arg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser;
+    use std::path::Path;
+    use torchc_cgen::cgen;
+    use torchc_diagnosis::panic;
+    use torchc_script::Script;
+
+    /// Diagnostic of the parser and the checker, if any.
+    fn check(code: &str) -> Option<String> {
+        std::panic::catch_unwind(|| {
+            let mut script: Script = Script::from_contents(code);
+            let mut diagnosis: panic::Diagnosis =
+                panic::Diagnosis::new(Path::new("main.t").into(), Path::new(".").into());
+            parser(&mut script, &mut diagnosis, &cgen::Stmt::Global(None));
+        })
+        .err()
+        .map(|err| err.downcast::<String>().map_or(String::new(), |msg| *msg))
+    }
+
+    #[test]
+    fn try_of_variables() {
+        const FNS: &str = "fn half(n: i64) result[i64, str]\n    return ok(n / 2)\n\n";
+        assert_eq!(
+            check(&format!(
+                "{}fn twice(n: i64) result[i64, str]\n    var h = half(n)\n    var x: i64 = h?\n    return ok(x * 2)\n",
+                FNS
+            )),
+            None
+        );
+        let diagnostic: String = check(&format!(
+            "{}fn first(n: i64) option[i64]\n    var h = half(n)\n    var x: i64 = h?\n    return some(x)\n",
+            FNS
+        ))
+        .unwrap();
+        assert!(
+            diagnostic.contains("illegal '?' of 'result' in a function that returns 'option'"),
+            "{}",
+            diagnostic
+        );
+        let diagnostic: String =
+            check("fn first(items: option[i64]) result[i64, str]\n    return ok(items?)\n")
+                .unwrap();
+        assert!(
+            diagnostic.contains("illegal '?' of 'option' in a function that returns 'result'"),
+            "{}",
+            diagnostic
+        );
+        let diagnostic: String =
+            check("fn first(n: i64) option[i64]\n    return some(n?)\n").unwrap();
+        assert!(
+            diagnostic.contains("illegal '?' of 'i64', it is not a 'result' or an 'option'"),
+            "{}",
+            diagnostic
+        );
+    }
+
+    #[test]
+    fn defer_cannot_leave_its_scope() {
+        for (code, msg) in [
+            (
+                "fn f() i64\n    defer\n        return 1\n    return 2\n",
+                "illegal 'return' in 'defer', the body cannot leave its scope",
+            ),
+            (
+                "fn f()\n    for i in 0..3\n        defer\n            break\n",
+                "illegal 'break' in 'defer', the body cannot leave its scope",
+            ),
+            (
+                "fn half(n: i64) result[i64, str]\n    return ok(n / 2)\n\n\
+                 fn f() result[i64, str]\n    defer\n        var h: i64 = half(2)?\n    return ok(1)\n",
+                "illegal '?' in 'defer', the body cannot leave its scope",
+            ),
+        ] {
+            let diagnostic: String = check(code).unwrap();
+            assert!(diagnostic.contains(msg), "{}", diagnostic);
+        }
+        // The loops of the body are its own.
+        assert_eq!(
+            check("fn f()\n    defer\n        for i in 0..3\n            break\n    return\n"),
+            None
+        );
+    }
+}
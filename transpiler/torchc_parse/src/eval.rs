@@ -0,0 +1,772 @@
+use crate::expr::start;
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+use torchc_script::Script;
+
+/// Maximum depth of the nested calls to `const fn`.
+const MAX_DEPTH: usize = 128;
+/// Maximum number of statements evaluated for a constant.
+const MAX_STEPS: usize = 1_000_000;
+
+/// Integer types and their ranges.
+const INTS: [(&str, i128, i128); 8] = [
+    (lits::types::I8, i8::MIN as i128, i8::MAX as i128),
+    (lits::types::I16, i16::MIN as i128, i16::MAX as i128),
+    (lits::types::I32, i32::MIN as i128, i32::MAX as i128),
+    (lits::types::I64, i64::MIN as i128, i64::MAX as i128),
+    (lits::types::U8, u8::MIN as i128, u8::MAX as i128),
+    (lits::types::U16, u16::MIN as i128, u16::MAX as i128),
+    (lits::types::U32, u32::MIN as i128, u32::MAX as i128),
+    (lits::types::U64, u64::MIN as i128, u64::MAX as i128),
+];
+
+/// Value known at compile time.
+#[derive(Debug, Clone, PartialEq)]
+#[repr(u8)]
+enum Value {
+    /// Integer and its type (`None` for the untyped literals, they behave as `i64`).
+    Int(i128, Option<&'static str>),
+    Float(f64),
+    Bool(bool),
+    Char(char),
+    Str(String),
+    /// Result of a `const fn` without return type.
+    Void,
+}
+impl Value {
+    fn kind(&self) -> &str {
+        match self {
+            Value::Int(_, Some(ty)) => ty,
+            Value::Int(_, None) => "integer",
+            Value::Float(_) => "float",
+            Value::Bool(_) => lits::types::BOOL,
+            Value::Char(_) => lits::types::CHAR,
+            Value::Str(_) => lits::types::STR,
+            Value::Void => "nothing",
+        }
+    }
+}
+
+/// Control flow of the statements of a `const fn`.
+#[repr(u8)]
+enum Flow {
+    Next,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+/// It evaluates the constants at compile time and replaces their values by the folded
+/// literals.
+///
+/// ---
+/// > _The global constants are evaluated in order, then the constants of the bodies;
+/// > the `const fn` are interpreted when a constant calls them._
+pub fn fold(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>, globals: &mut [cgen::Stmt]) {
+    let mut fns: HashMap<String, cgen::Fn> = HashMap::new();
+    for global in globals.iter() {
+        if let cgen::Stmt::Fn(Some(fn_stmt)) = global {
+            if fn_stmt.constant {
+                fns.insert(name(&fn_stmt.name), fn_stmt.clone());
+            }
+        }
+    }
+
+    let externs: HashSet<String> = globals
+        .iter()
+        .filter_map(|global| match global {
+            cgen::Stmt::Extern(Some(cgen::Extern {
+                decl: cgen::ExternDecl::Const(constant),
+                ..
+            })) => Some(name(&constant.name)),
+            _ => None,
+        })
+        .collect();
+
+    let mut eval: Eval = Eval {
+        script,
+        diagnosis,
+        fns: &fns,
+        externs: &externs,
+        globals: HashMap::new(),
+        locals: vec![],
+        depth: 0,
+        steps: 0,
+        at: Pos::default(),
+    };
+    for global in globals.iter_mut() {
+        if let cgen::Stmt::Const(Some(const_stmt)) = global {
+            let value: Value = eval.constant(const_stmt);
+            let name: String = name(&const_stmt.name);
+            if eval.globals.contains_key(&name) {
+                eval.diagnosis.diagnosis(
+                    &format!("the constant '{}' is already declared", name),
+                    const_stmt.name.pos,
+                    eval.script,
+                );
+            }
+            eval.globals.insert(name, value);
+        }
+    }
+    for global in globals.iter_mut() {
+        if let cgen::Stmt::Fn(Some(fn_stmt)) = global {
+            eval.fold(&mut fn_stmt.body);
+        }
+    }
+}
+
+struct Eval<'eval, 'diagnosis> {
+    script: &'eval mut Script,
+    diagnosis: &'eval mut panic::Diagnosis<'diagnosis>,
+    /// `const fn` of the script.
+    fns: &'eval HashMap<String, cgen::Fn>,
+    /// `extern` constants, their values are only known by C/C++.
+    externs: &'eval HashSet<String>,
+    globals: HashMap<String, Value>,
+    /// Constants of the current scope and, in a `const fn`, also its parameters and
+    /// variables (`name`, `value`, `mutable`).
+    locals: Vec<(String, Value, bool)>,
+    depth: usize,
+    steps: usize,
+    /// Position of the constant being evaluated.
+    at: Pos,
+}
+impl Eval<'_, '_> {
+    /// Fold the constants of the body.
+    fn fold(&mut self, body: &mut [cgen::Stmt]) {
+        let len: usize = self.locals.len();
+        for stmt in body {
+            match stmt {
+                cgen::Stmt::Const(Some(const_stmt)) => {
+                    let value: Value = self.constant(const_stmt);
+                    self.locals.push((name(&const_stmt.name), value, false));
+                }
+                cgen::Stmt::For(Some(for_stmt)) => self.fold(&mut for_stmt.body),
+                cgen::Stmt::Defer(Some(defer)) => self.fold(&mut defer.body),
+                _ => {}
+            }
+        }
+        self.locals.truncate(len);
+    }
+
+    /// Evaluate the constant and replace its value by the literal.
+    fn constant(&mut self, const_stmt: &mut cgen::Const) -> Value {
+        let pos: Pos = start(&const_stmt.value);
+        self.at = pos;
+        self.steps = 0;
+
+        let value: Value = self.expr(&const_stmt.value);
+        let value: Value = self.coerce(value, const_stmt.ty.as_ref(), pos);
+        const_stmt.value = self.lit(&value, pos);
+        // The typed integers keep their type without declaring it (`KB * KB`).
+        if let (None, Value::Int(_, Some(ty))) = (&const_stmt.ty, &value) {
+            let mut id: Token = Token::new();
+            id.lexeme = Table::Id(Some(ty.as_bytes().into()));
+            id.pos = pos;
+            const_stmt.ty = Some(cgen::Type::Id(id));
+        }
+        value
+    }
+
+    fn expr(&mut self, expr: &cgen::Expr) -> Value {
+        match expr {
+            cgen::Expr::Lit(token) => self.lit_value(token),
+            cgen::Expr::Id(id) => {
+                let name: String = name(id);
+                let value: Option<&Value> = match self.locals.iter().rev().find(|l| l.0 == name) {
+                    Some(local) => Some(&local.1),
+                    None => self.globals.get(&name),
+                };
+                match value {
+                    Some(value) => value.clone(),
+                    None if self.externs.contains(&name) => self.diagnosis.diagnosis(
+                        &format!(
+                            "the extern constant '{}' is not known at compile time",
+                            name
+                        ),
+                        id.pos,
+                        self.script,
+                    ),
+                    None => self.diagnosis.diagnosis(
+                        &format!("unknown constant '{}'", name),
+                        id.pos,
+                        self.script,
+                    ),
+                }
+            }
+            cgen::Expr::Unary(op, expr) => {
+                let value: Value = self.expr(expr);
+                match (&op.lexeme, value) {
+                    (Table::SubtractionSym, Value::Int(value, ty)) => {
+                        self.int(value.checked_neg(), ty, op.pos)
+                    }
+                    (Table::SubtractionSym, Value::Float(value)) => Value::Float(-value),
+                    (Table::NotSym, Value::Bool(value)) => Value::Bool(!value),
+                    (_, value) => self.diagnosis.diagnosis(
+                        &format!("illegal operator '{}' for '{}'", name(op), value.kind()),
+                        op.pos,
+                        self.script,
+                    ),
+                }
+            }
+            cgen::Expr::Binary(lhs, op, rhs) => {
+                let lhs: Value = self.expr(lhs);
+                // Short circuit.
+                match (&op.lexeme, &lhs) {
+                    (Table::AndSym, Value::Bool(false)) => return Value::Bool(false),
+                    (Table::OrSym, Value::Bool(true)) => return Value::Bool(true),
+                    _ => {}
+                }
+                let rhs: Value = self.expr(rhs);
+                self.binary(lhs, op, rhs)
+            }
+            cgen::Expr::Format(items) => {
+                let mut out: String = String::new();
+                for item in items {
+                    match self.expr(item) {
+                        Value::Int(value, _) => out.push_str(&value.to_string()),
+                        Value::Bool(value) => out.push_str(&value.to_string()),
+                        Value::Char(value) => out.push(value),
+                        Value::Str(value) => out.push_str(&value),
+                        value => self.diagnosis.diagnosis(
+                            &format!("illegal interpolation of '{}' in a constant", value.kind()),
+                            start(item),
+                            self.script,
+                        ),
+                    }
+                }
+                Value::Str(out)
+            }
+            cgen::Expr::Call(callee, args) => self.call(callee, args),
+            _ => self.diagnosis.diagnosis(
+                "illegal expression in a constant",
+                start(expr),
+                self.script,
+            ),
+        }
+    }
+
+    fn binary(&mut self, lhs: Value, op: &Token, rhs: Value) -> Value {
+        let pos: Pos = op.pos;
+        match (lhs, rhs) {
+            (Value::Int(lhs, lhs_ty), Value::Int(rhs, rhs_ty)) => {
+                if let (Some(lhs_ty), Some(rhs_ty)) = (lhs_ty, rhs_ty) {
+                    if lhs_ty != rhs_ty {
+                        self.diagnosis.diagnosis(
+                            &format!("mismatched types '{}' and '{}'", lhs_ty, rhs_ty),
+                            pos,
+                            self.script,
+                        );
+                    }
+                }
+                let ty: Option<&'static str> = lhs_ty.or(rhs_ty);
+                let value: Option<i128> = match op.lexeme {
+                    Table::AdditionSym => lhs.checked_add(rhs),
+                    Table::SubtractionSym => lhs.checked_sub(rhs),
+                    Table::MultiplicationSym => lhs.checked_mul(rhs),
+                    Table::DivisionSym | Table::RemainderSym if rhs == 0 => self
+                        .diagnosis
+                        .diagnosis("division by zero in a constant", pos, self.script),
+                    Table::DivisionSym => lhs.checked_div(rhs),
+                    Table::RemainderSym => lhs.checked_rem(rhs),
+                    _ => return self.compare(&lhs, op, &rhs, "integer"),
+                };
+                self.int(value, ty, pos)
+            }
+            (Value::Float(lhs), Value::Float(rhs)) => self.float(lhs, op, rhs),
+            (Value::Float(lhs), Value::Int(rhs, _)) => self.float(lhs, op, rhs as f64),
+            (Value::Int(lhs, _), Value::Float(rhs)) => self.float(lhs as f64, op, rhs),
+            (Value::Str(lhs), Value::Str(rhs)) => match op.lexeme {
+                Table::AdditionSym => Value::Str(lhs + &rhs),
+                _ => self.compare(&lhs, op, &rhs, lits::types::STR),
+            },
+            (Value::Char(lhs), Value::Char(rhs)) => self.compare(&lhs, op, &rhs, lits::types::CHAR),
+            (Value::Bool(lhs), Value::Bool(rhs)) => match op.lexeme {
+                Table::AndSym => Value::Bool(lhs && rhs),
+                Table::OrSym => Value::Bool(lhs || rhs),
+                Table::EqualSym => Value::Bool(lhs == rhs),
+                Table::NotEqualSym => Value::Bool(lhs != rhs),
+                _ => self.illegal_operator(op, lits::types::BOOL, lits::types::BOOL),
+            },
+            (lhs, rhs) => self.illegal_operator(op, lhs.kind(), rhs.kind()),
+        }
+    }
+    fn float(&mut self, lhs: f64, op: &Token, rhs: f64) -> Value {
+        Value::Float(match op.lexeme {
+            Table::AdditionSym => lhs + rhs,
+            Table::SubtractionSym => lhs - rhs,
+            Table::MultiplicationSym => lhs * rhs,
+            Table::DivisionSym if rhs == 0.0 => {
+                self.diagnosis
+                    .diagnosis("division by zero in a constant", op.pos, self.script)
+            }
+            Table::DivisionSym => lhs / rhs,
+            Table::RemainderSym => self.illegal_operator(op, "float", "float"),
+            _ => return self.compare(&lhs, op, &rhs, "float"),
+        })
+    }
+    fn compare<T: PartialOrd>(&mut self, lhs: &T, op: &Token, rhs: &T, kind: &str) -> Value {
+        Value::Bool(match op.lexeme {
+            Table::EqualSym => lhs == rhs,
+            Table::NotEqualSym => lhs != rhs,
+            Table::LessSym => lhs < rhs,
+            Table::LessEqualSym => lhs <= rhs,
+            Table::GreaterSym => lhs > rhs,
+            Table::GreaterEqualSym => lhs >= rhs,
+            _ => self.illegal_operator(op, kind, kind),
+        })
+    }
+    fn illegal_operator(&mut self, op: &Token, lhs: &str, rhs: &str) -> ! {
+        self.diagnosis.diagnosis(
+            &format!(
+                "illegal operator '{}' between '{}' and '{}'",
+                name(op),
+                lhs,
+                rhs
+            ),
+            op.pos,
+            self.script,
+        )
+    }
+    /// Integer result of an operation, it must fit in its type.
+    fn int(&mut self, value: Option<i128>, ty: Option<&'static str>, pos: Pos) -> Value {
+        let (min, max): (i128, i128) = range(ty.unwrap_or(lits::types::I64));
+        match value {
+            Some(value) if min <= value && value <= max => Value::Int(value, ty),
+            _ => self.diagnosis.diagnosis(
+                &format!(
+                    "overflow in a constant, the result does not fit in '{}'",
+                    ty.unwrap_or(lits::types::I64)
+                ),
+                pos,
+                self.script,
+            ),
+        }
+    }
+
+    /// `len(str)` or the call to a `const fn`.
+    fn call(&mut self, callee: &cgen::Expr, args: &[cgen::Expr]) -> Value {
+        let id: &Token = match callee {
+            cgen::Expr::Id(id) => id,
+            _ => self.diagnosis.diagnosis(
+                "illegal expression in a constant",
+                start(callee),
+                self.script,
+            ),
+        };
+        let callee: String = name(id);
+
+        if callee == lits::builtins::LEN {
+            return match args.iter().map(|arg| self.expr(arg)).collect::<Vec<_>>()[..] {
+                [Value::Str(ref value)] => Value::Int(value.len() as i128, Some(lits::types::I64)),
+                _ => self.diagnosis.diagnosis(
+                    &format!("expecting a '{}' argument", lits::types::STR),
+                    id.pos,
+                    self.script,
+                ),
+            };
+        }
+
+        let fns: &HashMap<String, cgen::Fn> = self.fns;
+        let fn_stmt: &cgen::Fn = match fns.get(&callee) {
+            Some(fn_stmt) => fn_stmt,
+            None => self.diagnosis.diagnosis(
+                &format!("call to the non-const function '{}' in a constant", callee),
+                id.pos,
+                self.script,
+            ),
+        };
+        if args.len() != fn_stmt.params.len() {
+            self.diagnosis.diagnosis(
+                &format!(
+                    "expecting {} arguments and found {}",
+                    fn_stmt.params.len(),
+                    args.len()
+                ),
+                id.pos,
+                self.script,
+            );
+        }
+
+        let mut locals: Vec<(String, Value, bool)> = vec![];
+        for (arg, param) in args.iter().zip(&fn_stmt.params) {
+            let value: Value = self.expr(arg);
+            let value: Value = self.coerce(value, Some(&param.ty), start(arg));
+            locals.push((name(&param.name), value, true));
+        }
+
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            self.diagnosis.diagnosis(
+                &format!(
+                    "the constant evaluation exceeds the limit of {} nested calls",
+                    MAX_DEPTH
+                ),
+                id.pos,
+                self.script,
+            );
+        }
+        let locals: Vec<(String, Value, bool)> = mem::replace(&mut self.locals, locals);
+        let flow: Flow = self.run(&fn_stmt.body);
+        self.locals = locals;
+        self.depth -= 1;
+
+        match (flow, &fn_stmt.ret) {
+            (Flow::Return(value), ret) => self.coerce(value, ret.as_ref(), id.pos),
+            (_, Some(_)) => self.diagnosis.diagnosis(
+                &format!("the 'const fn' '{}' ends without 'return'", callee),
+                id.pos,
+                self.script,
+            ),
+            (_, None) => Value::Void,
+        }
+    }
+
+    /// Interpret the statements of a `const fn`.
+    fn run(&mut self, body: &[cgen::Stmt]) -> Flow {
+        let len: usize = self.locals.len();
+        let mut flow: Flow = Flow::Next;
+        for stmt in body {
+            self.step(self.at);
+
+            flow = match stmt {
+                cgen::Stmt::Var(Some(var)) => {
+                    let value: Value = match &var.value {
+                        Some(value) => {
+                            let pos: Pos = start(value);
+                            let value: Value = self.expr(value);
+                            self.coerce(value, var.ty.as_ref(), pos)
+                        }
+                        None => self.diagnosis.diagnosis(
+                            "expecting the value of the variable in 'const fn'",
+                            var.name.pos,
+                            self.script,
+                        ),
+                    };
+                    self.locals.push((name(&var.name), value, true));
+                    Flow::Next
+                }
+                cgen::Stmt::Const(Some(const_stmt)) => {
+                    let pos: Pos = start(&const_stmt.value);
+                    let value: Value = self.expr(&const_stmt.value);
+                    let value: Value = self.coerce(value, const_stmt.ty.as_ref(), pos);
+                    self.locals.push((name(&const_stmt.name), value, false));
+                    Flow::Next
+                }
+                cgen::Stmt::Assign(Some(assign)) => {
+                    let id: &Token = match &assign.target {
+                        cgen::Expr::Id(id) => id,
+                        target => self.diagnosis.diagnosis(
+                            "illegal assignment target in 'const fn'",
+                            start(target),
+                            self.script,
+                        ),
+                    };
+                    let name: String = name(id);
+                    let i: usize = match self.locals.iter().rposition(|l| l.0 == name) {
+                        Some(i) if self.locals[i].2 => i,
+                        _ => self.diagnosis.diagnosis(
+                            &format!("cannot assign to '{}', it is not a variable", name),
+                            id.pos,
+                            self.script,
+                        ),
+                    };
+                    let pos: Pos = start(&assign.value);
+                    let value: Value = self.expr(&assign.value);
+                    self.locals[i].1 = match (&self.locals[i].1, value) {
+                        (Value::Int(_, Some(ty)), Value::Int(value, _)) => {
+                            let ty: &'static str = ty;
+                            self.int(Some(value), Some(ty), pos)
+                        }
+                        (Value::Float(_), Value::Int(value, _)) => Value::Float(value as f64),
+                        (old, value) if mem::discriminant(old) == mem::discriminant(&value) => {
+                            value
+                        }
+                        (old, value) => self.diagnosis.diagnosis(
+                            &format!(
+                                "mismatched types, expecting '{}' and found '{}'",
+                                old.kind(),
+                                value.kind()
+                            ),
+                            pos,
+                            self.script,
+                        ),
+                    };
+                    Flow::Next
+                }
+                cgen::Stmt::Expr(Some(expr)) => {
+                    self.expr(expr);
+                    Flow::Next
+                }
+                cgen::Stmt::For(Some(for_stmt)) => self.for_loop(for_stmt),
+                cgen::Stmt::Return(Some(ret)) => Flow::Return(match &ret.value {
+                    Some(value) => self.expr(value),
+                    None => Value::Void,
+                }),
+                cgen::Stmt::Break(Some(_)) => Flow::Break,
+                cgen::Stmt::Continue(Some(_)) => Flow::Continue,
+                cgen::Stmt::Defer(Some(defer)) => self.diagnosis.diagnosis(
+                    "illegal 'defer' in 'const fn'",
+                    defer.pos,
+                    self.script,
+                ),
+                cgen::Stmt::Cpp(Some(cpp)) => self.diagnosis.diagnosis(
+                    &format!("illegal '{}' block in 'const fn'", lits::token_table::CPP),
+                    cpp.pos,
+                    self.script,
+                ),
+                _ => Flow::Next,
+            };
+            if !matches!(flow, Flow::Next) {
+                break;
+            }
+        }
+        self.locals.truncate(len);
+        flow
+    }
+    /// Count a step of the evaluation, diagnosed at `pos` over the limit.
+    fn step(&mut self, pos: Pos) {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            self.diagnosis.diagnosis(
+                &format!(
+                    "the constant evaluation exceeds the limit of {} steps",
+                    MAX_STEPS
+                ),
+                pos,
+                self.script,
+            );
+        }
+    }
+    /// `for name in expr..expr` of a `const fn`.
+    fn for_loop(&mut self, for_stmt: &cgen::For) -> Flow {
+        let (lo, hi): (&cgen::Expr, &cgen::Expr) = match &for_stmt.iter {
+            cgen::Expr::Range(lo, hi) => (lo, hi),
+            iter => self.diagnosis.diagnosis(
+                "illegal loop in 'const fn', expecting a range",
+                start(iter),
+                self.script,
+            ),
+        };
+        let (lo, hi, ty): (i128, i128, Option<&'static str>) = match (self.expr(lo), self.expr(hi))
+        {
+            (Value::Int(lo, lo_ty), Value::Int(hi, hi_ty)) => (lo, hi, lo_ty.or(hi_ty)),
+            (lo, hi) => self.diagnosis.diagnosis(
+                &format!(
+                    "illegal range between '{}' and '{}', expecting integers",
+                    lo.kind(),
+                    hi.kind()
+                ),
+                start(&for_stmt.iter),
+                self.script,
+            ),
+        };
+
+        for i in lo..hi {
+            // An empty iteration is a step too, the loop always ends.
+            self.step(start(&for_stmt.iter));
+            self.locals
+                .push((name(&for_stmt.name), Value::Int(i, ty), false));
+            let flow: Flow = self.run(&for_stmt.body);
+            self.locals.pop();
+            match flow {
+                Flow::Break => break,
+                Flow::Return(value) => return Flow::Return(value),
+                Flow::Next | Flow::Continue => {}
+            }
+        }
+        Flow::Next
+    }
+
+    /// Convert the value to the declared type.
+    fn coerce(&mut self, value: Value, ty: Option<&cgen::Type>, pos: Pos) -> Value {
+        let ty: String = match ty {
+            Some(cgen::Type::Id(id)) => name(id),
+            Some(_) => self.diagnosis.diagnosis(
+                "illegal type of a constant, expecting a primitive type or 'str'",
+                pos,
+                self.script,
+            ),
+            None => {
+                if let Value::Void = value {
+                    self.diagnosis
+                        .diagnosis("expecting a value in the constant", pos, self.script);
+                }
+                return value;
+            }
+        };
+
+        if let Some(&(int, min, max)) = INTS.iter().find(|int| int.0 == ty) {
+            return match value {
+                Value::Int(value, _) if min <= value && value <= max => {
+                    Value::Int(value, Some(int))
+                }
+                Value::Int(value, _) => self.diagnosis.diagnosis(
+                    &format!(
+                        "overflow in a constant, '{}' does not fit in '{}'",
+                        value, ty
+                    ),
+                    pos,
+                    self.script,
+                ),
+                value => self.mismatched(&ty, &value, pos),
+            };
+        }
+        match (ty.as_str(), value) {
+            (lits::types::F32 | lits::types::F64, Value::Int(value, _)) => {
+                Value::Float(value as f64)
+            }
+            (lits::types::F32 | lits::types::F64, Value::Float(value)) => Value::Float(value),
+            (lits::types::BOOL, Value::Bool(value)) => Value::Bool(value),
+            (lits::types::CHAR, Value::Char(value)) => Value::Char(value),
+            (lits::types::STR, Value::Str(value)) => Value::Str(value),
+            (_, value) => self.mismatched(&ty, &value, pos),
+        }
+    }
+    fn mismatched(&mut self, ty: &str, value: &Value, pos: Pos) -> ! {
+        self.diagnosis.diagnosis(
+            &format!(
+                "mismatched types, expecting '{}' and found '{}'",
+                ty,
+                value.kind()
+            ),
+            pos,
+            self.script,
+        )
+    }
+
+    /// Value of the literal token.
+    fn lit_value(&mut self, token: &Token) -> Value {
+        let lit: String = name(token);
+        match token.lexeme {
+            Table::IntLit(_) => match lit.parse::<i128>() {
+                Ok(value) if value <= u64::MAX as i128 => Value::Int(value, None),
+                _ => self.diagnosis.diagnosis(
+                    "overflow in a constant, the integer literal is too large",
+                    token.pos,
+                    self.script,
+                ),
+            },
+            Table::FloatLit(_) => match lit.parse::<f64>() {
+                Ok(value) => Value::Float(value),
+                Err(_) => self
+                    .diagnosis
+                    .diagnosis("illegal float literal", token.pos, self.script),
+            },
+            Table::True => Value::Bool(true),
+            Table::False => Value::Bool(false),
+            Table::CharLit(_) => match unescape(&lit[1..lit.len() - 1]).chars().next() {
+                Some(value) => Value::Char(value),
+                None => self
+                    .diagnosis
+                    .diagnosis("illegal char literal", token.pos, self.script),
+            },
+            Table::StringLit(_) => Value::Str(unescape(&lit[1..lit.len() - 1])),
+            _ => {
+                self.diagnosis
+                    .diagnosis("illegal expression in a constant", token.pos, self.script)
+            }
+        }
+    }
+    /// Literal of the folded value.
+    fn lit(&mut self, value: &Value, pos: Pos) -> cgen::Expr {
+        let mut token: Token = Token::new();
+        token.pos = pos;
+        token.lexeme = match value {
+            // `(-9223372036854775807 - 1)`, the C++ literals have no sign.
+            Value::Int(value, _) if *value == i64::MIN as i128 => Table::IntLit(Some(
+                format!("({} - 1)", value + 1)
+                    .into_bytes()
+                    .into_boxed_slice(),
+            )),
+            Value::Int(value, _) if *value > i64::MAX as i128 => Table::IntLit(Some(
+                format!("{}ULL", value).into_bytes().into_boxed_slice(),
+            )),
+            Value::Int(value, _) => {
+                Table::IntLit(Some(value.to_string().into_bytes().into_boxed_slice()))
+            }
+            Value::Float(value) if value.is_finite() => {
+                Table::FloatLit(Some(format!("{:?}", value).into_bytes().into_boxed_slice()))
+            }
+            Value::Float(_) => {
+                self.diagnosis
+                    .diagnosis("the constant is not a finite float", pos, self.script)
+            }
+            Value::Bool(true) => Table::True,
+            Value::Bool(false) => Table::False,
+            Value::Char(value) => Table::CharLit(Some(
+                format!("'{}'", escape(&value.to_string()))
+                    .into_bytes()
+                    .into_boxed_slice(),
+            )),
+            Value::Str(value) => Table::StringLit(Some(
+                format!("\"{}\"", escape(value))
+                    .into_bytes()
+                    .into_boxed_slice(),
+            )),
+            Value::Void => {
+                self.diagnosis
+                    .diagnosis("expecting a value in the constant", pos, self.script)
+            }
+        };
+        cgen::Expr::Lit(token)
+    }
+}
+
+fn name(token: &Token) -> String {
+    match token.lit() {
+        Some(lit) => format!("{}", lit),
+        None => String::new(),
+    }
+}
+fn range(ty: &str) -> (i128, i128) {
+    match INTS.iter().find(|int| int.0 == ty) {
+        Some(&(_, min, max)) => (min, max),
+        None => (i64::MIN as i128, i64::MAX as i128),
+    }
+}
+
+/// Contents of the literal without its escape sequences (`\n`, `\t`, ...).
+fn unescape(lit: &str) -> String {
+    let mut out: String = String::new();
+    let mut chars = lit.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('0') => out.push('\0'),
+            Some(c) => out.push(c),
+            None => {}
+        }
+    }
+    out
+}
+/// Contents of the C++ literal (`\n`, `\t`, ...).
+fn escape(value: &str) -> String {
+    let mut out: String = String::new();
+    for c in value.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            // The octal escape has at most 3 digits, a digit can follow it.
+            '\0' => out.push_str("\\000"),
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\'' => out.push_str("\\'"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,314 @@
+use crate::string::string;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// Operand parser of a precedence level.
+type Operand = fn(&mut Script, &mut panic::Diagnosis<'_>) -> cgen::Expr;
+
+/// It parses the expression and obtains the _**cgen data**_.
+///
+/// ---
+/// **Precedence** (_from lowest to highest_):
+///
+/// `||`<br>
+/// `&&`<br>
+/// `==` `!=` `<` `<=` `>` `>=`<br>
+/// `..`<br>
+/// `+` `-`<br>
+/// `*` `/` `%`<br>
+/// `-expr` `!expr`<br>
+/// `expr(...)` `expr[...]` `expr.name` `expr?`
+pub fn expr(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    binary(script, diagnosis, &[Table::OrSym], and)
+}
+fn and(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    binary(script, diagnosis, &[Table::AndSym], comparison)
+}
+fn comparison(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    binary(
+        script,
+        diagnosis,
+        &[
+            Table::EqualSym,
+            Table::NotEqualSym,
+            Table::LessSym,
+            Table::LessEqualSym,
+            Table::GreaterSym,
+            Table::GreaterEqualSym,
+        ],
+        range,
+    )
+}
+fn range(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    let lo: cgen::Expr = additive(script, diagnosis);
+
+    // `expr..expr`
+    //      ^^
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::RangeSym) => {
+            script.token(Next(Feature::Code)).unwrap();
+            cgen::Expr::Range(Box::new(lo), Box::new(additive(script, diagnosis)))
+        }
+        _ => lo,
+    }
+}
+fn additive(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    binary(
+        script,
+        diagnosis,
+        &[Table::AdditionSym, Table::SubtractionSym],
+        multiplicative,
+    )
+}
+fn multiplicative(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    binary(
+        script,
+        diagnosis,
+        &[
+            Table::MultiplicationSym,
+            Table::DivisionSym,
+            Table::RemainderSym,
+        ],
+        unary,
+    )
+}
+
+/// Left-associative binary operations of the same precedence.
+fn binary(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+    ops: &[Table],
+    operand: Operand,
+) -> cgen::Expr {
+    let mut lhs: cgen::Expr = operand(script, diagnosis);
+    loop {
+        let op: Token = match script.token(Peek(Feature::Code)) {
+            Some(token) if ops.iter().any(|op| token.is(op)) => token.clone(),
+            _ => return lhs,
+        };
+        script.token(Next(Feature::Code)).unwrap();
+        lhs = cgen::Expr::Binary(Box::new(lhs), op, Box::new(operand(script, diagnosis)));
+    }
+}
+
+fn unary(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    // `-expr`, `!expr`
+    //  ^       ^
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::SubtractionSym) || token.is(&Table::NotSym) => {
+            let op: Token = token.clone();
+            script.token(Next(Feature::Code)).unwrap();
+            cgen::Expr::Unary(op, Box::new(unary(script, diagnosis)))
+        }
+        _ => postfix(script, diagnosis),
+    }
+}
+
+fn postfix(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    let mut expr: cgen::Expr = primary(script, diagnosis);
+    loop {
+        let token: Token = match script.token(Peek(Feature::Code)) {
+            Some(token) => token.clone(),
+            None => return expr,
+        };
+
+        // `expr(expr, expr, ...)`
+        //      ^
+        if token.is(&Table::OpenParenSym) {
+            script.token(Next(Feature::Code)).unwrap();
+            let args: Vec<cgen::Expr> = items(script, diagnosis, &Table::CloseParenSym, ")");
+            expr = cgen::Expr::Call(Box::new(expr), args);
+
+            // `expr[expr]`, `expr[expr..expr]`
+            //      ^             ^
+        } else if token.is(&Table::OpenBracketSym) {
+            script.token(Next(Feature::Code)).unwrap();
+            expr = index(script, diagnosis, expr, token.pos);
+
+            // `expr.name`
+            //      ^
+        } else if token.is(&Table::DotSym) {
+            script.token(Next(Feature::Code)).unwrap();
+            match script.token(Peek(Feature::Code)) {
+                Some(token) if token.is(&Table::Id(None)) => {
+                    let name: Token = token.clone();
+                    script.token(Next(Feature::Code)).unwrap();
+                    expr = cgen::Expr::Member(Box::new(expr), name);
+                }
+                _ => diagnosis.diagnosis("expecting member name", after(&token), script),
+            }
+
+            // `expr?`
+            //      ^
+        } else if token.is(&Table::QuestionSym) {
+            script.token(Next(Feature::Code)).unwrap();
+            expr = cgen::Expr::Try(Box::new(expr), token.pos);
+        } else {
+            return expr;
+        }
+    }
+}
+
+/// `expr[expr]`, `expr[expr..expr]`, `expr[..expr]`, `expr[expr..]`
+///
+/// _The `[` has already been consumed._
+fn index(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+    expr: cgen::Expr,
+    pos: Pos,
+) -> cgen::Expr {
+    let lo: Option<Box<cgen::Expr>> = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::RangeSym) => None,
+        _ => Some(Box::new(additive(script, diagnosis))),
+    };
+
+    let expr: cgen::Expr = match script.token(Peek(Feature::Code)) {
+        // Slice.
+        Some(token) if token.is(&Table::RangeSym) => {
+            script.token(Next(Feature::Code)).unwrap();
+            let hi: Option<Box<cgen::Expr>> = match script.token(Peek(Feature::Code)) {
+                Some(token) if token.is(&Table::CloseBracketSym) => None,
+                _ => Some(Box::new(additive(script, diagnosis))),
+            };
+            cgen::Expr::Slice(Box::new(expr), lo, hi, pos)
+        }
+        // Index.
+        _ => match lo {
+            Some(i) => cgen::Expr::Index(Box::new(expr), i, pos),
+            None => unreachable!(),
+        },
+    };
+
+    // `expr[expr]`
+    //           ^
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::CloseBracketSym) => {
+            script.token(Next(Feature::Code)).unwrap();
+        }
+        Some(token) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("expecting ']'", pos, script)
+        }
+        None => diagnosis.diagnosis("expecting ']'", pos, script),
+    }
+    expr
+}
+
+fn primary(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Expr {
+    let token: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) => token.clone(),
+        None => diagnosis.diagnosis("expecting expression", Pos::default(), script),
+    };
+
+    match &token.lexeme {
+        // `123`, `1.5`, `'.'`, `true`, `false`
+        Table::IntLit(_) | Table::FloatLit(_) | Table::CharLit(_) | Table::True | Table::False => {
+            script.token(Next(Feature::Code)).unwrap();
+            cgen::Expr::Lit(token)
+        }
+        // `"..."`, `"...{expr}..."`
+        Table::StringLit(_) => {
+            script.token(Next(Feature::Code)).unwrap();
+            string(script, diagnosis, token)
+        }
+        // `name`
+        Table::Id(_) => {
+            script.token(Next(Feature::Code)).unwrap();
+            cgen::Expr::Id(token)
+        }
+        // `(expr)`
+        Table::OpenParenSym => {
+            script.token(Next(Feature::Code)).unwrap();
+            let expr: cgen::Expr = expr(script, diagnosis);
+            match script.token(Peek(Feature::Code)) {
+                Some(token) if token.is(&Table::CloseParenSym) => {
+                    script.token(Next(Feature::Code)).unwrap();
+                }
+                _ => diagnosis.diagnosis("expecting ')'", token.pos, script),
+            }
+            expr
+        }
+        // `[expr, expr, ...]`
+        Table::OpenBracketSym => {
+            script.token(Next(Feature::Code)).unwrap();
+            cgen::Expr::Array(items(script, diagnosis, &Table::CloseBracketSym, "]"))
+        }
+        _ => diagnosis.diagnosis("expecting expression", token.pos, script),
+    }
+}
+
+/// Comma-separated expressions up to the closing symbol (`expr, expr, ...)`).
+///
+/// _The opening symbol has already been consumed._
+pub fn items(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+    close: &Table,
+    close_lit: &str,
+) -> Vec<cgen::Expr> {
+    let mut items: Vec<cgen::Expr> = vec![];
+    loop {
+        match script.token(Peek(Feature::Code)) {
+            Some(token) if token.is(close) => {
+                script.token(Next(Feature::Code)).unwrap();
+                return items;
+            }
+            Some(_) => {}
+            None => diagnosis.diagnosis(
+                &format!("expecting '{}'", close_lit),
+                Pos::default(),
+                script,
+            ),
+        }
+        items.push(expr(script, diagnosis));
+
+        match script.token(Peek(Feature::Code)) {
+            Some(token) if token.is(&Table::CommaSym) => {
+                script.token(Next(Feature::Code)).unwrap();
+            }
+            Some(token) if token.is(close) => {}
+            Some(token) => {
+                let pos: Pos = token.pos;
+                diagnosis.diagnosis(&format!("expecting ',' or '{}'", close_lit), pos, script)
+            }
+            None => diagnosis.diagnosis(
+                &format!("expecting '{}'", close_lit),
+                Pos::default(),
+                script,
+            ),
+        }
+    }
+}
+
+/// Position right after the token.
+pub fn after(token: &Token) -> Pos {
+    let mut pos: Pos = token.pos;
+    pos.grapheme += token.len();
+    pos
+}
+/// Position of the first token of the expression.
+pub fn start(expr: &cgen::Expr) -> Pos {
+    match expr {
+        cgen::Expr::Lit(token) | cgen::Expr::Id(token) | cgen::Expr::Unary(token, _) => token.pos,
+        cgen::Expr::Binary(expr, ..)
+        | cgen::Expr::Range(expr, _)
+        | cgen::Expr::Call(expr, _)
+        | cgen::Expr::Member(expr, _)
+        | cgen::Expr::Index(expr, ..)
+        | cgen::Expr::Slice(expr, ..)
+        | cgen::Expr::Try(expr, _) => start(expr),
+        cgen::Expr::Array(items) | cgen::Expr::Format(items) => match items.first() {
+            Some(item) => start(item),
+            None => Pos::default(),
+        },
+    }
+}
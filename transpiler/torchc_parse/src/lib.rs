@@ -1,6 +1,6 @@
 use torchc_cgen::cgen;
 use torchc_diagnosis::panic;
-use torchc_lex::Table;
+use torchc_lex::{Table, Token};
 use torchc_script::{
     iter::{
         Feature,
@@ -9,7 +9,12 @@ use torchc_script::{
     Script,
 };
 
+mod check;
+mod eval;
+mod expr;
 mod stmt;
+mod string;
+mod ty;
 
 /// Parse the syntax of the script and obtain the _**cgen data**_.
 ///
@@ -26,6 +31,8 @@ pub fn parser(
     //  - The statement without indentation is added in `globals` and
     //    with indentation it is returned (`return cgen::Stmt`).
     while let Some(token) = script.token(Peek(Feature::Code)) {
+        let token: Token = token.clone();
+
         // Skip tokens such as:
         //  - The ends of empty statements (the `EndOfStmt` are automatically added
         //    from newlines).
@@ -35,20 +42,70 @@ pub fn parser(
         }
 
         // Function statement.
-        if token.is(&Table::Fn) {
+        if token.is(&Table::Fn)
+            || token.is(&Table::Export)
+            || token.is(&Table::Const)
+                && script
+                    .peek_second(Feature::Code)
+                    .is_some_and(|token| token.is(&Table::Fn))
+        {
             let fn_stmt: cgen::Stmt = cgen::Stmt::Fn(None);
             if let cgen::Stmt::Global(_) = parent_stmt {
                 globals.push(stmt::function(script, diagnosis, &fn_stmt));
             }
 
-            // Illegal token.
-        } else {
+            // Declarations of the C/C++ files of `src/`.
+        } else if token.is(&Table::Include) || token.is(&Table::Extern) {
+            match parent_stmt {
+                cgen::Stmt::Global(_) if token.is(&Table::Include) => {
+                    globals.push(stmt::include(script, diagnosis))
+                }
+                cgen::Stmt::Global(_) => globals.push(stmt::external(script, diagnosis)),
+                _ => diagnosis.diagnosis(
+                    &format!("illegal '{}', it must go in the root", token.lit().unwrap()),
+                    token.pos,
+                    script,
+                ),
+            }
+
+            // Constant statement (global or of the bodies).
+        } else if token.is(&Table::Const) {
+            let const_stmt: cgen::Stmt = stmt::constant(script, diagnosis);
+            match parent_stmt {
+                cgen::Stmt::Global(_) => globals.push(const_stmt),
+                _ => return const_stmt,
+            }
+
+            // C++ block (global or of the bodies).
+        } else if token.is(&Table::Cpp) {
+            let cpp_stmt: cgen::Stmt = stmt::cpp(script, diagnosis);
+            match parent_stmt {
+                cgen::Stmt::Global(_) => globals.push(cpp_stmt),
+                _ => return cpp_stmt,
+            }
+
+            // Statements of the bodies.
+        } else if let cgen::Stmt::Global(_) = parent_stmt {
             diagnosis.diagnosis("illegal token", token.pos, script);
+        } else if token.is(&Table::Var) {
+            return stmt::variable(script, diagnosis);
+        } else if token.is(&Table::For) {
+            return stmt::for_loop(script, diagnosis);
+        } else if token.is(&Table::Return) {
+            return stmt::ret(script, diagnosis);
+        } else if token.is(&Table::Break) || token.is(&Table::Continue) {
+            return stmt::jump(script, diagnosis);
+        } else if token.is(&Table::Defer) {
+            return stmt::defer(script, diagnosis);
+        } else {
+            return stmt::expression(script, diagnosis);
         }
     }
 
     // Global scope.
     if let cgen::Stmt::Global(_) = parent_stmt {
+        eval::fold(script, diagnosis, &mut globals);
+        check::check(script, diagnosis, &globals);
         cgen::Stmt::Global(Some(globals))
     } else {
         cgen::Stmt::Global(None)
@@ -0,0 +1,70 @@
+use super::end_of_stmt;
+use crate::{
+    expr::{after, expr},
+    ty::ty,
+};
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the constant statement and obtains the _**cgen data**_.
+///
+/// ---
+/// > _The value is folded later, when all the constants and `const fn` are known._
+pub fn constant(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `const name: type = expr`
+    //  ^^^^^
+    let mut pos: Pos = match script.token(Next(Feature::Code)) {
+        Some(token) => after(token),
+        None => return cgen::Stmt::Const(None),
+    };
+
+    // `const name: type = expr`
+    //        ^^^^
+    let name: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::Id(None)) => token.clone(),
+        Some(token) if !token.is(&Table::EndOfStmt) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("illegal constant name", pos, script)
+        }
+        _ => diagnosis.diagnosis("expecting constant name", pos, script),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+    pos = after(&name);
+
+    // `const name: type = expr`
+    //            ^^^^^^
+    let mut const_ty: Option<cgen::Type> = None;
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::ColonSym) {
+            pos = after(token);
+            script.token(Next(Feature::Code)).unwrap();
+            const_ty = Some(ty(script, diagnosis, pos));
+        }
+    }
+
+    // `const name: type = expr`
+    //                   ^^^^^^
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::AssignSym) => {
+            script.token(Next(Feature::Code)).unwrap();
+        }
+        _ => diagnosis.diagnosis("expecting the value of the constant", pos, script),
+    }
+    let value: cgen::Expr = expr(script, diagnosis);
+
+    end_of_stmt(script, diagnosis, pos);
+
+    cgen::Stmt::Const(Some(cgen::Const {
+        name,
+        ty: const_ty,
+        value,
+    }))
+}
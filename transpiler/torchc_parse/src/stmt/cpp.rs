@@ -0,0 +1,56 @@
+use super::end_of_stmt;
+use crate::expr::after;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the `cpp` block and obtains the _**cgen data**_.
+///
+/// ---
+/// > _The lexer captures the indented lines without tokenizing them._
+pub fn cpp(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `cpp`
+    //  ^^^
+    let (pos, end): (Pos, Pos) = match script.token(Next(Feature::Code)) {
+        Some(token) => (token.pos, after(token)),
+        None => return cgen::Stmt::Cpp(None),
+    };
+
+    // `cpp`
+    //     `...`
+    //      ^^^
+    let raw: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::Raw(None)) => token.clone(),
+        _ => diagnosis.diagnosis(
+            &format!(
+                "expecting the indented C++ block, '{}' goes alone in its line",
+                lits::token_table::CPP
+            ),
+            end,
+            script,
+        ),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+    let code: String = match raw.lit() {
+        Some(lit) => format!("{}", lit),
+        None => String::new(),
+    };
+    if code.is_empty() {
+        diagnosis.diagnosis("expecting the indented C++ block", end, script);
+    }
+    end_of_stmt(script, diagnosis, end);
+
+    cgen::Stmt::Cpp(Some(cgen::Cpp {
+        pos,
+        line: raw.pos.line,
+        code,
+    }))
+}
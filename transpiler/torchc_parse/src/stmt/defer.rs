@@ -0,0 +1,27 @@
+use super::{body, end_of_stmt};
+use crate::expr::after;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::Pos;
+use torchc_script::{
+    iter::{Feature, Mode::Next},
+    Script,
+};
+
+/// It recursively parses the `defer` statement and obtains the _**cgen data**_.
+pub fn defer(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `defer`
+    //  ^^^^^
+    let (indent, pos, end): (usize, Pos, Pos) = match script.token(Next(Feature::Code)) {
+        Some(token) => (token.pos.grapheme, token.pos, after(token)),
+        None => return cgen::Stmt::Defer(None),
+    };
+    end_of_stmt(script, diagnosis, end);
+
+    let body: Vec<cgen::Stmt> = body(script, diagnosis, &cgen::Stmt::Defer(None), indent);
+    if body.is_empty() {
+        diagnosis.diagnosis("expecting the indented body of 'defer'", end, script);
+    }
+
+    cgen::Stmt::Defer(Some(cgen::Defer { pos, body }))
+}
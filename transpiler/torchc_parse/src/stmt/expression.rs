@@ -0,0 +1,42 @@
+use super::end_of_stmt;
+use crate::expr::expr;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table};
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the expression or assignment statement and obtains the _**cgen data**_.
+pub fn expression(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    let pos: Pos = match script.token(Peek(Feature::Code)) {
+        Some(token) => token.pos,
+        None => return cgen::Stmt::Expr(None),
+    };
+
+    // `expr`
+    //  ^^^^
+    let target: cgen::Expr = expr(script, diagnosis);
+
+    // `expr = expr`
+    //       ^^^^^^
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::AssignSym) {
+            match target {
+                cgen::Expr::Id(_) | cgen::Expr::Index(..) | cgen::Expr::Member(..) => {}
+                _ => diagnosis.diagnosis("illegal assignment target", pos, script),
+            }
+            script.token(Next(Feature::Code)).unwrap();
+            let value: cgen::Expr = expr(script, diagnosis);
+            end_of_stmt(script, diagnosis, pos);
+            return cgen::Stmt::Assign(Some(cgen::Assign { target, value }));
+        }
+    }
+
+    end_of_stmt(script, diagnosis, pos);
+    cgen::Stmt::Expr(Some(target))
+}
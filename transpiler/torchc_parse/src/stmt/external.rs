@@ -0,0 +1,203 @@
+use super::{end_of_stmt, function::signature};
+use crate::{expr::after, ty::ty};
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the `extern` declaration and obtains the _**cgen data**_.
+pub fn external(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `extern "c" fn name(arg1: type, arg2: type, ...) type`
+    //  ^^^^^^
+    let (indent, pos): (usize, Pos) = match script.token(Next(Feature::Code)) {
+        Some(token) => (token.pos.grapheme, after(token)),
+        None => return cgen::Stmt::Extern(None),
+    };
+
+    // `extern "c" fn name(arg1: type, arg2: type, ...) type`
+    //         ^^^
+    let abi: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::StringLit(None)) => token.clone(),
+        _ => diagnosis.diagnosis(
+            &format!(
+                "expecting the linkage (\"{}\" or \"{}\")",
+                lits::abi::C,
+                lits::abi::CPP
+            ),
+            pos,
+            script,
+        ),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+    let abi: cgen::Abi = match format!("{}", abi.lit().unwrap()).trim_matches('"') {
+        lits::abi::C => cgen::Abi::C,
+        lits::abi::CPP => cgen::Abi::Cpp,
+        _ => diagnosis.diagnosis(
+            &format!(
+                "illegal linkage, expecting \"{}\" or \"{}\"",
+                lits::abi::C,
+                lits::abi::CPP
+            ),
+            abi.pos,
+            script,
+        ),
+    };
+
+    // `extern "c" const NAME: type`
+    //             ^^^^^
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::Const)
+            && !script
+                .peek_second(Feature::Code)
+                .is_some_and(|token| token.is(&Table::Fn))
+        {
+            let constant: cgen::Param = constant(script, diagnosis);
+            if abi == cgen::Abi::C && !c_compatible(&constant.ty) {
+                diagnosis.diagnosis(
+                    &format!("illegal type of an extern \"{}\" constant", lits::abi::C),
+                    constant.name.pos,
+                    script,
+                );
+            }
+            return cgen::Stmt::Extern(Some(cgen::Extern {
+                abi,
+                decl: cgen::ExternDecl::Const(constant),
+            }));
+        }
+    }
+
+    // `extern "c" fn name(arg1: type, arg2: type, ...) type`
+    //             ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    let fn_pos: Pos = match script.token(Peek(Feature::Code)) {
+        Some(token) => token.pos,
+        None => pos,
+    };
+    let (sig, _, pos): (cgen::Fn, usize, Pos) = signature(script, diagnosis);
+    if sig.export {
+        diagnosis.diagnosis(
+            &format!(
+                "illegal '{}', the extern function is defined in C/C++",
+                lits::token_table::EXPORT
+            ),
+            fn_pos,
+            script,
+        );
+    }
+    if sig.constant {
+        diagnosis.diagnosis(
+            &format!(
+                "illegal '{}', the extern function is not evaluated at compile time",
+                lits::token_table::CONST
+            ),
+            fn_pos,
+            script,
+        );
+    }
+    // Only the values of C are passed with C linkage.
+    if abi == cgen::Abi::C {
+        for param in &sig.params {
+            if !c_compatible(&param.ty) {
+                diagnosis.diagnosis(
+                    &format!(
+                        "illegal type of the parameter in an extern \"{}\" function",
+                        lits::abi::C
+                    ),
+                    param.name.pos,
+                    script,
+                );
+            }
+        }
+        if !sig.ret.as_ref().is_none_or(c_compatible) {
+            diagnosis.diagnosis(
+                &format!(
+                    "illegal return type of an extern \"{}\" function",
+                    lits::abi::C
+                ),
+                sig.name.pos,
+                script,
+            );
+        }
+    }
+    end_of_stmt(script, diagnosis, pos);
+
+    // The function is defined in C/C++, it has no body.
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.pos.grapheme > indent && !token.is(&Table::EndOfStmt) {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis(
+                "illegal indentation, the extern function has no body",
+                pos,
+                script,
+            );
+        }
+    }
+
+    cgen::Stmt::Extern(Some(cgen::Extern {
+        abi,
+        decl: cgen::ExternDecl::Fn(sig),
+    }))
+}
+
+/// `const NAME: type`
+///
+/// ---
+/// > _The value is defined by the header (a macro or an enumerator)._
+fn constant(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Param {
+    // `const NAME: type`
+    //  ^^^^^
+    let mut pos: Pos = after(script.token(Next(Feature::Code)).unwrap());
+
+    // `const NAME: type`
+    //        ^^^^
+    let name: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::Id(None)) => token.clone(),
+        Some(token) if !token.is(&Table::EndOfStmt) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("illegal constant name", pos, script)
+        }
+        _ => diagnosis.diagnosis("expecting constant name", pos, script),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+    pos = after(&name);
+
+    // `const NAME: type`
+    //            ^^^^^^
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::ColonSym) => {
+            pos = after(token);
+            script.token(Next(Feature::Code)).unwrap();
+        }
+        _ => diagnosis.diagnosis(
+            "expecting ':', the type of the extern constant is required",
+            pos,
+            script,
+        ),
+    }
+    let const_ty: cgen::Type = ty(script, diagnosis, pos);
+
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::AssignSym) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis(
+                "illegal value, the extern constant is defined in C/C++",
+                pos,
+                script,
+            )
+        }
+        _ => end_of_stmt(script, diagnosis, pos),
+    }
+
+    cgen::Param { name, ty: const_ty }
+}
+
+/// Primitive types and `str` (`const char *`).
+pub fn c_compatible(ty: &cgen::Type) -> bool {
+    matches!(ty, cgen::Type::Id(_))
+}
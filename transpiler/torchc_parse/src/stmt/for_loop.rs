@@ -0,0 +1,65 @@
+use super::{body, end_of_stmt};
+use crate::expr::{after, expr};
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It recursively parses the `for` statement and obtains the _**cgen data**_.
+pub fn for_loop(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `for name in expr`
+    //  ^^^
+    let (indent, pos): (usize, Pos) = match script.token(Next(Feature::Code)) {
+        Some(token) => (token.pos.grapheme, after(token)),
+        None => return cgen::Stmt::For(None),
+    };
+
+    // `for name in expr`
+    //      ^^^^
+    let name: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::Id(None)) => token.clone(),
+        Some(token) if !token.is(&Table::EndOfStmt) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("illegal loop variable name", pos, script)
+        }
+        _ => diagnosis.diagnosis("expecting loop variable name", pos, script),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+
+    // `for name in expr`
+    //           ^^
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::In) => {
+            script.token(Next(Feature::Code)).unwrap();
+        }
+        _ => diagnosis.diagnosis(
+            &format!("expecting '{}'", lits::token_table::IN),
+            after(&name),
+            script,
+        ),
+    }
+
+    // `for name in expr`
+    //              ^^^^
+    let iter: cgen::Expr = expr(script, diagnosis);
+    end_of_stmt(script, diagnosis, pos);
+
+    let mut for_stmt: cgen::For = cgen::For {
+        name,
+        iter,
+        body: vec![],
+    };
+    for_stmt.body = body(script, diagnosis, &cgen::Stmt::For(None), indent);
+    if for_stmt.body.is_empty() {
+        diagnosis.diagnosis("expecting the indented body of 'for'", pos, script);
+    }
+
+    cgen::Stmt::For(Some(for_stmt))
+}
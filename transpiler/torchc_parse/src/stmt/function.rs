@@ -1,8 +1,8 @@
-use super::IllegalIndentAccordingTo;
-use crate::parser;
+use super::{body, end_of_stmt, external::c_compatible};
+use crate::{expr::after, ty::ty};
 use torchc_cgen::cgen;
 use torchc_diagnosis::panic;
-use torchc_lex::{Pos, Table};
+use torchc_lex::{Pos, Table, Token};
 use torchc_lits::lits;
 use torchc_script::{
     iter::{
@@ -18,16 +18,95 @@ pub fn function(
     diagnosis: &mut panic::Diagnosis<'_>,
     stmt: &cgen::Stmt,
 ) -> cgen::Stmt {
+    let (mut fn_stmt, indent, pos): (cgen::Fn, usize, Pos) = signature(script, diagnosis);
+    if fn_stmt.export {
+        export(script, diagnosis, &fn_stmt);
+    }
+
+    // `fn name(arg1: type, arg2: type, ...) type`
+    //                                  End of line ^
+    end_of_stmt(script, diagnosis, pos);
+
+    fn_stmt.body = body(script, diagnosis, stmt, indent);
+
+    cgen::Stmt::Fn(if let cgen::Stmt::Fn(_) = stmt {
+        Some(fn_stmt)
+    } else {
+        None
+    })
+}
+
+/// `export fn name(arg1: type, arg2: type, ...) type`
+///
+/// The exported function is called from C, its values must be of C.
+fn export(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>, fn_stmt: &cgen::Fn) {
+    if fn_stmt.is_main() {
+        diagnosis.diagnosis(
+            &format!(
+                "illegal '{}', the entry point cannot be exported",
+                lits::token_table::EXPORT
+            ),
+            fn_stmt.name.pos,
+            script,
+        );
+    }
+    for param in &fn_stmt.params {
+        if !c_compatible(&param.ty) {
+            diagnosis.diagnosis(
+                "illegal type of the parameter in an exported function",
+                param.name.pos,
+                script,
+            );
+        }
+    }
+    if !fn_stmt.ret.as_ref().is_none_or(c_compatible) {
+        diagnosis.diagnosis(
+            "illegal return type of an exported function",
+            fn_stmt.name.pos,
+            script,
+        );
+    }
+}
+
+/// `export const fn name(arg1: type, arg2: type, ...) type`
+///
+/// It obtains the function without body, the indentation of its statement and the
+/// position after the signature.
+pub fn signature(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+) -> (cgen::Fn, usize, Pos) {
     let mut fn_stmt: cgen::Fn = cgen::Fn::new();
-    let mut indent: usize = 0;
-    let mut pos: Pos = Pos::default();
+    let indent: usize;
+    let mut pos: Pos;
+
+    // `export const fn name(arg1: type, arg2: type, ...) type`
+    //  ^^^^^^
+    let mut prefix: Option<usize> = None; // Indentation of the first prefix.
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::Export) {
+            prefix = Some(token.pos.grapheme);
+            fn_stmt.export = true;
+            script.token(Next(Feature::Code)).unwrap();
+        }
+    }
+
+    // `export const fn name(arg1: type, arg2: type, ...) type`
+    //         ^^^^^
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::Const) {
+            prefix = prefix.or(Some(token.pos.grapheme));
+            fn_stmt.constant = true;
+            script.token(Next(Feature::Code)).unwrap();
+        }
+    }
 
-    // `fn name(var arg1 = type <lit>, arg2 = type <lit>, ...) var type <lit>, type <lit>, ...`
+    // `fn name(arg1: type, arg2: type, ...) type`
     //  ^^
     match script.token(Peek(Feature::Code)) {
         Some(token) => {
             if token.is(&Table::Fn) {
-                indent = token.pos.grapheme;
+                indent = prefix.unwrap_or(token.pos.grapheme);
                 pos = token.pos;
                 pos.grapheme += token.len() + 1; // `+1` == space
                 script.token(Next(Feature::Code)).unwrap();
@@ -39,16 +118,19 @@ pub fn function(
                 );
             }
         }
-        None => return cgen::Stmt::Fn(None),
+        None => diagnosis.diagnosis(
+            &format!("expecting '{}'", lits::token_table::FN),
+            Pos::default(),
+            script,
+        ),
     }
 
-    // `fn name(var arg1 = type <lit>, arg2 = type <lit>, ...) var type <lit>, type <lit>, ...`
+    // `fn name(arg1: type, arg2: type, ...) type`
     //     ^^^^
     match script.token(Peek(Feature::Code)) {
         Some(token) if !token.is(&Table::EndOfStmt) => {
             if token.is(&Table::Id(None)) {
-                pos = token.pos;
-                pos.grapheme += token.len();
+                pos = after(token);
                 fn_stmt.name = token.clone();
                 script.token(Next(Feature::Code)).unwrap();
             } else {
@@ -58,45 +140,86 @@ pub fn function(
         _ => diagnosis.diagnosis("expecting function name", pos, script),
     }
 
-    // `fn name(var arg1 = type <lit>, arg2 = type <lit>, ...) var type <lit>, type <lit>, ...`
-    //                                                                            End of line ^
-    match script.token(Peek(Feature::Code)) {
-        Some(token) if token.is(&Table::EndOfStmt) => {
+    // `fn name(arg1: type, arg2: type, ...) type`
+    //         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::OpenParenSym) {
             script.token(Next(Feature::Code)).unwrap();
+            (fn_stmt.params, pos) = params(script, diagnosis, pos);
         }
-        _ => diagnosis.diagnosis("expecting newline", pos, script),
     }
 
-    // Recursive indentation.
-    while let Some(token) = script.token(Peek(Feature::Code)) {
-        // Skip the automatic end of statements.
-        if token.is(&Table::EndOfStmt) {
-            script.token(Next(Feature::Code));
-            continue;
+    // `fn name(arg1: type, arg2: type, ...) type`
+    //                                       ^^^^
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if !token.is(&Table::EndOfStmt) => {
+            fn_stmt.ret = Some(ty(script, diagnosis, pos));
         }
+        _ => {}
+    }
 
-        if token.pos.grapheme <= indent {
-            break;
-        }
-        println!("({})", token.lit().unwrap());
+    (fn_stmt, indent, pos)
+}
 
-        // Checks for valid statements within the function.
-        if token.is(&Table::Fn) {
-            diagnosis.diagnosis(
-                "illegal indentation"
-                    .to_string()
-                    .illegal_indent_according_to(stmt, &cgen::Stmt::Fn(None)),
-                token.pos,
+/// `arg1: type, arg2: type, ...)`
+///
+/// _The `(` has already been consumed._
+fn params(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+    mut pos: Pos,
+) -> (Vec<cgen::Param>, Pos) {
+    let mut params: Vec<cgen::Param> = vec![];
+    loop {
+        // `name: type`
+        //  ^^^^
+        let name: Token = match script.token(Peek(Feature::Code)) {
+            Some(token) if token.is(&Table::CloseParenSym) => {
+                pos = after(token);
+                script.token(Next(Feature::Code)).unwrap();
+                return (params, pos);
+            }
+            Some(token) if token.is(&Table::Id(None)) => token.clone(),
+            Some(token) if !token.is(&Table::EndOfStmt) => {
+                let pos: Pos = token.pos;
+                diagnosis.diagnosis("illegal parameter name", pos, script)
+            }
+            _ => diagnosis.diagnosis("expecting ')'", pos, script),
+        };
+        script.token(Next(Feature::Code)).unwrap();
+
+        // `name: type`
+        //      ^
+        match script.token(Peek(Feature::Code)) {
+            Some(token) if token.is(&Table::ColonSym) => {
+                pos = after(token);
+                script.token(Next(Feature::Code)).unwrap();
+            }
+            _ => diagnosis.diagnosis(
+                "expecting ':' after the parameter name",
+                after(&name),
                 script,
-            );
+            ),
         }
 
-        fn_stmt.body.push(parser(script, diagnosis, stmt));
-    }
+        // `name: type`
+        //        ^^^^
+        params.push(cgen::Param {
+            name,
+            ty: ty(script, diagnosis, pos),
+        });
 
-    cgen::Stmt::Fn(if let cgen::Stmt::Fn(_) = stmt {
-        Some(fn_stmt)
-    } else {
-        None
-    })
+        match script.token(Peek(Feature::Code)) {
+            Some(token) if token.is(&Table::CommaSym) => {
+                pos = after(token);
+                script.token(Next(Feature::Code)).unwrap();
+            }
+            Some(token) if token.is(&Table::CloseParenSym) => {}
+            Some(token) if !token.is(&Table::EndOfStmt) => {
+                let pos: Pos = token.pos;
+                diagnosis.diagnosis("expecting ',' or ')'", pos, script)
+            }
+            _ => diagnosis.diagnosis("expecting ')'", pos, script),
+        }
+    }
 }
@@ -0,0 +1,94 @@
+use super::end_of_stmt;
+use crate::expr::after;
+use std::path::{Path, PathBuf};
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the `include` directive and obtains the _**cgen data**_.
+///
+/// ---
+/// > _The header is relative to the script, it must exist in `src/`._
+pub fn include(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `include "file.h"`
+    //  ^^^^^^^
+    let pos: Pos = match script.token(Next(Feature::Code)) {
+        Some(token) => after(token),
+        None => return cgen::Stmt::Include(None),
+    };
+
+    // `include "file.h"`
+    //          ^^^^^^^^
+    let path: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::StringLit(None)) => token.clone(),
+        Some(token) if !token.is(&Table::EndOfStmt) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("illegal header, expecting a string literal", pos, script)
+        }
+        _ => diagnosis.diagnosis("expecting the header", pos, script),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+
+    let lit: String = format!("{}", path.lit().unwrap());
+    let lit: &str = &lit[1..lit.len() - 1];
+    if lit.contains(['\\', '{', '}']) {
+        diagnosis.diagnosis("illegal header, expecting a plain path", path.pos, script);
+    }
+    let header: PathBuf = {
+        let script: &Path = diagnosis.path().as_ref();
+        match script.parent() {
+            Some(dir) => dir.join(lit),
+            None => PathBuf::from(lit),
+        }
+    };
+    match header.extension() {
+        Some(ext) if ext.eq_ignore_ascii_case(lits::extensions::H) => {}
+        _ => diagnosis.diagnosis(
+            &format!(
+                "illegal header, expecting a '{}{}' file",
+                lits::DOT,
+                lits::extensions::H
+            ),
+            path.pos,
+            script,
+        ),
+    }
+    if !header.is_file() {
+        diagnosis.diagnosis(
+            &format!("the header '{}' does not exist", lit),
+            path.pos,
+            script,
+        );
+    }
+    // `include "../../usr/include/x.h"`
+    if let Some(src) = diagnosis.src() {
+        let src: &Path = src.as_ref();
+        let inside: bool = match (header.canonicalize(), src.canonicalize()) {
+            (Ok(header), Ok(src)) => header.starts_with(src),
+            _ => false,
+        };
+        if !inside {
+            diagnosis.diagnosis(
+                &format!(
+                    "the header '{}' is not in the '{}' folder of the package",
+                    lit,
+                    src.file_name().unwrap_or_default().to_string_lossy()
+                ),
+                path.pos,
+                script,
+            );
+        }
+    }
+
+    end_of_stmt(script, diagnosis, after(&path));
+
+    cgen::Stmt::Include(Some(cgen::Include { path, header }))
+}
@@ -0,0 +1,26 @@
+use super::end_of_stmt;
+use crate::expr::after;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_script::{
+    iter::{Feature, Mode::Next},
+    Script,
+};
+
+/// It parses the `break` or `continue` statement and obtains the _**cgen data**_.
+pub fn jump(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `break`, `continue`
+    //  ^^^^^    ^^^^^^^^
+    let (token, pos): (Token, Pos) = match script.token(Next(Feature::Code)) {
+        Some(token) => (token.clone(), after(token)),
+        None => return cgen::Stmt::Break(None),
+    };
+    end_of_stmt(script, diagnosis, pos);
+
+    if token.is(&Table::Continue) {
+        cgen::Stmt::Continue(Some(token))
+    } else {
+        cgen::Stmt::Break(Some(token))
+    }
+}
@@ -1,6 +1,35 @@
+pub use constant::constant;
+pub use cpp::cpp;
+pub use defer::defer;
+pub use expression::expression;
+pub use external::external;
+pub use for_loop::for_loop;
 pub use function::function;
+pub use include::include;
+pub use jump::jump;
+pub use ret::ret;
 use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table};
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+pub use variable::variable;
+mod constant;
+mod cpp;
+mod defer;
+mod expression;
+mod external;
+mod for_loop;
 mod function;
+mod include;
+mod jump;
+mod ret;
+mod variable;
 
 pub trait IllegalIndentAccordingTo<'according> {
     /// Adds to the error message a custom extension for the respective statement combinations
@@ -14,17 +43,71 @@ pub trait IllegalIndentAccordingTo<'according> {
 impl<'according> IllegalIndentAccordingTo<'according> for String {
     fn illegal_indent_according_to(
         &'according mut self,
-        parent: &cgen::Stmt,
+        _parent: &cgen::Stmt,
         child: &cgen::Stmt,
     ) -> &'according str {
-        match (parent, child) {
-            // Illegal: indented function.
-            (_, cgen::Stmt::Fn(_)) => {
-                self.push_str(", the function statement must go in the root");
-            }
-            // Default.
-            _ => {}
+        // Illegal: indented function.
+        if let cgen::Stmt::Fn(_) = child {
+            self.push_str(", the function statement must go in the root");
         }
         self
     }
 }
+
+/// Expect the end of the statement (`\n`).
+pub fn end_of_stmt(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>, pos: Pos) {
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::EndOfStmt) => {
+            script.token(Next(Feature::Code)).unwrap();
+        }
+        Some(token) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("expecting newline", pos, script)
+        }
+        None => diagnosis.diagnosis("expecting newline", pos, script),
+    }
+}
+
+/// It recursively parses the statements indented deeper than the parent statement.
+pub fn body(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+    stmt: &cgen::Stmt,
+    indent: usize,
+) -> Vec<cgen::Stmt> {
+    let mut body: Vec<cgen::Stmt> = vec![];
+
+    // Recursive indentation.
+    while let Some(token) = script.token(Peek(Feature::Code)) {
+        // Skip the automatic end of statements.
+        if token.is(&Table::EndOfStmt) {
+            script.token(Next(Feature::Code));
+            continue;
+        }
+
+        if token.pos.grapheme <= indent {
+            break;
+        }
+
+        // Checks for valid statements within the body.
+        if token.is(&Table::Fn)
+            || token.is(&Table::Export)
+            || token.is(&Table::Const)
+                && script
+                    .peek_second(Feature::Code)
+                    .is_some_and(|token| token.is(&Table::Fn))
+        {
+            let pos: Pos = script.token(Peek(Feature::Code)).unwrap().pos;
+            diagnosis.diagnosis(
+                "illegal indentation"
+                    .to_string()
+                    .illegal_indent_according_to(stmt, &cgen::Stmt::Fn(None)),
+                pos,
+                script,
+            );
+        }
+
+        body.push(crate::parser(script, diagnosis, stmt));
+    }
+    body
+}
@@ -0,0 +1,32 @@
+use super::end_of_stmt;
+use crate::expr::{after, expr};
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table};
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the `return` statement and obtains the _**cgen data**_.
+pub fn ret(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `return expr`
+    //  ^^^^^^
+    let (ret, pos): (Pos, Pos) = match script.token(Next(Feature::Code)) {
+        Some(token) => (token.pos, after(token)),
+        None => return cgen::Stmt::Return(None),
+    };
+
+    // `return expr`
+    //         ^^^^
+    let value: Option<cgen::Expr> = match script.token(Peek(Feature::Code)) {
+        Some(token) if !token.is(&Table::EndOfStmt) => Some(expr(script, diagnosis)),
+        _ => None,
+    };
+    end_of_stmt(script, diagnosis, pos);
+
+    cgen::Stmt::Return(Some(cgen::Return { pos: ret, value }))
+}
@@ -0,0 +1,74 @@
+use super::end_of_stmt;
+use crate::{
+    expr::{after, expr},
+    ty::ty,
+};
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the variable statement and obtains the _**cgen data**_.
+pub fn variable(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>) -> cgen::Stmt {
+    // `var name: type = expr`
+    //  ^^^
+    let mut pos: Pos = match script.token(Next(Feature::Code)) {
+        Some(token) => after(token),
+        None => return cgen::Stmt::Var(None),
+    };
+
+    // `var name: type = expr`
+    //      ^^^^
+    let name: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if token.is(&Table::Id(None)) => token.clone(),
+        Some(token) if !token.is(&Table::EndOfStmt) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("illegal variable name", pos, script)
+        }
+        _ => diagnosis.diagnosis("expecting variable name", pos, script),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+    pos = after(&name);
+
+    // `var name: type = expr`
+    //          ^^^^^^
+    let mut var_ty: Option<cgen::Type> = None;
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::ColonSym) {
+            pos = after(token);
+            script.token(Next(Feature::Code)).unwrap();
+            var_ty = Some(ty(script, diagnosis, pos));
+        }
+    }
+
+    // `var name: type = expr`
+    //                 ^^^^^^
+    let mut value: Option<cgen::Expr> = None;
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::AssignSym) {
+            script.token(Next(Feature::Code)).unwrap();
+            value = Some(expr(script, diagnosis));
+        }
+    }
+    if var_ty.is_none() && value.is_none() {
+        diagnosis.diagnosis(
+            "expecting the type or the value of the variable",
+            pos,
+            script,
+        );
+    }
+
+    end_of_stmt(script, diagnosis, pos);
+
+    cgen::Stmt::Var(Some(cgen::Var {
+        name,
+        ty: var_ty,
+        value,
+    }))
+}
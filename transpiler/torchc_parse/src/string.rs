@@ -0,0 +1,145 @@
+use crate::expr::expr;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_script::{
+    iter::{Feature, Mode::Peek},
+    Script,
+};
+
+/// Escape sequences allowed in string literals (`\n`, `\t`, ...).
+const ESCAPES: [char; 7] = ['n', 't', 'r', '0', '\\', '"', '\''];
+
+/// It parses the string literal and its interpolations (`"...{expr}..."`) and
+/// obtains the _**cgen data**_.
+///
+/// ---
+/// > _`{{` and `}}` are the literal braces._
+pub fn string(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+    token: Token,
+) -> cgen::Expr {
+    let lit: String = format!("{}", token.lit().unwrap());
+    let mut chars = lit.chars().peekable();
+    // Position of the current character.
+    let mut pos: Pos = token.pos;
+
+    // `"...{expr}..."`
+    //  ^
+    chars.next();
+    let mut pieces: Vec<cgen::Expr> = vec![];
+    let mut piece: String = String::new();
+    let mut piece_pos: Pos = pos;
+    loop {
+        let c: char = match chars.next() {
+            Some(c) => pos.advance(c),
+            None => diagnosis.diagnosis("unterminated string literal", token.pos, script),
+        };
+        match c {
+            '"' => break,
+            '\\' => {
+                let escape_pos: Pos = pos;
+                match chars.next() {
+                    Some(c) if ESCAPES.contains(&c) => {
+                        piece.push('\\');
+                        piece.push(pos.advance(c));
+                    }
+                    _ => diagnosis.diagnosis("illegal escape sequence", escape_pos, script),
+                }
+            }
+            // `{{`
+            '{' if chars.peek() == Some(&'{') => {
+                pos.advance(chars.next().unwrap());
+                piece.push('{');
+            }
+            // `}}`
+            '}' if chars.peek() == Some(&'}') => {
+                pos.advance(chars.next().unwrap());
+                piece.push('}');
+            }
+            '}' => diagnosis.diagnosis(
+                "illegal '}' in string literal, the literal brace is '}}'",
+                pos,
+                script,
+            ),
+            // `{expr}`
+            '{' => {
+                let open: Pos = pos;
+                let mut contents: String = String::new();
+                let mut nested: bool = false; // Inside a nested string literal?
+                loop {
+                    match chars.next() {
+                        Some('}') if !nested => {
+                            pos.advance('}');
+                            break;
+                        }
+                        Some('"') => {
+                            nested = !nested;
+                            contents.push(pos.advance('"'));
+                        }
+                        Some('\\') if nested => {
+                            contents.push(pos.advance('\\'));
+                            if let Some(c) = chars.next() {
+                                contents.push(pos.advance(c));
+                            }
+                        }
+                        Some(c) => contents.push(pos.advance(c)),
+                        None => diagnosis.diagnosis("expecting '}'", open, script),
+                    }
+                }
+
+                if !piece.is_empty() {
+                    pieces.push(piece_lit(&piece, piece_pos));
+                    piece.clear();
+                }
+                pieces.push(interpolation(diagnosis, &contents, open));
+                piece_pos = pos;
+            }
+            c => piece.push(c),
+        }
+    }
+
+    // Without interpolations it is a simple literal.
+    if pieces.is_empty() {
+        return piece_lit(&piece, token.pos);
+    }
+    if !piece.is_empty() {
+        pieces.push(piece_lit(&piece, piece_pos));
+    }
+    cgen::Expr::Format(pieces)
+}
+
+/// String literal of a piece of the string (`"..."`).
+fn piece_lit(piece: &str, pos: Pos) -> cgen::Expr {
+    let mut token: Token = Token::new();
+    token.lexeme = Table::StringLit(Some(
+        format!("\"{}\"", piece).into_bytes().into_boxed_slice(),
+    ));
+    token.pos = pos;
+    cgen::Expr::Lit(token)
+}
+
+/// `{expr}`
+///
+/// The expression is parsed as a script nested in the string literal, its diagnostics
+/// are reported in the line of the literal.
+fn interpolation(diagnosis: &mut panic::Diagnosis<'_>, contents: &str, open: Pos) -> cgen::Expr {
+    let mut script: Script = Script::from_contents(contents);
+    script.offset(open);
+    let diagnosis: &mut panic::Diagnosis = &mut diagnosis.nested();
+
+    if let Some(token) = script.token(Peek(Feature::Code)) {
+        if token.is(&Table::EndOfStmt) {
+            diagnosis.diagnosis("expecting expression", open, &mut script);
+        }
+    }
+    let expr: cgen::Expr = expr(&mut script, diagnosis);
+    match script.token(Peek(Feature::Code)) {
+        Some(token) if !token.is(&Table::EndOfStmt) => {
+            let pos: Pos = token.pos;
+            diagnosis.diagnosis("expecting '}'", pos, &mut script)
+        }
+        _ => expr,
+    }
+}
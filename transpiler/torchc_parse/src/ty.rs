@@ -0,0 +1,263 @@
+use crate::expr::after;
+use torchc_cgen::cgen;
+use torchc_diagnosis::panic;
+use torchc_lex::{Pos, Table, Token};
+use torchc_lits::lits;
+use torchc_script::{
+    iter::{
+        Feature,
+        Mode::{Next, Peek},
+    },
+    Script,
+};
+
+/// It parses the type and obtains the _**cgen data**_.
+///
+/// ---
+/// `name`, `[n]type`, `[]type`, `list[type]`, `result[type, type]`, `option[type]`
+pub fn ty(script: &mut Script, diagnosis: &mut panic::Diagnosis<'_>, pos: Pos) -> cgen::Type {
+    let token: Token = match script.token(Peek(Feature::Code)) {
+        Some(token) if !token.is(&Table::EndOfStmt) => token.clone(),
+        _ => diagnosis.diagnosis("expecting type", pos, script),
+    };
+    script.token(Next(Feature::Code)).unwrap();
+
+    match &token.lexeme {
+        // `[n]type`, `[]type`
+        //  ^          ^
+        Table::OpenBracketSym => {
+            let len: Option<usize> = match script.token(Peek(Feature::Code)) {
+                Some(token) if token.is(&Table::CloseBracketSym) => None,
+                Some(token) if token.is(&Table::IntLit(None)) => {
+                    let pos: Pos = token.pos;
+                    let len: Option<usize> = format!("{}", token.lit().unwrap()).parse().ok();
+                    script.token(Next(Feature::Code)).unwrap();
+                    match len {
+                        Some(len) => Some(len),
+                        None => diagnosis.diagnosis("illegal array length", pos, script),
+                    }
+                }
+                _ => diagnosis.diagnosis("expecting array length or ']'", after(&token), script),
+            };
+
+            // `[n]type`
+            //    ^
+            let close: Token = match script.token(Peek(Feature::Code)) {
+                Some(token) if token.is(&Table::CloseBracketSym) => token.clone(),
+                Some(token) => {
+                    let pos: Pos = token.pos;
+                    diagnosis.diagnosis("expecting ']'", pos, script)
+                }
+                None => diagnosis.diagnosis("expecting ']'", after(&token), script),
+            };
+            script.token(Next(Feature::Code)).unwrap();
+
+            let item: Box<cgen::Type> = Box::new(ty(script, diagnosis, after(&close)));
+            match len {
+                Some(len) => cgen::Type::Array(item, len),
+                None => cgen::Type::Slice(item),
+            }
+        }
+        Table::Id(_) => {
+            // `list[type]`, `result[type, type]`, `option[type]`
+            //      ^              ^                     ^
+            let name: String = format!("{}", token.lit().unwrap());
+            match name.as_str() {
+                lits::types::LIST => {
+                    let [item] = args(script, diagnosis, &token);
+                    cgen::Type::List(Box::new(item))
+                }
+                lits::types::RESULT => {
+                    let [value, error] = args(script, diagnosis, &token);
+                    cgen::Type::Result(Box::new(value), Box::new(error))
+                }
+                lits::types::OPTION => {
+                    let [value] = args(script, diagnosis, &token);
+                    cgen::Type::Option(Box::new(value))
+                }
+                _ => cgen::Type::Id(token),
+            }
+        }
+        _ => diagnosis.diagnosis("illegal type", token.pos, script),
+    }
+}
+
+/// Type arguments of the generic type (`name[type, type, ...]`).
+fn args<const N: usize>(
+    script: &mut Script,
+    diagnosis: &mut panic::Diagnosis<'_>,
+    name: &Token,
+) -> [cgen::Type; N] {
+    match script.token(Peek(Feature::Code)) {
+        Some(open) if open.is(&Table::OpenBracketSym) => {
+            script.token(Next(Feature::Code)).unwrap();
+        }
+        _ => diagnosis.diagnosis(
+            &format!("expecting '[' after '{}'", name.lit().unwrap()),
+            after(name),
+            script,
+        ),
+    }
+
+    let mut args: Vec<cgen::Type> = vec![];
+    let mut pos: Pos = after(name);
+    for i in 0..N {
+        if i > 0 {
+            // `name[type, type]`
+            //           ^
+            match script.token(Peek(Feature::Code)) {
+                Some(comma) if comma.is(&Table::CommaSym) => {
+                    pos = after(comma);
+                    script.token(Next(Feature::Code)).unwrap();
+                }
+                Some(token) => {
+                    let pos: Pos = token.pos;
+                    diagnosis.diagnosis("expecting ','", pos, script)
+                }
+                None => diagnosis.diagnosis("expecting ','", pos, script),
+            }
+        }
+        args.push(ty(script, diagnosis, pos));
+    }
+
+    match script.token(Peek(Feature::Code)) {
+        Some(close) if close.is(&Table::CloseBracketSym) => {
+            script.token(Next(Feature::Code)).unwrap();
+        }
+        Some(close) => {
+            let pos: Pos = close.pos;
+            diagnosis.diagnosis("expecting ']'", pos, script)
+        }
+        None => diagnosis.diagnosis("expecting ']'", pos, script),
+    }
+    match args.try_into() {
+        Ok(args) => args,
+        Err(_) => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use std::path::Path;
+
+    /// Catch the diagnostic of the parser, if any.
+    fn diagnostic<T>(
+        parse: impl FnOnce(&mut Script, &mut panic::Diagnosis) -> T,
+    ) -> Result<T, String> {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut script: Script = Script::from_contents("");
+            let mut diagnosis: panic::Diagnosis =
+                panic::Diagnosis::new(Path::new("main.t").into(), Path::new(".").into());
+            parse(&mut script, &mut diagnosis)
+        }))
+        .map_err(|err| err.downcast::<String>().map_or(String::new(), |msg| *msg))
+    }
+    /// Type of the code in the syntax of the language.
+    fn parse(code: &str) -> Result<String, String> {
+        diagnostic(|script, diagnosis| {
+            *script = Script::from_contents(code);
+            show(&ty(script, diagnosis, Pos::default()))
+        })
+    }
+    fn show(ty: &cgen::Type) -> String {
+        match ty {
+            cgen::Type::Id(id) => format!("{}", id.lit().unwrap()),
+            cgen::Type::Array(item, len) => format!("[{}]{}", len, show(item)),
+            cgen::Type::Slice(item) => format!("[]{}", show(item)),
+            cgen::Type::List(item) => format!("list[{}]", show(item)),
+            cgen::Type::Result(value, error) => {
+                format!("result[{}, {}]", show(value), show(error))
+            }
+            cgen::Type::Option(value) => format!("option[{}]", show(value)),
+        }
+    }
+
+    #[test]
+    fn types() {
+        for code in [
+            "i64",
+            "[3]i64",
+            "[]str",
+            "list[f64]",
+            "result[i64, str]",
+            "option[char]",
+            "list[[]option[result[[2]u8, str]]]",
+        ] {
+            assert_eq!(parse(code).as_deref(), Ok(code));
+        }
+        assert_eq!(
+            parse("result[ i64 ,str ]").as_deref(),
+            Ok("result[i64, str]")
+        );
+    }
+
+    #[test]
+    fn illegal_types() {
+        for (code, msg) in [
+            ("", "expecting type"),
+            ("[x]i64", "expecting array length or ']'"),
+            ("[3 i64", "expecting ']'"),
+            ("list i64", "expecting '[' after 'list'"),
+            ("list[i64", "expecting ']'"),
+            ("result[i64]", "expecting ','"),
+            ("option[i64, str]", "expecting ']'"),
+            ("list[]", "illegal type"),
+        ] {
+            let diagnostic: String = parse(code).unwrap_err();
+            assert!(diagnostic.contains(msg), "{}: {}", code, diagnostic);
+        }
+    }
+
+    #[test]
+    fn signatures() {
+        let globals: cgen::Stmt = diagnostic(|script, diagnosis| {
+            *script = Script::from_contents(
+                "fn first(items: []i64, n: i32) option[i64]\n    return none\n\nfn run()\n    return\n",
+            );
+            parser(script, diagnosis, &cgen::Stmt::Global(None))
+        })
+        .unwrap();
+        let cgen::Stmt::Global(Some(globals)) = globals else {
+            panic!("expecting the statements of the script");
+        };
+        let sigs: Vec<String> = globals
+            .iter()
+            .filter_map(|stmt| match stmt {
+                cgen::Stmt::Fn(Some(fn_stmt)) => Some(format!(
+                    "{}({}){}",
+                    fn_stmt.name.lit().unwrap(),
+                    fn_stmt
+                        .params
+                        .iter()
+                        .map(|param| format!("{}: {}", param.name.lit().unwrap(), show(&param.ty)))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    fn_stmt
+                        .ret
+                        .as_ref()
+                        .map_or(String::new(), |ret| format!(" {}", show(ret)))
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sigs, ["first(items: []i64, n: i32) option[i64]", "run()"]);
+
+        for (code, msg) in [
+            (
+                "fn f(a) i64\n    return 1\n",
+                "expecting ':' after the parameter name",
+            ),
+            ("fn f(a: i64 b: i64)\n    return\n", "expecting ',' or ')'"),
+            ("fn f(a: list[i64)\n    return\n", "expecting ']'"),
+        ] {
+            let diagnostic: String = diagnostic(|script, diagnosis| {
+                *script = Script::from_contents(code);
+                parser(script, diagnosis, &cgen::Stmt::Global(None));
+            })
+            .unwrap_err();
+            assert!(diagnostic.contains(msg), "{}: {}", code, diagnostic);
+        }
+    }
+}
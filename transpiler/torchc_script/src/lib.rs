@@ -3,7 +3,7 @@ use self::iter::{
     Mode::{Next, Peek},
 };
 use async_std::{fs, io, path::Path};
-use torchc_lex::{lexer, Table, ToScript, Token};
+use torchc_lex::{lexer, Pos, Table, ToScript, Token};
 
 pub mod iter {
     #[derive(Debug)]
@@ -63,29 +63,56 @@ impl Script {
         None
     }
 
+    /// Obtain the token after the next one without advancing.
+    pub fn peek_second(&mut self, ft: Feature) -> Option<Token> {
+        let i: usize = self.i;
+        self.token(Next(ft));
+        let token: Option<Token> = self.token(Peek(ft)).cloned();
+        self.i = i;
+        token
+    }
+
     /// Interpret the script by tokens.
     pub async fn script(path: &Path) -> io::Result<Script> {
-        Ok({
+        Ok(Self::from_contents(&fs::read_to_string(path).await?))
+    }
+    /// Interpret the contents of a script by tokens.
+    pub fn from_contents(contents: &str) -> Script {
+        {
+            let contents: String = contents.to_string();
+            let mut script: torchc_lex::Script = contents.to_script();
+            let mut tokens: Vec<Token> = vec![];
+            while let Some(token) = lexer(&mut script) {
+                tokens.push(token);
+            }
+            // Replace `EOF` with `\n` (automatic end of statement).
+            if !tokens
+                .last()
+                .is_some_and(|token| token.is(&Table::EndOfStmt))
             {
-                let contents: String = fs::read_to_string(path).await?;
-                let mut script: torchc_lex::Script = contents.to_script();
-                let mut tokens: Vec<Token> = vec![];
-                while let Some(token) = lexer(&mut script) {
-                    tokens.push(token);
-                }
-                // Replace `EOF` with `\n` (automatic end of statement).
-                if !tokens[tokens.len() - 1].is(&Table::EndOfStmt) {
-                    let mut token: Token = Token::new();
-                    token.pos = tokens[tokens.len() - 1].pos;
-                    token.pos.grapheme = script.pos.grapheme + 1;
-                    token.lexeme = Table::EndOfStmt;
-                    tokens.push(token);
-                }
-                Script { tokens, i: 0 }
+                let mut token: Token = Token::new();
+                token.pos = match tokens.last() {
+                    Some(last) => last.pos,
+                    None => script.pos,
+                };
+                token.pos.grapheme = script.pos.grapheme + 1;
+                token.lexeme = Table::EndOfStmt;
+                tokens.push(token);
+            }
+            Script { tokens, i: 0 }
+        }
+        .retokenizer()
+        .filter()
+    }
+    /// Move the positions of all the tokens as if the script started at `pos`
+    /// (_for scripts nested in other scripts, such as string interpolations_).
+    pub fn offset(&mut self, pos: Pos) {
+        for token in &mut self.tokens {
+            if token.pos.line == 1 {
+                token.pos.grapheme += pos.grapheme;
             }
-            .retokenizer()
+            token.pos.line += pos.line - 1;
         }
-        .filter())
     }
     /// Restructures the most complex tokens.
     fn retokenizer(&mut self) -> Self {